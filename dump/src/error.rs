@@ -7,6 +7,10 @@ pub enum Error {
     BadIndexName,
     #[error("Malformed task.")]
     MalformedTask,
+    #[error("The dump version `{0}` is not supported. Only the dumps from `V1` to `V6` can be imported.")]
+    UnsupportedVersion(String),
+    #[error("The file is not a valid dump. A dump must be a gzipped tarball containing a `metadata.json` file.")]
+    InvalidDump,
 
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -28,6 +32,8 @@ impl ErrorCode for Error {
             // all these errors should never be raised when creating a dump, thus no error code should be associated.
             Error::BadIndexName => Code::Internal,
             Error::MalformedTask => Code::Internal,
+
+            Error::UnsupportedVersion(_) | Error::InvalidDump => Code::InvalidDump,
         }
     }
 }
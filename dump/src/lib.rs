@@ -3,9 +3,11 @@
 
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{IndexDocumentsMethod, Setting};
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, DocumentEdition, IndexSwap, KindWithContent, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -16,7 +18,7 @@ mod reader;
 mod writer;
 
 pub use error::Error;
-pub use reader::{DumpReader, UpdateFile};
+pub use reader::{DumpIndexReader, DumpReader, UpdateFile};
 pub use writer::DumpWriter;
 
 const CURRENT_DUMP_VERSION: Version = Version::V6;
@@ -37,6 +39,9 @@ pub struct Metadata {
 pub struct IndexMetadata {
     pub uid: String,
     pub primary_key: Option<String>,
+    /// The maximum size of the index, in bytes, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<u64>,
     #[serde(with = "time::serde::rfc3339")]
     pub created_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339")]
@@ -101,6 +106,10 @@ pub enum KindDump {
         documents_ids: Vec<String>,
     },
     DocumentClear,
+    DocumentEdition {
+        filter_expr: String,
+        edition: DocumentEdition,
+    },
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
         is_deletion: bool,
@@ -109,9 +118,13 @@ pub enum KindDump {
     IndexDeletion,
     IndexCreation {
         primary_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+        max_size: Setting<u64>,
     },
     IndexUpdate {
         primary_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+        max_size: Setting<u64>,
     },
     IndexSwap {
         swaps: Vec<IndexSwap>,
@@ -129,6 +142,12 @@ pub enum KindDump {
         instance_uid: Option<InstanceUid>,
     },
     SnapshotCreation,
+    DumpImport,
+    /// The `SettingsDelta` of the import is the only object of its update file.
+    SettingsImport {
+        added_entries: u64,
+        removed_entries: u64,
+    },
 }
 
 impl From<Task> for TaskDump {
@@ -167,6 +186,9 @@ impl From<KindWithContent> for KindDump {
                 KindDump::DocumentDeletion { documents_ids }
             }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
+            KindWithContent::DocumentEdition { filter_expr, edition, .. } => {
+                KindDump::DocumentEdition { filter_expr, edition }
+            }
             KindWithContent::SettingsUpdate {
                 new_settings,
                 is_deletion,
@@ -174,11 +196,11 @@ impl From<KindWithContent> for KindDump {
                 ..
             } => KindDump::Settings { settings: new_settings, is_deletion, allow_index_creation },
             KindWithContent::IndexDeletion { .. } => KindDump::IndexDeletion,
-            KindWithContent::IndexCreation { primary_key, .. } => {
-                KindDump::IndexCreation { primary_key }
+            KindWithContent::IndexCreation { primary_key, max_size, .. } => {
+                KindDump::IndexCreation { primary_key, max_size }
             }
-            KindWithContent::IndexUpdate { primary_key, .. } => {
-                KindDump::IndexUpdate { primary_key }
+            KindWithContent::IndexUpdate { primary_key, max_size, .. } => {
+                KindDump::IndexUpdate { primary_key, max_size }
             }
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
             KindWithContent::TaskCancelation { query, tasks } => {
@@ -191,6 +213,10 @@ impl From<KindWithContent> for KindDump {
                 KindDump::DumpCreation { keys, instance_uid }
             }
             KindWithContent::SnapshotCreation => KindDump::SnapshotCreation,
+            KindWithContent::DumpImport { .. } => KindDump::DumpImport,
+            KindWithContent::SettingsImport { added_entries, removed_entries, .. } => {
+                KindDump::SettingsImport { added_entries, removed_entries }
+            }
         }
     }
 }
@@ -203,12 +229,11 @@ pub(crate) mod test {
 
     use big_s::S;
     use maplit::btreeset;
-    use meilisearch_types::index_uid::IndexUid;
+    use meilisearch_types::index_uid_pattern::IndexUidPattern;
     use meilisearch_types::keys::{Action, Key};
     use meilisearch_types::milli::update::Setting;
     use meilisearch_types::milli::{self};
     use meilisearch_types::settings::{Checked, Settings};
-    use meilisearch_types::star_or::StarOr;
     use meilisearch_types::tasks::{Details, Status};
     use serde_json::{json, Map, Value};
     use time::macros::datetime;
@@ -225,6 +250,7 @@ pub(crate) mod test {
         IndexMetadata {
             uid: S("doggo"),
             primary_key: None,
+            max_size: None,
             created_at: datetime!(2022-11-20 12:00 UTC),
             updated_at: datetime!(2022-11-21 00:00 UTC),
         }
@@ -282,6 +308,8 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        rejected_documents: Vec::new(),
+                        rejected_documents_count: 0,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -305,6 +333,8 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        rejected_documents: Vec::new(),
+                        rejected_documents_count: 0,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -341,7 +371,8 @@ pub(crate) mod test {
                 name: Some(S("doggos_key")),
                 uid: Uuid::from_str("9f8a34da-b6b2-42f0-939b-dbd4c3448655").unwrap(),
                 actions: vec![Action::DocumentsAll],
-                indexes: vec![StarOr::Other(IndexUid::from_str("doggos").unwrap())],
+                indexes: vec![IndexUidPattern::from_str("doggos").unwrap()],
+                filter: None,
                 expires_at: Some(datetime!(4130-03-14 12:21 UTC)),
                 created_at: datetime!(1960-11-15 0:00 UTC),
                 updated_at: datetime!(2022-11-10 0:00 UTC),
@@ -351,7 +382,8 @@ pub(crate) mod test {
                 name: Some(S("master_key")),
                 uid: Uuid::from_str("4622f717-1c00-47bb-a494-39d76a49b591").unwrap(),
                 actions: vec![Action::All],
-                indexes: vec![StarOr::Star],
+                indexes: vec![IndexUidPattern::all()],
+                filter: None,
                 expires_at: None,
                 created_at: datetime!(0000-01-01 00:01 UTC),
                 updated_at: datetime!(1964-05-04 17:25 UTC),
@@ -362,6 +394,7 @@ pub(crate) mod test {
                 uid: Uuid::from_str("fb80b58b-0a34-412f-8ba7-1ce868f8ac5c").unwrap(),
                 actions: vec![],
                 indexes: vec![],
+                filter: None,
                 expires_at: None,
                 created_at: datetime!(400-02-29 0:00 UTC),
                 updated_at: datetime!(1024-02-29 0:00 UTC),
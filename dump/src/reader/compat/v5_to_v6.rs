@@ -77,10 +77,10 @@ impl CompatV5ToV6 {
                     },
                     kind: match task.content {
                         v5::tasks::TaskContent::IndexCreation { primary_key, .. } => {
-                            v6::Kind::IndexCreation { primary_key }
+                            v6::Kind::IndexCreation { primary_key, max_size: v6::Setting::NotSet }
                         }
                         v5::tasks::TaskContent::IndexUpdate { primary_key, .. } => {
-                            v6::Kind::IndexUpdate { primary_key }
+                            v6::Kind::IndexUpdate { primary_key, max_size: v6::Setting::NotSet }
                         }
                         v5::tasks::TaskContent::IndexDeletion { .. } => v6::Kind::IndexDeletion,
                         v5::tasks::TaskContent::DocumentAddition {
@@ -130,13 +130,15 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                rejected_documents: Vec::new(),
+                                rejected_documents_count: 0,
                             }
                         }
                         v5::Details::Settings { settings } => {
                             v6::Details::SettingsUpdate { settings: Box::new(settings.into()) }
                         }
                         v5::Details::IndexInfo { primary_key } => {
-                            v6::Details::IndexInfo { primary_key }
+                            v6::Details::IndexInfo { primary_key, max_size: v6::Setting::NotSet }
                         }
                         v5::Details::DocumentDeletion {
                             received_document_ids,
@@ -179,12 +181,11 @@ impl CompatV5ToV6 {
                     .indexes
                     .into_iter()
                     .map(|index| match index {
-                        v5::StarOr::Star => v6::StarOr::Star,
-                        v5::StarOr::Other(uid) => {
-                            v6::StarOr::Other(v6::IndexUid::new_unchecked(uid.as_str()))
-                        }
+                        v5::StarOr::Star => v6::IndexUidPattern::all(),
+                        v5::StarOr::Other(uid) => v6::IndexUidPattern::new_unchecked(uid.as_str()),
                     })
                     .collect(),
+                filter: None,
                 expires_at: key.expires_at,
                 created_at: key.created_at,
                 updated_at: key.updated_at,
@@ -1,5 +1,6 @@
 use std::fs::File;
 use std::io::{BufReader, Read};
+use std::path::Path;
 
 use flate2::bufread::GzDecoder;
 use serde::Deserialize;
@@ -9,7 +10,7 @@ use self::compat::v4_to_v5::CompatV4ToV5;
 use self::compat::v5_to_v6::{CompatIndexV5ToV6, CompatV5ToV6};
 use self::v5::V5Reader;
 use self::v6::{V6IndexReader, V6Reader};
-use crate::{Result, Version};
+use crate::{Error, Result, Version};
 
 mod compat;
 
@@ -23,6 +24,20 @@ pub(self) mod v6;
 pub type Document = serde_json::Map<String, serde_json::Value>;
 pub type UpdateFile = dyn Iterator<Item = Result<Document>>;
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetadataVersion {
+    pub dump_version: String,
+}
+
+impl MetadataVersion {
+    fn version(self) -> Result<Version> {
+        let MetadataVersion { dump_version } = self;
+        serde_json::from_value(serde_json::Value::String(dump_version.clone()))
+            .map_err(|_| Error::UnsupportedVersion(dump_version))
+    }
+}
+
 pub enum DumpReader {
     Current(V6Reader),
     Compat(CompatV5ToV6),
@@ -36,15 +51,10 @@ impl DumpReader {
         let mut archive = tar::Archive::new(gz);
         archive.unpack(path.path())?;
 
-        #[derive(Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct MetadataVersion {
-            pub dump_version: Version,
-        }
         let mut meta_file = File::open(path.path().join("metadata.json"))?;
-        let MetadataVersion { dump_version } = serde_json::from_reader(&mut meta_file)?;
+        let metadata: MetadataVersion = serde_json::from_reader(&mut meta_file)?;
 
-        match dump_version {
+        match metadata.version()? {
             Version::V1 => {
                 Ok(v1::V1Reader::open(path)?.to_v2().to_v3().to_v4().to_v5().to_v6().into())
             }
@@ -56,6 +66,26 @@ impl DumpReader {
         }
     }
 
+    /// Returns the version of a dump, without unpacking it, if it can be imported.
+    pub fn read_version(dump: impl Read) -> Result<Version> {
+        let mut dump = BufReader::new(dump);
+        let gz = GzDecoder::new(&mut dump);
+        let mut archive = tar::Archive::new(gz);
+
+        // Anything that can't be read is not a dump in the first place.
+        for entry in archive.entries().map_err(|_| Error::InvalidDump)? {
+            let entry = entry.map_err(|_| Error::InvalidDump)?;
+            let path = entry.path().map_err(|_| Error::InvalidDump)?.into_owned();
+            if path.strip_prefix(".").unwrap_or(&path) == Path::new("metadata.json") {
+                let metadata: MetadataVersion =
+                    serde_json::from_reader(entry).map_err(|_| Error::InvalidDump)?;
+                return metadata.version();
+            }
+        }
+
+        Err(Error::InvalidDump)
+    }
+
     pub fn version(&self) -> crate::Version {
         match self {
             DumpReader::Current(current) => current.version(),
@@ -189,6 +219,23 @@ pub(crate) mod test {
 
     use super::*;
 
+    #[test]
+    fn read_dump_version() {
+        for (path, version) in [
+            ("tests/assets/v1.dump", Version::V1),
+            ("tests/assets/v2.dump", Version::V2),
+            ("tests/assets/v3.dump", Version::V3),
+            ("tests/assets/v4.dump", Version::V4),
+            ("tests/assets/v5.dump", Version::V5),
+        ] {
+            let dump = File::open(path).unwrap();
+            assert_eq!(DumpReader::read_version(dump).unwrap(), version);
+        }
+
+        let error = DumpReader::read_version(&b"not a dump"[..]).unwrap_err();
+        assert!(matches!(error, Error::InvalidDump), "{error}");
+    }
+
     #[test]
     fn import_dump_v5() {
         let dump = File::open("tests/assets/v5.dump").unwrap();
@@ -154,6 +154,7 @@ impl From<Index> for IndexMetadataV1 {
             metadata: crate::IndexMetadata {
                 uid: index.uid,
                 primary_key: index.primary_key,
+                max_size: None,
                 created_at: index.created_at,
                 updated_at: index.updated_at,
             },
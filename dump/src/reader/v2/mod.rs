@@ -149,6 +149,7 @@ impl V2IndexReader {
         let metadata = IndexMetadata {
             uid: name,
             primary_key: meta.primary_key,
+            max_size: None,
             // FIXME: Iterate over the whole task queue to find the creation and last update date.
             created_at: OffsetDateTime::now_utc(),
             updated_at: OffsetDateTime::now_utc(),
@@ -22,7 +22,9 @@ enum AutobatchKind {
     DocumentImport { method: IndexDocumentsMethod, allow_index_creation: bool },
     DocumentDeletion,
     DocumentClear,
+    DocumentEdition,
     Settings { allow_index_creation: bool },
+    SettingsImport,
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
@@ -48,11 +50,13 @@ impl From<KindWithContent> for AutobatchKind {
             }
             KindWithContent::DocumentDeletion { .. } => AutobatchKind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => AutobatchKind::DocumentClear,
+            KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentEdition,
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
                 AutobatchKind::Settings {
                     allow_index_creation: allow_index_creation && !is_deletion,
                 }
             }
+            KindWithContent::SettingsImport { .. } => AutobatchKind::SettingsImport,
             KindWithContent::IndexDeletion { .. } => AutobatchKind::IndexDeletion,
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
@@ -60,6 +64,7 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
+            | KindWithContent::DumpImport { .. }
             | KindWithContent::SnapshotCreation => {
                 panic!("The autobatcher should never be called with tasks that don't apply to an index.")
             }
@@ -80,6 +85,12 @@ pub enum BatchKind {
     DocumentDeletion {
         deletion_ids: Vec<TaskId>,
     },
+    DocumentEdition {
+        id: TaskId,
+    },
+    SettingsImport {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -139,6 +150,10 @@ impl BatchKind {
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
+            // The edited documents depend on the tasks processed before, it can't be batched.
+            K::DocumentEdition => (Break(BatchKind::DocumentEdition { id: task_id }), false),
+            // The import is merged into the settings left by the tasks processed before.
+            K::SettingsImport => (Break(BatchKind::SettingsImport { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { method, allow_index_creation } => (
                 Continue(BatchKind::DocumentImport {
@@ -168,7 +183,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentEdition | K::SettingsImport) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -384,7 +399,9 @@ impl BatchKind {
                 BatchKind::IndexCreation { .. }
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
-                | BatchKind::IndexSwap { .. },
+                | BatchKind::IndexSwap { .. }
+                | BatchKind::DocumentEdition { .. }
+                | BatchKind::SettingsImport { .. },
                 _,
             ) => {
                 unreachable!()
@@ -433,6 +450,7 @@ pub fn autobatch(
 
 #[cfg(test)]
 mod tests {
+    use meilisearch_types::milli::update::Setting;
     use meilisearch_types::tasks::IndexSwap;
     use uuid::Uuid;
 
@@ -480,12 +498,37 @@ mod tests {
         }
     }
 
+    fn doc_edit() -> KindWithContent {
+        KindWithContent::DocumentEdition {
+            index_uid: String::from("doggo"),
+            filter_expr: String::from("age > 2"),
+            edition: Default::default(),
+        }
+    }
+
+    fn settings_import() -> KindWithContent {
+        KindWithContent::SettingsImport {
+            index_uid: String::from("doggo"),
+            content_file: Uuid::new_v4(),
+            added_entries: 0,
+            removed_entries: 0,
+        }
+    }
+
     fn idx_create() -> KindWithContent {
-        KindWithContent::IndexCreation { index_uid: String::from("doggo"), primary_key: None }
+        KindWithContent::IndexCreation {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            max_size: Setting::NotSet,
+        }
     }
 
     fn idx_update() -> KindWithContent {
-        KindWithContent::IndexUpdate { index_uid: String::from("doggo"), primary_key: None }
+        KindWithContent::IndexUpdate {
+            index_uid: String::from("doggo"),
+            primary_key: None,
+            max_size: Setting::NotSet,
+        }
     }
 
     fn idx_del() -> KindWithContent {
@@ -567,6 +610,25 @@ mod tests {
         debug_snapshot!(autobatch_from(true, [doc_del(), idx_swap()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
     }
 
+    #[test]
+    fn document_edition_is_never_batched() {
+        debug_snapshot!(autobatch_from(true, [doc_edit()]), @"Some((DocumentEdition { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_edit(), doc_edit()]), @"Some((DocumentEdition { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_edit(), doc_imp(ReplaceDocuments, true)]), @"Some((DocumentEdition { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), doc_edit()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_del(), doc_edit()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, [settings(true), doc_edit()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_clr(), doc_edit()]), @"Some((DocumentClear { ids: [0] }, false))");
+    }
+
+    #[test]
+    fn settings_import_is_never_batched() {
+        debug_snapshot!(autobatch_from(true, [settings_import()]), @"Some((SettingsImport { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [settings_import(), settings(true)]), @"Some((SettingsImport { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, [settings(true), settings_import()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, [doc_imp(ReplaceDocuments, true), settings_import()]), @"Some((DocumentImport { method: ReplaceDocuments, allow_index_creation: true, import_ids: [0] }, true))");
+    }
+
     #[test]
     fn document_addition_batch_with_settings() {
         // simple case
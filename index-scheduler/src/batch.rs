@@ -19,28 +19,34 @@ one indexing operation.
 
 use std::collections::{BTreeSet, HashSet};
 use std::ffi::OsStr;
-use std::fs::{self, File};
-use std::io::BufWriter;
+use std::fs;
+use std::io::{BufReader, BufWriter, IntoInnerError};
 
 use dump::IndexMetadata;
 use log::{debug, error, info};
-use meilisearch_types::heed::{RoTxn, RwTxn};
-use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::heed::{self, MdbError, RoTxn, RwTxn};
+use meilisearch_types::milli::documents::{
+    obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
     DocumentAdditionResult, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    Setting, Settings as MilliSettings,
 };
-use meilisearch_types::milli::{self, BEU32};
+use meilisearch_types::milli::{self, Filter, Object, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, DocumentEdition, IndexSwap, Kind, KindWithContent, SettingsDelta, Status, Task,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
+use serde_json::{Number, Value};
 use time::macros::format_description;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
+use crate::snapshot::{snapshot_in_chain, IndexManifest, SnapshotManifest, MANIFEST_FILE_NAME};
 use crate::utils::{self, swap_index_uid_in_task};
 use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
 
@@ -62,6 +68,7 @@ pub(crate) enum Batch {
     TaskDeletion(Task),
     SnapshotCreation(Vec<Task>),
     Dump(Task),
+    DumpImport(Task),
     IndexOperation {
         op: IndexOperation,
         must_create_index: bool,
@@ -69,11 +76,13 @@ pub(crate) enum Batch {
     IndexCreation {
         index_uid: String,
         primary_key: Option<String>,
+        max_size: Setting<u64>,
         task: Task,
     },
     IndexUpdate {
         index_uid: String,
         primary_key: Option<String>,
+        max_size: Setting<u64>,
         task: Task,
     },
     IndexDeletion {
@@ -107,6 +116,14 @@ pub(crate) enum IndexOperation {
         index_uid: String,
         tasks: Vec<Task>,
     },
+    DocumentEdition {
+        index_uid: String,
+        task: Task,
+    },
+    SettingsImport {
+        index_uid: String,
+        task: Task,
+    },
     Settings {
         index_uid: String,
         // The boolean indicates if it's a settings deletion or creation.
@@ -143,6 +160,7 @@ impl Batch {
             Batch::TaskCancelation { task, .. }
             | Batch::TaskDeletion(task)
             | Batch::Dump(task)
+            | Batch::DumpImport(task)
             | Batch::IndexCreation { task, .. }
             | Batch::IndexUpdate { task, .. } => vec![task.uid],
             Batch::SnapshotCreation(tasks) | Batch::IndexDeletion { tasks, .. } => {
@@ -165,6 +183,8 @@ impl Batch {
                     settings_tasks: other,
                     ..
                 } => tasks.iter().chain(other).map(|task| task.uid).collect(),
+                IndexOperation::DocumentEdition { task, .. }
+                | IndexOperation::SettingsImport { task, .. } => vec![task.uid],
             },
             Batch::IndexSwap { task } => vec![task.uid],
         }
@@ -177,7 +197,9 @@ impl IndexOperation {
             IndexOperation::DocumentImport { index_uid, .. }
             | IndexOperation::DocumentDeletion { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
+            | IndexOperation::DocumentEdition { index_uid, .. }
             | IndexOperation::Settings { index_uid, .. }
+            | IndexOperation::SettingsImport { index_uid, .. }
             | IndexOperation::DocumentClearAndSetting { index_uid, .. }
             | IndexOperation::SettingsAndDocumentImport { index_uid, .. } => index_uid,
         }
@@ -261,6 +283,20 @@ impl IndexScheduler {
                     must_create_index,
                 }))
             }
+            BatchKind::DocumentEdition { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexOperation {
+                    op: IndexOperation::DocumentEdition { index_uid, task },
+                    must_create_index,
+                }))
+            }
+            BatchKind::SettingsImport { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexOperation {
+                    op: IndexOperation::SettingsImport { index_uid, task },
+                    must_create_index,
+                }))
+            }
             BatchKind::Settings { settings_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, settings_ids)?;
 
@@ -376,21 +412,23 @@ impl IndexScheduler {
             }
             BatchKind::IndexCreation { id } => {
                 let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
-                let (index_uid, primary_key) = match &task.kind {
-                    KindWithContent::IndexCreation { index_uid, primary_key } => {
-                        (index_uid.clone(), primary_key.clone())
+                let (index_uid, primary_key, max_size) = match &task.kind {
+                    KindWithContent::IndexCreation { index_uid, primary_key, max_size } => {
+                        (index_uid.clone(), primary_key.clone(), *max_size)
                     }
                     _ => unreachable!(),
                 };
-                Ok(Some(Batch::IndexCreation { index_uid, primary_key, task }))
+                Ok(Some(Batch::IndexCreation { index_uid, primary_key, max_size, task }))
             }
             BatchKind::IndexUpdate { id } => {
                 let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
-                let primary_key = match &task.kind {
-                    KindWithContent::IndexUpdate { primary_key, .. } => primary_key.clone(),
+                let (primary_key, max_size) = match &task.kind {
+                    KindWithContent::IndexUpdate { primary_key, max_size, .. } => {
+                        (primary_key.clone(), *max_size)
+                    }
                     _ => unreachable!(),
                 };
-                Ok(Some(Batch::IndexUpdate { index_uid, primary_key, task }))
+                Ok(Some(Batch::IndexUpdate { index_uid, primary_key, max_size, task }))
             }
             BatchKind::IndexDeletion { ids } => Ok(Some(Batch::IndexDeletion {
                 index_uid,
@@ -409,7 +447,8 @@ impl IndexScheduler {
     /// 2. We get the *next* task to delete.
     /// 3. We get the *next* snapshot to process.
     /// 4. We get the *next* dump to process.
-    /// 5. We get the *next* tasks to process for a specific index.
+    /// 5. We get the *next* dump to import.
+    /// 6. We get the *next* tasks to process for a specific index.
     pub(crate) fn create_next_batch(&self, rtxn: &RoTxn) -> Result<Option<Batch>> {
         #[cfg(test)]
         self.maybe_fail(crate::tests::FailureLocation::InsideCreateBatch)?;
@@ -451,7 +490,19 @@ impl IndexScheduler {
             )));
         }
 
-        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
+        // 5. we get the next dump to import, once every task enqueued before it is processed,
+        // as it may replace any index.
+        let next_import = (self.get_kind(rtxn, Kind::DumpImport)? & enqueued).min();
+        match next_import {
+            Some(to_import) if enqueued.min() == Some(to_import) => {
+                return Ok(Some(Batch::DumpImport(
+                    self.get_task(rtxn, to_import)?.ok_or(Error::CorruptedTaskQueue)?,
+                )));
+            }
+            _ => (),
+        }
+
+        // 6. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
         let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
         let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
@@ -468,20 +519,43 @@ impl IndexScheduler {
 
         let index_already_exists = self.index_mapper.exists(rtxn, index_name)?;
 
-        let index_tasks = self.index_tasks(rtxn, index_name)? & enqueued;
+        let mut index_tasks = self.index_tasks(rtxn, index_name)? & enqueued;
+        // The tasks enqueued after the next dump import must be processed after it.
+        if let Some(next_import) = next_import {
+            index_tasks.remove_range(next_import..);
+        }
 
         // If autobatching is disabled we only take one task at a time.
         let tasks_limit = if self.autobatching_enabled { usize::MAX } else { 1 };
 
-        let enqueued = index_tasks
-            .into_iter()
-            .take(tasks_limit)
-            .map(|task_id| {
-                self.get_task(rtxn, task_id)
-                    .and_then(|task| task.ok_or(Error::CorruptedTaskQueue))
-                    .map(|task| (task.uid, task.kind))
-            })
-            .collect::<Result<Vec<_>>>()?;
+        let mut enqueued = Vec::new();
+        let mut batched_documents = 0;
+        let mut batched_payload_size = 0;
+        for task_id in index_tasks.into_iter().take(tasks_limit) {
+            let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+
+            // Stop at the first document addition going over the limits of the batch,
+            // unless there is nothing to process in the batch yet.
+            if let KindWithContent::DocumentAdditionOrUpdate {
+                content_file, documents_count, ..
+            } = &task.kind
+            {
+                batched_documents += documents_count;
+                if let Some(max_batched_payload_size) = self.max_batched_payload_size {
+                    batched_payload_size += self.file_store.get_size(*content_file)?;
+                    if !enqueued.is_empty() && batched_payload_size > max_batched_payload_size {
+                        break;
+                    }
+                }
+                if let Some(max_batched_documents) = self.max_batched_documents {
+                    if !enqueued.is_empty() && batched_documents > max_batched_documents {
+                        break;
+                    }
+                }
+            }
+
+            enqueued.push((task.uid, task.kind));
+        }
 
         if let Some((batchkind, create_index)) =
             autobatcher::autobatch(enqueued, index_already_exists)
@@ -593,6 +667,28 @@ impl IndexScheduler {
                 fs::create_dir_all(&self.snapshots_path)?;
                 let temp_snapshot_dir = tempfile::tempdir()?;
 
+                // 0. Find the original name of the database and where this snapshot goes in the chain.
+                // TODO find a better way to get this path
+                let mut base_path = self.env.path().to_owned();
+                base_path.pop();
+                let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
+                let base_snapshot_path = self.snapshots_path.join(format!("{}.snapshot", db_name));
+                let manifest_path =
+                    self.snapshots_path.join(format!("{}.{}", db_name, MANIFEST_FILE_NAME));
+
+                // We can only make an incremental snapshot on top of an existing chain,
+                // otherwise, or if the chain is long enough, we start a new one.
+                let previous_manifest = if base_snapshot_path.exists() {
+                    SnapshotManifest::read_from(&manifest_path).ok().filter(|manifest| {
+                        manifest.position < self.max_incremental_snapshots
+                            && snapshot_in_chain(&base_snapshot_path, manifest.position).exists()
+                    })
+                } else {
+                    None
+                };
+                let position = previous_manifest.as_ref().map_or(0, |m| m.position + 1);
+                let mut manifest = SnapshotManifest { position, indexes: Vec::new() };
+
                 // 1. Snapshot the version file.
                 let dst = temp_snapshot_dir.path().join(VERSION_FILE_NAME);
                 fs::copy(&self.version_file_path, dst)?;
@@ -638,6 +734,17 @@ impl IndexScheduler {
                 for result in self.index_mapper.index_mapping.iter(&rtxn)? {
                     let (name, uuid) = result?;
                     let index = self.index_mapper.index(&rtxn, name)?;
+                    let updated_at = index.updated_at(&index.read_txn()?)?;
+                    manifest.indexes.push(IndexManifest { uuid, updated_at });
+
+                    // In an incremental snapshot, we skip the indexes that didn't change. The
+                    // ones that changed are still copied in full.
+                    if let Some(previous_manifest) = &previous_manifest {
+                        if !previous_manifest.index_changed(uuid, updated_at) {
+                            continue;
+                        }
+                    }
+
                     let dst = temp_snapshot_dir.path().join("indexes").join(uuid.to_string());
                     fs::create_dir_all(&dst)?;
                     index.copy_to_path(dst.join("data.mdb"), CompactionOption::Enabled)?;
@@ -656,14 +763,11 @@ impl IndexScheduler {
                 auth.copy_to_path(dst.join("data.mdb"), CompactionOption::Enabled)?;
 
                 // 5. Copy and tarball the flat snapshot
-                // 5.1 Find the original name of the database
-                // TODO find a better way to get this path
-                let mut base_path = self.env.path().to_owned();
-                base_path.pop();
-                let db_name = base_path.file_name().and_then(OsStr::to_str).unwrap_or("data.ms");
+                // 5.1 Write the manifest listing the indexes of the snapshot
+                manifest.write_to(temp_snapshot_dir.path().join(MANIFEST_FILE_NAME))?;
 
                 // 5.2 Tarball the content of the snapshot in a tempfile with a .snapshot extension
-                let snapshot_path = self.snapshots_path.join(format!("{}.snapshot", db_name));
+                let snapshot_path = snapshot_in_chain(&base_snapshot_path, position);
                 let temp_snapshot_file = tempfile::NamedTempFile::new_in(&self.snapshots_path)?;
                 compression::to_tar_gz(temp_snapshot_dir.path(), temp_snapshot_file.path())?;
                 let file = temp_snapshot_file.persist(&snapshot_path)?;
@@ -673,6 +777,19 @@ impl IndexScheduler {
                 permissions.set_readonly(true);
                 file.set_permissions(permissions)?;
 
+                // 5.4 A new full snapshot starts a new chain, the incremental
+                //     snapshots of the previous one must not be replayed on it.
+                if position == 0 {
+                    for position in 1.. {
+                        let incremental = snapshot_in_chain(&base_snapshot_path, position);
+                        if !incremental.exists() {
+                            break;
+                        }
+                        fs::remove_file(incremental)?;
+                    }
+                }
+                manifest.write_to(&manifest_path)?;
+
                 for task in &mut tasks {
                     task.status = Status::Succeeded;
                 }
@@ -703,7 +820,12 @@ impl IndexScheduler {
                 for ret in self.all_tasks.iter(&rtxn)? {
                     let (_, mut t) = ret?;
                     let status = t.status;
-                    let content_file = t.content_uuid();
+                    // The uploaded dumps of the dump imports are not part of the dump.
+                    let content_file = match t.kind {
+                        KindWithContent::DumpImport { .. } => None,
+                        _ => t.content_uuid(),
+                    };
+                    let is_settings_import = t.kind.as_kind() == Kind::SettingsImport;
 
                     // In the case we're dumping ourselves we want to be marked as finished
                     // to not loop over ourselves indefinitely.
@@ -724,6 +846,16 @@ impl IndexScheduler {
                         if status == Status::Enqueued {
                             let content_file = self.file_store.get_update(content_file)?;
 
+                            // The delta of a settings import is dumped as a single object.
+                            if is_settings_import {
+                                let delta: Object =
+                                    serde_json::from_reader(BufReader::new(content_file))
+                                        .map_err(std::io::Error::from)?;
+                                dump_content_file.push_document(&delta)?;
+                                dump_content_file.flush()?;
+                                continue;
+                            }
+
                             let reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
 
@@ -746,10 +878,12 @@ impl IndexScheduler {
 
                 // 3. Dump the indexes
                 for (uid, index) in self.index_mapper.indexes(&rtxn)? {
+                    let max_size = self.index_mapper.max_size(&rtxn, &uid)?;
                     let rtxn = index.read_txn()?;
                     let metadata = IndexMetadata {
                         uid: uid.clone(),
                         primary_key: index.primary_key(&rtxn)?.map(String::from),
+                        max_size,
                         created_at: index.created_at(&rtxn)?,
                         updated_at: index.updated_at(&rtxn)?,
                     };
@@ -774,42 +908,100 @@ impl IndexScheduler {
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
                 )).unwrap();
 
+                // The dump is written in a temporary file first so that
+                // it can't be downloaded before being complete.
                 let path = self.dumps_path.join(format!("{}.dump", dump_uid));
-                let file = File::create(path)?;
-                dump.persist_to(BufWriter::new(file))?;
+                let mut temp_dump_file = tempfile::NamedTempFile::new_in(&self.dumps_path)?;
+                dump.persist_to(BufWriter::new(&mut temp_dump_file))?;
+                temp_dump_file.persist(path)?;
 
                 // if we reached this step we can tell the scheduler we succeeded to dump ourselves.
                 task.status = Status::Succeeded;
                 task.details = Some(Details::Dump { dump_uid: Some(dump_uid) });
                 Ok(vec![task])
             }
+            Batch::DumpImport(mut task) => {
+                let content_file = match &task.kind {
+                    KindWithContent::DumpImport { content_file } => *content_file,
+                    _ => unreachable!(),
+                };
+                let dump_reader =
+                    dump::DumpReader::open(self.file_store.get_update(content_file)?)?;
+                info!("Importing a dump of version `{:?}`.", dump_reader.version());
+
+                // Only the indexes of the dump are imported, replacing the indexes with the
+                // same uids. The keys and the tasks of the instance are kept as is.
+                // Every index is imported aside first, the current indexes are still served
+                // meanwhile. They are then all replaced at once, or none of them if any import
+                // failed.
+                let mut imported_indexes = Vec::new();
+                let imported = (|| -> Result<()> {
+                    for index_reader in dump_reader.indexes()? {
+                        let mut index_reader = index_reader?;
+                        let index_uid = index_reader.metadata().uid.clone();
+                        info!("Importing index `{}`.", index_uid);
+
+                        let max_size = index_reader.metadata().max_size;
+                        let (uuid, index) = self.index_mapper.create_detached_index(max_size)?;
+                        imported_indexes.push((index_uid, uuid, max_size));
+                        self.import_dumped_index(&index, &mut index_reader)?;
+                    }
+                    Ok(())
+                })();
+                let replaced = imported.and_then(|()| {
+                    let wtxn = self.env.write_txn()?;
+                    self.index_mapper.replace_indexes(wtxn, &imported_indexes)
+                });
+                if let Err(e) = replaced {
+                    for (index_uid, uuid, _) in &imported_indexes {
+                        self.index_mapper.delete_detached_index(*uuid, index_uid);
+                    }
+                    return Err(e);
+                }
+                let imported_indexes = imported_indexes.len() as u64;
+
+                task.status = Status::Succeeded;
+                task.details =
+                    Some(Details::DumpImport { imported_indexes: Some(imported_indexes) });
+                Ok(vec![task])
+            }
             Batch::IndexOperation { op, must_create_index } => {
-                let index_uid = op.index_uid();
+                let index_uid = op.index_uid().to_string();
                 let index = if must_create_index {
                     // create the index if it doesn't already exist
                     let wtxn = self.env.write_txn()?;
-                    self.index_mapper.create_index(wtxn, index_uid)?
+                    self.index_mapper.create_index(wtxn, &index_uid, None)?
                 } else {
                     let rtxn = self.env.read_txn()?;
-                    self.index_mapper.index(&rtxn, index_uid)?
+                    self.index_mapper.index(&rtxn, &index_uid)?
                 };
 
                 let mut index_wtxn = index.write_txn()?;
-                let tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
-                index_wtxn.commit()?;
+                let tasks = self
+                    .apply_index_operation(&mut index_wtxn, &index, op)
+                    .and_then(|tasks| Ok(index_wtxn.commit().map(|()| tasks)?))
+                    .map_err(|e| self.index_size_error(&index_uid, e))?;
 
                 Ok(tasks)
             }
-            Batch::IndexCreation { index_uid, primary_key, task } => {
+            Batch::IndexCreation { index_uid, primary_key, max_size, task } => {
                 let wtxn = self.env.write_txn()?;
                 if self.index_mapper.exists(&wtxn, &index_uid)? {
                     return Err(Error::IndexAlreadyExists(index_uid));
                 }
-                self.index_mapper.create_index(wtxn, &index_uid)?;
+                // The maximum size can be too small for the index to even be created.
+                self.index_mapper.create_index(wtxn, &index_uid, max_size.set()).map_err(|e| {
+                    match max_size {
+                        Setting::Set(max_size) if is_map_full(&e) => {
+                            Error::IndexSizeLimitReached { index_uid: index_uid.clone(), max_size }
+                        }
+                        _ => e,
+                    }
+                })?;
 
-                self.process_batch(Batch::IndexUpdate { index_uid, primary_key, task })
+                self.process_batch(Batch::IndexUpdate { index_uid, primary_key, max_size, task })
             }
-            Batch::IndexUpdate { index_uid, primary_key, mut task } => {
+            Batch::IndexUpdate { index_uid, primary_key, max_size, mut task } => {
                 let rtxn = self.env.read_txn()?;
                 let index = self.index_mapper.index(&rtxn, &index_uid)?;
 
@@ -822,14 +1014,34 @@ impl IndexScheduler {
                     );
                     builder.set_primary_key(primary_key);
                     let must_stop_processing = self.must_stop_processing.clone();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.get(),
-                    )?;
-                    index_wtxn.commit()?;
+                    builder
+                        .execute(
+                            |indexing_step| debug!("update: {:?}", indexing_step),
+                            || must_stop_processing.get(),
+                        )
+                        .map_err(|e| self.index_size_error(&index_uid, e.into()))?;
+                    index_wtxn.commit().map_err(|e| self.index_size_error(&index_uid, e.into()))?;
+                }
+
+                // The maximum size is only changed once the rest of the update succeeded. The
+                // index is reopened with a map of the new size once it isn't used anymore.
+                let new_max_size = match max_size {
+                    Setting::Set(max_size) => Some(Some(max_size)),
+                    Setting::Reset => Some(None),
+                    Setting::NotSet => None,
+                };
+                if let Some(new_max_size) = new_max_size {
+                    drop(index);
+                    let current_max_size = self.index_mapper.max_size(&rtxn, &index_uid)?;
+                    drop(rtxn);
+                    if current_max_size != new_max_size {
+                        let wtxn = self.env.write_txn()?;
+                        self.index_mapper.set_max_size(wtxn, &index_uid, new_max_size)?;
+                    }
                 }
+
                 task.status = Status::Succeeded;
-                task.details = Some(Details::IndexInfo { primary_key });
+                task.details = Some(Details::IndexInfo { primary_key, max_size });
 
                 Ok(vec![task])
             }
@@ -943,6 +1155,25 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Return the error of an index reaching its maximum size in place of the error of LMDB
+    /// reaching the end of the map of the index, when the index has a maximum size.
+    fn index_size_error(&self, index_uid: &str, error: Error) -> Error {
+        if !is_map_full(&error) {
+            return error;
+        }
+        let max_size = self
+            .env
+            .read_txn()
+            .ok()
+            .and_then(|rtxn| self.index_mapper.max_size(&rtxn, index_uid).ok().flatten());
+        match max_size {
+            Some(max_size) => {
+                Error::IndexSizeLimitReached { index_uid: index_uid.to_string(), max_size }
+            }
+            None => error,
+        }
+    }
+
     /// Process the index operation on the given index.
     ///
     /// ## Return
@@ -1047,12 +1278,23 @@ impl IndexScheduler {
                 for (task, (ret, count)) in
                     tasks.iter_mut().zip(results.into_iter().zip(documents_counts))
                 {
+                    // The documents rejected while receiving the payload must be kept.
+                    let (rejected_documents, rejected_documents_count) = match task.details.take() {
+                        Some(Details::DocumentAdditionOrUpdate {
+                            rejected_documents,
+                            rejected_documents_count,
+                            ..
+                        }) => (rejected_documents, rejected_documents_count),
+                        _ => (Vec::new(), 0),
+                    };
                     match ret {
                         Ok(DocumentAdditionResult { indexed_documents, number_of_documents }) => {
                             task.status = Status::Succeeded;
                             task.details = Some(Details::DocumentAdditionOrUpdate {
                                 received_documents: number_of_documents,
                                 indexed_documents: Some(indexed_documents),
+                                rejected_documents,
+                                rejected_documents_count,
                             });
                         }
                         Err(error) => {
@@ -1060,6 +1302,8 @@ impl IndexScheduler {
                             task.details = Some(Details::DocumentAdditionOrUpdate {
                                 received_documents: count,
                                 indexed_documents: Some(count),
+                                rejected_documents,
+                                rejected_documents_count,
                             });
                             task.error = Some(error.into())
                         }
@@ -1086,6 +1330,59 @@ impl IndexScheduler {
 
                 Ok(tasks)
             }
+            IndexOperation::DocumentEdition { index_uid: _, mut task } => {
+                let (filter_expr, edition) = match &task.kind {
+                    KindWithContent::DocumentEdition { filter_expr, edition, .. } => {
+                        (filter_expr.clone(), edition)
+                    }
+                    _ => unreachable!(),
+                };
+                let edited_documents =
+                    self.edit_documents(index_wtxn, index, &filter_expr, edition)?;
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::DocumentEdition {
+                    edited_documents: Some(edited_documents),
+                    original_filter: filter_expr,
+                });
+
+                Ok(vec![task])
+            }
+            IndexOperation::SettingsImport { index_uid: _, mut task } => {
+                let content_file = match &task.kind {
+                    KindWithContent::SettingsImport { content_file, .. } => *content_file,
+                    _ => unreachable!(),
+                };
+                let content_file = self.file_store.get_update(content_file)?;
+                let delta: SettingsDelta = serde_json::from_reader(BufReader::new(content_file))
+                    .map_err(std::io::Error::from)?;
+
+                // The delta is merged into the settings as they are when the task is processed.
+                let current = meilisearch_types::settings::settings(index, index_wtxn)?;
+                let mut new_settings = Settings::<Unchecked>::default();
+                if delta.modifies_synonyms() {
+                    let mut synonyms = current.synonyms.set().unwrap_or_default();
+                    delta.apply_to_synonyms(&mut synonyms);
+                    new_settings.synonyms = Setting::Set(synonyms);
+                }
+                if delta.modifies_stop_words() {
+                    let mut stop_words = current.stop_words.set().unwrap_or_default();
+                    delta.apply_to_stop_words(&mut stop_words);
+                    new_settings.stop_words = Setting::Set(stop_words);
+                }
+
+                let indexer_config = self.index_mapper.indexer_config();
+                let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
+                apply_settings_to_builder(&new_settings.check(), &mut builder);
+                let must_stop_processing = self.must_stop_processing.clone();
+                builder.execute(
+                    |indexing_step| debug!("update: {:?}", indexing_step),
+                    || must_stop_processing.get(),
+                )?;
+
+                task.status = Status::Succeeded;
+                Ok(vec![task])
+            }
             IndexOperation::Settings { index_uid: _, settings, mut tasks } => {
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
@@ -1173,6 +1470,73 @@ impl IndexScheduler {
         }
     }
 
+    /// Applies the edition to the documents matching the filter and returns the number of
+    /// documents it modified.
+    fn edit_documents(
+        &self,
+        index_wtxn: &mut RwTxn,
+        index: &Index,
+        filter_expr: &str,
+        edition: &DocumentEdition,
+    ) -> Result<u64> {
+        let primary_key = match index.primary_key(index_wtxn)? {
+            Some(primary_key) => primary_key.to_string(),
+            // An index without a primary key doesn't contain any document.
+            None => return Ok(0),
+        };
+        if edition.fields().any(|field| field == primary_key) {
+            return Err(Error::DocumentEditionPrimaryKey(primary_key));
+        }
+
+        let candidates = match Filter::from_str(filter_expr)? {
+            Some(filter) => filter.evaluate(index_wtxn, index)?,
+            None => index.documents_ids(index_wtxn)?,
+        };
+
+        let fields_ids_map = index.fields_ids_map(index_wtxn)?;
+        let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+        // The edited documents are written on disk, as they can be as large as the index.
+        let mut edited_documents =
+            DocumentsBatchBuilder::new(BufWriter::new(tempfile::tempfile()?));
+        let mut count = 0;
+        for (_id, document) in index.documents(index_wtxn, candidates)? {
+            let document = milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
+            if let Some(document) = edit_document(edition, document, &primary_key)? {
+                edited_documents.append_json_object(&document).map_err(milli::Error::from)?;
+                count += 1;
+            }
+        }
+        if count == 0 {
+            return Ok(0);
+        }
+
+        // The edited documents are complete, they replace their previous version.
+        let config = IndexDocumentsConfig {
+            update_method: IndexDocumentsMethod::ReplaceDocuments,
+            ..Default::default()
+        };
+        let must_stop_processing = self.must_stop_processing.clone();
+        let builder = milli::update::IndexDocuments::new(
+            index_wtxn,
+            index,
+            self.index_mapper.indexer_config(),
+            config,
+            |indexing_step| debug!("update: {:?}", indexing_step),
+            || must_stop_processing.get(),
+        )?;
+        // This flush the content of the batch builder.
+        let edited_documents =
+            edited_documents.into_inner()?.into_inner().map_err(IntoInnerError::into_error)?;
+        let reader = DocumentsBatchReader::from_reader(BufReader::new(edited_documents))
+            .map_err(milli::Error::from)?;
+        let (builder, user_result) = builder.add_documents(reader)?;
+        user_result.map_err(milli::Error::from)?;
+        let edition = builder.execute()?;
+        info!("document edition done: {:?}", edition);
+
+        Ok(count)
+    }
+
     /// Delete each given task from all the databases (if it is deleteable).
     ///
     /// Return the number of tasks that were actually deleted.
@@ -1192,6 +1556,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_batches = RoaringBitmap::new();
 
         for task_id in to_delete_tasks.iter() {
             let task = self.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -1213,6 +1578,9 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            if let Some(batch_uid) = task.batch_uid {
+                affected_batches.insert(batch_uid);
+            }
         }
 
         for index in affected_indexes {
@@ -1241,6 +1609,18 @@ impl IndexScheduler {
                 }
             }
         }
+        // A batch is deleted along with its last task.
+        for batch_uid in affected_batches {
+            let batch_uid = BEU32::new(batch_uid);
+            if let Some(mut batch) = self.batches.get(wtxn, &batch_uid)? {
+                batch.task_uids.retain(|uid| !to_delete_tasks.contains(*uid));
+                if batch.task_uids.is_empty() {
+                    self.batches.delete(wtxn, &batch_uid)?;
+                } else {
+                    self.batches.put(wtxn, &batch_uid, &batch)?;
+                }
+            }
+        }
 
         Ok(to_delete_tasks.len())
     }
@@ -1284,3 +1664,60 @@ impl IndexScheduler {
         Ok(content_files_to_delete)
     }
 }
+
+/// Whether LMDB refused a write because the map of the index is full.
+fn is_map_full(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Milli(milli::Error::UserError(milli::UserError::MaxDatabaseSizeReached))
+            | Error::Heed(heed::Error::Mdb(MdbError::MapFull))
+    )
+}
+
+/// Applies the edition to the document, returns `None` if it didn't modify the document.
+fn edit_document(
+    edition: &DocumentEdition,
+    mut document: Object,
+    primary_key: &str,
+) -> Result<Option<Object>> {
+    let original = document.clone();
+    for (field, value) in &edition.set {
+        document.insert(field.clone(), value.clone());
+    }
+    for field in &edition.unset {
+        document.remove(field);
+    }
+    for (field, increment) in &edition.increment {
+        let incremented = match document.get(field) {
+            None | Some(Value::Null) => Some(increment.clone()),
+            Some(Value::Number(number)) => increment_number(number, increment),
+            Some(_) => None,
+        };
+        match incremented {
+            Some(number) => {
+                document.insert(field.clone(), Value::Number(number));
+            }
+            None => {
+                let document_id = match document.get(primary_key) {
+                    Some(Value::String(id)) => id.clone(),
+                    Some(id) => id.to_string(),
+                    None => String::new(),
+                };
+                return Err(Error::DocumentEditionInvalidIncrement {
+                    field: field.clone(),
+                    document_id,
+                });
+            }
+        }
+    }
+
+    Ok(Some(document).filter(|document| document != &original))
+}
+
+/// Adds the increment to the number, the integers are added without losing precision.
+fn increment_number(number: &Number, increment: &Number) -> Option<Number> {
+    match (number.as_i64(), increment.as_i64()) {
+        (Some(number), Some(increment)) => number.checked_add(increment).map(Number::from),
+        _ => Number::from_f64(number.as_f64()? + increment.as_f64()?),
+    }
+}
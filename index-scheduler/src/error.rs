@@ -3,7 +3,7 @@ use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
 use thiserror::Error;
 
-use crate::TaskId;
+use crate::{BatchId, TaskId};
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Error, Debug)]
@@ -17,6 +17,14 @@ pub enum Error {
     IndexesNotFound(Vec<String>),
     #[error("Index `{0}` already exists.")]
     IndexAlreadyExists(String),
+    #[error("Index `{index_uid}` reached its maximum size of {max_size} bytes.")]
+    IndexSizeLimitReached { index_uid: String, max_size: u64 },
+    #[error(
+        "The maximum size of an index must be at least {min_size} bytes, but `{max_size}` was given."
+    )]
+    InvalidIndexMaxSize { max_size: u64, min_size: u64 },
+    #[error("Index `{0}` is being resized. Try again once it is reopened with its new size.")]
+    IndexBeingResized(String),
     #[error(
         "Indexes must be declared only once during a swap. `{0}` was specified several times."
     )]
@@ -60,6 +68,18 @@ pub enum Error {
     InvalidIndexUid { index_uid: String },
     #[error("Task `{0}` not found.")]
     TaskNotFound(TaskId),
+    #[error("Batch uid `{batch_uid}` is invalid. It should only contain numeric characters.")]
+    InvalidBatchUid { batch_uid: String },
+    #[error("Batch `{0}` not found.")]
+    BatchNotFound(BatchId),
+    #[error("Dump `{0}` not found.")]
+    DumpNotFound(String),
+    #[error("The primary key `{0}` of the documents can't be modified by a document edition.")]
+    DocumentEditionPrimaryKey(String),
+    #[error(
+        "Cannot increment the field `{field}` of the document `{document_id}`. The field must be a number and its new value must fit in a 64-bit number."
+    )]
+    DocumentEditionInvalidIncrement { field: String, document_id: String },
     #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
     TaskDeletionWithEmptyQuery,
     #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
@@ -100,6 +120,9 @@ impl ErrorCode for Error {
             Error::IndexNotFound(_) => Code::IndexNotFound,
             Error::IndexesNotFound(_) => Code::IndexNotFound,
             Error::IndexAlreadyExists(_) => Code::IndexAlreadyExists,
+            Error::IndexSizeLimitReached { .. } => Code::IndexSizeLimitReached,
+            Error::InvalidIndexMaxSize { .. } => Code::InvalidIndexMaxSize,
+            Error::IndexBeingResized(_) => Code::IndexBeingResized,
             Error::SwapDuplicateIndexesFound(_) => Code::DuplicateIndexFound,
             Error::SwapDuplicateIndexFound(_) => Code::DuplicateIndexFound,
             Error::InvalidTaskDate { .. } => Code::InvalidTaskDateFilter,
@@ -109,6 +132,11 @@ impl ErrorCode for Error {
             Error::InvalidTaskCanceledBy { .. } => Code::InvalidTaskCanceledByFilter,
             Error::InvalidIndexUid { .. } => Code::InvalidIndexUid,
             Error::TaskNotFound(_) => Code::TaskNotFound,
+            Error::InvalidBatchUid { .. } => Code::InvalidBatchUid,
+            Error::BatchNotFound(_) => Code::BatchNotFound,
+            Error::DumpNotFound(_) => Code::DumpNotFound,
+            Error::DocumentEditionPrimaryKey(_) => Code::InvalidDocumentEdition,
+            Error::DocumentEditionInvalidIncrement { .. } => Code::InvalidDocumentEdition,
             Error::TaskDeletionWithEmptyQuery => Code::TaskDeletionWithEmptyQuery,
             Error::TaskCancelationWithEmptyQuery => Code::TaskCancelationWithEmptyQuery,
             Error::Dump(e) => e.error_code(),
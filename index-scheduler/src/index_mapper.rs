@@ -2,20 +2,27 @@ use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use std::{fs, thread};
 
-use log::error;
-use meilisearch_types::heed::types::Str;
-use meilisearch_types::heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn};
+use log::{error, info};
+use meilisearch_types::heed::types::{OwnedType, Str};
+use meilisearch_types::heed::{
+    CompactionOption, Database, Env, EnvClosingEvent, EnvOpenOptions, RoTxn, RwTxn,
+};
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::Index;
 use uuid::Uuid;
 
-use self::IndexStatus::{Available, BeingDeleted};
+use self::IndexStatus::{Available, BeingDeleted, BeingResized};
 use crate::uuid_codec::UuidCodec;
 use crate::{Error, Result};
 
 const INDEX_MAPPING: &str = "index-mapping";
+const INDEX_MAX_SIZES: &str = "index-max-sizes";
+
+/// The smallest maximum size an index can be given, an empty index must fit in it.
+pub(crate) const MIN_INDEX_MAX_SIZE: u64 = 1024 * 1024;
 
 /// Structure managing meilisearch's indexes.
 ///
@@ -32,6 +39,9 @@ pub struct IndexMapper {
     /// Map an index name with an index uuid currently available on disk.
     pub(crate) index_mapping: Database<Str, UuidCodec>,
 
+    /// Map the uuid of an index with its maximum size, for the indexes that have one.
+    pub(crate) index_max_sizes: Database<UuidCodec, OwnedType<u64>>,
+
     /// Path to the folder where the LMDB environments of each index are.
     base_path: PathBuf,
     index_size: usize,
@@ -44,8 +54,27 @@ pub struct IndexMapper {
 pub enum IndexStatus {
     /// Do not insert it back in the index map as it is currently being deleted.
     BeingDeleted,
-    /// You can use the index without worrying about anything.
-    Available(Index),
+    /// You can use the index without worrying about anything, its LMDB map has the given size.
+    Available(Index, usize),
+    /// The index can't be used until it is reopened with its new maximum size, which can only be
+    /// done once it is closed by everyone using it.
+    BeingResized { closing_event: EnvClosingEvent, max_size: Option<u64> },
+}
+
+/// The space taken on disk by an index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexDiskUsage {
+    /// The size of the files of the index, in bytes.
+    pub on_disk_size: u64,
+    /// The size of the LMDB map of the index, in bytes.
+    pub map_size: u64,
+    /// The maximum size of the index, in bytes, if it has one.
+    pub max_size: Option<u64>,
+    /// The share of the map taken by the files of the index, in percent.
+    pub map_usage: f64,
+    /// The share of the files of the index taken by free pages, in percent. Only computed when
+    /// asked as it requires a compacted copy of the index.
+    pub fragmentation: Option<f64>,
 }
 
 impl IndexMapper {
@@ -58,23 +87,58 @@ impl IndexMapper {
         Ok(Self {
             index_map: Arc::default(),
             index_mapping: env.create_database(Some(INDEX_MAPPING))?,
+            index_max_sizes: env.create_database(Some(INDEX_MAX_SIZES))?,
             base_path,
             index_size,
             indexer_config: Arc::new(indexer_config),
         })
     }
 
-    /// Create or open an index in the specified path.
+    /// Create or open an index in the specified path, returns it with the size of its map.
     /// The path *must* exists or an error will be thrown.
-    fn create_or_open_index(&self, path: &Path) -> Result<Index> {
+    fn create_or_open_index(&self, path: &Path, max_size: Option<u64>) -> Result<(Index, usize)> {
+        let map_size = self.map_size(max_size);
         let mut options = EnvOpenOptions::new();
-        options.map_size(self.index_size);
+        options.map_size(map_size);
         options.max_readers(1024);
-        Ok(Index::new(options, path)?)
+        Ok((Index::new(options, path)?, map_size))
+    }
+
+    /// Create the directory of a new index and open the index in it. The directory is removed
+    /// if the index can't be opened, e.g. because its maximum size is too small.
+    fn create_index_files(&self, uuid: Uuid, max_size: Option<u64>) -> Result<(Index, usize)> {
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+        match self.create_or_open_index(&index_path, max_size) {
+            Ok(opened) => Ok(opened),
+            Err(e) => {
+                let _ = fs::remove_dir_all(&index_path);
+                Err(e)
+            }
+        }
+    }
+
+    /// The size of the LMDB map of an index, which makes LMDB refuse any write that would make
+    /// the index exceed its maximum size.
+    fn map_size(&self, max_size: Option<u64>) -> usize {
+        match max_size {
+            Some(max_size) => {
+                let max_size = usize::try_from(max_size).unwrap_or(usize::MAX).min(self.index_size);
+                // The map size must be a multiple of the page size.
+                let page_size = page_size::get();
+                (max_size / page_size).max(1) * page_size
+            }
+            None => self.index_size,
+        }
     }
 
-    /// Get or create the index.
-    pub fn create_index(&self, mut wtxn: RwTxn, name: &str) -> Result<Index> {
+    /// Get or create the index, with the given maximum size if it is created.
+    pub fn create_index(
+        &self,
+        mut wtxn: RwTxn,
+        name: &str,
+        max_size: Option<u64>,
+    ) -> Result<Index> {
         match self.index(&wtxn, name) {
             Ok(index) => {
                 wtxn.commit()?;
@@ -83,15 +147,16 @@ impl IndexMapper {
             Err(Error::IndexNotFound(_)) => {
                 let uuid = Uuid::new_v4();
                 self.index_mapping.put(&mut wtxn, name, &uuid)?;
+                if let Some(max_size) = max_size {
+                    self.index_max_sizes.put(&mut wtxn, &uuid, &max_size)?;
+                }
 
-                let index_path = self.base_path.join(uuid.to_string());
-                fs::create_dir_all(&index_path)?;
-                let index = self.create_or_open_index(&index_path)?;
+                let (index, map_size) = self.create_index_files(uuid, max_size)?;
 
                 wtxn.commit()?;
                 // TODO: it would be better to lazily create the index. But we need an Index::open function for milli.
                 if let Some(BeingDeleted) =
-                    self.index_map.write().unwrap().insert(uuid, Available(index.clone()))
+                    self.index_map.write().unwrap().insert(uuid, Available(index.clone(), map_size))
                 {
                     panic!("Uuid v4 conflict.");
                 }
@@ -112,12 +177,98 @@ impl IndexMapper {
 
         // Once we retrieved the UUID of the index we remove it from the mapping table.
         assert!(self.index_mapping.delete(&mut wtxn, name)?);
+        self.index_max_sizes.delete(&mut wtxn, &uuid)?;
 
         wtxn.commit()?;
+        self.remove_index_files(uuid, name);
+
+        Ok(())
+    }
+
+    /// Create an index which can't be accessed through a name, until it replaces the index of
+    /// a name with [`Self::replace_indexes`].
+    pub fn create_detached_index(&self, max_size: Option<u64>) -> Result<(Uuid, Index)> {
+        let uuid = Uuid::new_v4();
+        let (index, map_size) = self.create_index_files(uuid, max_size)?;
+
+        if let Some(BeingDeleted) =
+            self.index_map.write().unwrap().insert(uuid, Available(index.clone(), map_size))
+        {
+            panic!("Uuid v4 conflict.");
+        }
+
+        Ok((uuid, index))
+    }
+
+    /// Make each detached index accessible through its name, with its maximum size, all at
+    /// once. The indexes previously accessible through these names, if any, are deleted.
+    pub fn replace_indexes(
+        &self,
+        mut wtxn: RwTxn,
+        indexes: &[(String, Uuid, Option<u64>)],
+    ) -> Result<()> {
+        let mut previous_indexes = Vec::new();
+        for (name, uuid, max_size) in indexes {
+            if let Some(previous_uuid) = self.index_mapping.get(&wtxn, name)? {
+                self.index_max_sizes.delete(&mut wtxn, &previous_uuid)?;
+                previous_indexes.push((name, previous_uuid));
+            }
+            self.index_mapping.put(&mut wtxn, name, uuid)?;
+            if let Some(max_size) = max_size {
+                self.index_max_sizes.put(&mut wtxn, uuid, max_size)?;
+            }
+        }
+        wtxn.commit()?;
+
+        for (name, previous_uuid) in previous_indexes {
+            self.remove_index_files(previous_uuid, name);
+        }
+
+        Ok(())
+    }
+
+    /// Delete a detached index that didn't replace any index.
+    pub fn delete_detached_index(&self, uuid: Uuid, name: &str) {
+        self.remove_index_files(uuid, name);
+    }
+
+    /// Remove the files of the indexes that aren't accessible through any name, which are left
+    /// by a crash in the middle of a dump import or of an index deletion.
+    pub fn remove_orphaned_indexes(&self, rtxn: &RoTxn) -> Result<()> {
+        let mut uuids = Vec::new();
+        for ret in self.index_mapping.iter(rtxn)? {
+            let (_, uuid) = ret?;
+            uuids.push(uuid);
+        }
+
+        if !self.base_path.exists() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(&self.base_path)? {
+            let path = entry?.path();
+            let uuid = path.file_name().and_then(|name| name.to_str()?.parse::<Uuid>().ok());
+            match uuid {
+                Some(uuid) if path.is_dir() && !uuids.contains(&uuid) => {
+                    info!("Removing the orphaned index `{}`.", uuid);
+                    if let Err(e) = fs::remove_dir_all(&path) {
+                        error!("Error while removing the orphaned index `{}`: {}", uuid, e);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Close the index, which must not be accessible through its name anymore, and remove its
+    /// files once everyone using it dropped it.
+    fn remove_index_files(&self, uuid: Uuid, name: &str) {
         // We remove the index from the in-memory index map.
         let mut lock = self.index_map.write().unwrap();
         let closing_event = match lock.insert(uuid, BeingDeleted) {
-            Some(Available(index)) => Some(index.prepare_for_closing()),
+            Some(Available(index, _)) => Some(index.prepare_for_closing()),
+            Some(BeingResized { closing_event, .. }) => Some(closing_event),
             _ => None,
         };
 
@@ -147,8 +298,6 @@ impl IndexMapper {
                 assert!(matches!(index_map.write().unwrap().remove(&uuid), Some(BeingDeleted)));
             })
             .unwrap();
-
-        Ok(())
     }
 
     pub fn exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
@@ -165,8 +314,9 @@ impl IndexMapper {
         // we clone here to drop the lock before entering the match
         let index = self.index_map.read().unwrap().get(&uuid).cloned();
         let index = match index {
-            Some(Available(index)) => index,
+            Some(Available(index, _)) => index,
             Some(BeingDeleted) => return Err(Error::IndexNotFound(name.to_string())),
+            Some(BeingResized { .. }) => return self.reopen_resized_index(name, uuid),
             // since we're lazy, it's possible that the index has not been opened yet.
             None => {
                 let mut index_map = self.index_map.write().unwrap();
@@ -176,21 +326,51 @@ impl IndexMapper {
                 // if it's not already there.
                 // Since there is a good chance it's not already there we can use
                 // the entry method.
-                match index_map.entry(uuid) {
+                let index = match index_map.entry(uuid) {
                     Entry::Vacant(entry) => {
                         let index_path = self.base_path.join(uuid.to_string());
-                        let index = self.create_or_open_index(&index_path)?;
-                        entry.insert(Available(index.clone()));
-                        index
+                        let max_size = self.index_max_sizes.get(rtxn, &uuid)?;
+                        let (index, map_size) = self.create_or_open_index(&index_path, max_size)?;
+                        entry.insert(Available(index.clone(), map_size));
+                        Some(index)
                     }
                     Entry::Occupied(entry) => match entry.get() {
-                        Available(index) => index.clone(),
+                        Available(index, _) => Some(index.clone()),
                         BeingDeleted => return Err(Error::IndexNotFound(name.to_string())),
+                        BeingResized { .. } => None,
                     },
+                };
+                drop(index_map);
+
+                match index {
+                    Some(index) => index,
+                    None => return self.reopen_resized_index(name, uuid),
+                }
+            }
+        };
+
+        Ok(index)
+    }
+
+    /// Reopen an index with the map size matching its new maximum size if everyone using it
+    /// closed it, return an [`Error::IndexBeingResized`] otherwise.
+    fn reopen_resized_index(&self, name: &str, uuid: Uuid) -> Result<Index> {
+        let mut index_map = self.index_map.write().unwrap();
+        let max_size = match index_map.get(&uuid) {
+            Some(BeingResized { closing_event, max_size }) => {
+                if !closing_event.wait_timeout(Duration::ZERO) {
+                    return Err(Error::IndexBeingResized(name.to_string()));
                 }
+                *max_size
             }
+            // Someone else reopened the index meanwhile.
+            Some(Available(index, _)) => return Ok(index.clone()),
+            Some(BeingDeleted) | None => return Err(Error::IndexNotFound(name.to_string())),
         };
 
+        let index_path = self.base_path.join(uuid.to_string());
+        let (index, map_size) = self.create_or_open_index(&index_path, max_size)?;
+        index_map.insert(uuid, Available(index.clone(), map_size));
         Ok(index)
     }
 
@@ -223,6 +403,96 @@ impl IndexMapper {
         Ok(())
     }
 
+    /// Return the maximum size of an index, if it has one.
+    pub fn max_size(&self, rtxn: &RoTxn, name: &str) -> Result<Option<u64>> {
+        let uuid = self
+            .index_mapping
+            .get(rtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        Ok(self.index_max_sizes.get(rtxn, &uuid)?)
+    }
+
+    /// Set or remove the maximum size of an index.
+    ///
+    /// The index is reopened with a map of the matching size once it is closed by everyone using
+    /// it, which isn't waited for. Meanwhile, accessing the index returns an
+    /// [`Error::IndexBeingResized`].
+    pub fn set_max_size(&self, mut wtxn: RwTxn, name: &str, max_size: Option<u64>) -> Result<()> {
+        let uuid = self
+            .index_mapping
+            .get(&wtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        match max_size {
+            Some(max_size) => self.index_max_sizes.put(&mut wtxn, &uuid, &max_size)?,
+            None => {
+                self.index_max_sizes.delete(&mut wtxn, &uuid)?;
+            }
+        }
+
+        // The lock is held while committing, so that the index isn't opened between the commit
+        // and the change of its status.
+        let mut index_map = self.index_map.write().unwrap();
+        wtxn.commit()?;
+        match index_map.remove(&uuid) {
+            Some(Available(index, _)) => {
+                let closing_event = index.prepare_for_closing();
+                index_map.insert(uuid, BeingResized { closing_event, max_size });
+            }
+            Some(BeingResized { closing_event, .. }) => {
+                index_map.insert(uuid, BeingResized { closing_event, max_size });
+            }
+            Some(BeingDeleted) => {
+                index_map.insert(uuid, BeingDeleted);
+            }
+            // The index isn't opened, it will be with its new map size.
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    /// Return the space taken on disk by an index.
+    pub fn disk_usage(
+        &self,
+        rtxn: &RoTxn,
+        name: &str,
+        with_fragmentation: bool,
+    ) -> Result<IndexDiskUsage> {
+        let index = self.index(rtxn, name)?;
+        let max_size = self.max_size(rtxn, name)?;
+        let on_disk_size = index.on_disk_size()?;
+        let uuid = self
+            .index_mapping
+            .get(rtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+        let map_size = match self.index_map.read().unwrap().get(&uuid) {
+            Some(Available(_, map_size)) => *map_size as u64,
+            // The maximum size of the index was changed since it was opened above.
+            _ => return Err(Error::IndexBeingResized(name.to_string())),
+        };
+        // LMDB grows the map of an index opened with a map smaller than the index.
+        let map_size = map_size.max(on_disk_size);
+
+        // A compacted copy of the index only contains the pages in use.
+        let fragmentation = if with_fragmentation {
+            let dir = tempfile::tempdir_in(&self.base_path)?;
+            let path = dir.path().join("data.mdb");
+            index.copy_to_path(&path, CompactionOption::Enabled)?;
+            let compacted_size = fs::metadata(&path)?.len().min(on_disk_size);
+            Some((on_disk_size - compacted_size) as f64 / on_disk_size.max(1) as f64 * 100.0)
+        } else {
+            None
+        };
+
+        Ok(IndexDiskUsage {
+            on_disk_size,
+            map_size,
+            max_size,
+            map_usage: on_disk_size as f64 / map_size as f64 * 100.0,
+            fragmentation,
+        })
+    }
+
     pub fn index_exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
         Ok(self.index_mapping.get(rtxn, name)?.is_some())
     }
@@ -230,4 +500,9 @@ impl IndexMapper {
     pub fn indexer_config(&self) -> &IndexerConfig {
         &self.indexer_config
     }
+
+    /// The size of the LMDB map of every index.
+    pub fn index_size(&self) -> usize {
+        self.index_size
+    }
 }
@@ -14,6 +14,11 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
     let IndexScheduler {
         autobatching_enabled,
+        max_batched_documents: _,
+        max_batched_payload_size: _,
+        batch_max_wait_time: _,
+        task_notifiers: _,
+        max_incremental_snapshots: _,
         must_stop_processing: _,
         processing_tasks,
         file_store,
@@ -26,6 +31,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         enqueued_at,
         started_at,
         finished_at,
+        batches: _,
         index_mapper,
         wake_up: _,
         dumps_path: _,
@@ -140,6 +146,7 @@ pub fn snapshot_task(task: &Task) -> String {
         finished_at: _,
         error,
         canceled_by,
+        batch_uid: _,
         details,
         status,
         kind,
@@ -167,14 +174,25 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            rejected_documents,
+            rejected_documents_count,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            if rejected_documents.is_empty() {
+                format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            } else {
+                let rejected_lines: Vec<_> = rejected_documents.iter().map(|r| r.line).collect();
+                format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}, rejected_lines: {rejected_lines:?}, rejected_documents_count: {rejected_documents_count} }}")
+            }
         }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
         }
-        Details::IndexInfo { primary_key } => {
-            format!("{{ primary_key: {primary_key:?} }}")
+        Details::IndexInfo { primary_key, max_size } => {
+            if max_size.is_not_set() {
+                format!("{{ primary_key: {primary_key:?} }}")
+            } else {
+                format!("{{ primary_key: {primary_key:?}, max_size: {max_size:?} }}")
+            }
         }
         Details::DocumentDeletion {
             provided_ids: received_document_ids,
@@ -183,6 +201,9 @@ fn snapshot_details(d: &Details) -> String {
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
+        Details::DocumentEdition { edited_documents, original_filter } => {
+            format!("{{ edited_documents: {edited_documents:?}, original_filter: {original_filter:?} }}")
+        },
         Details::TaskCancelation {
             matched_tasks,
             canceled_tasks,
@@ -203,6 +224,12 @@ fn snapshot_details(d: &Details) -> String {
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::DumpImport { imported_indexes } => {
+            format!("{{ imported_indexes: {imported_indexes:?} }}")
+        }
+        Details::SettingsImport { added_entries, removed_entries } => {
+            format!("{{ added_entries: {added_entries:?}, removed_entries: {removed_entries:?} }}")
+        }
     }
 }
 
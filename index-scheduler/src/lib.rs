@@ -24,12 +24,15 @@ pub mod error;
 mod index_mapper;
 #[cfg(test)]
 mod insta_snapshot;
+mod snapshot;
 mod utils;
 mod uuid_codec;
 
 pub type Result<T> = std::result::Result<T, Error>;
 pub type TaskId = u32;
 
+use std::collections::HashMap;
+use std::io::{BufReader, BufWriter, IntoInnerError};
 use std::ops::{Bound, RangeBounds};
 use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
@@ -37,25 +40,35 @@ use std::sync::atomic::Ordering::Relaxed;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
-use dump::{KindDump, TaskDump, UpdateFile};
+use dump::{DumpIndexReader, KindDump, TaskDump, UpdateFile};
 pub use error::Error;
 use file_store::FileStore;
+use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{self, Database, Env, RoTxn};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
 use meilisearch_types::milli;
-use meilisearch_types::milli::documents::DocumentsBatchBuilder;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
+use meilisearch_types::milli::update::{
+    IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig,
+};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::settings::apply_settings_to_builder;
+use meilisearch_types::tasks::{Details, Kind, KindWithContent, Status, Task};
 use roaring::RoaringBitmap;
+pub use snapshot::import_snapshot;
 use synchronoise::SignalEvent;
 use time::OffsetDateTime;
-use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound};
+use utils::{
+    filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound, task_span,
+    trace_processed_task,
+};
 use uuid::Uuid;
 
+pub use crate::index_mapper::IndexDiskUsage;
 use crate::index_mapper::IndexMapper;
-use crate::utils::check_index_swap_validity;
+use crate::utils::{check_index_max_size_validity, check_index_swap_validity};
 
 pub(crate) type BEI128 =
     meilisearch_types::heed::zerocopy::I128<meilisearch_types::heed::byteorder::BE>;
@@ -103,6 +116,17 @@ pub struct Query {
     pub after_finished_at: Option<OffsetDateTime>,
 }
 
+/// Defines a subset of batches to be retrieved from the [`IndexScheduler`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct BatchQuery {
+    /// The maximum number of batches to be matched
+    pub limit: Option<u32>,
+    /// The maximum [batch id](`meilisearch_types::batches::Batch::uid`) to be matched
+    pub from: Option<BatchId>,
+    /// The [batch ids](`meilisearch_types::batches::Batch::uid`) to be matched
+    pub uids: Option<Vec<BatchId>>,
+}
+
 impl Query {
     /// Return `true` if every field of the query is set to `None`, such that the query
     /// matches all tasks.
@@ -193,6 +217,7 @@ mod db_name {
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const BATCHES: &str = "batches";
 }
 
 #[cfg(test)]
@@ -225,6 +250,9 @@ pub struct IndexSchedulerOptions {
     pub indexes_path: PathBuf,
     /// The path to the folder containing the snapshots.
     pub snapshots_path: PathBuf,
+    /// The number of incremental snapshots made after a full snapshot before starting over.
+    /// Set to `0` to only make full snapshots.
+    pub max_incremental_snapshots: u32,
     /// The path to the folder containing the dumps.
     pub dumps_path: PathBuf,
     /// The maximum size, in bytes, of each meilisearch index.
@@ -236,6 +264,21 @@ pub struct IndexSchedulerOptions {
     /// Set to `true` iff the index scheduler is allowed to automatically
     /// batch tasks together, to process multiple tasks at once.
     pub autobatching_enabled: bool,
+    /// The maximum number of documents added or updated by a single batch, `None` for no limit.
+    /// A task is always processed, even if it goes over the limit by itself.
+    pub max_batched_documents: Option<u64>,
+    /// The maximum total size, in bytes, of the payloads added or updated by a single batch,
+    /// `None` for no limit. A task is always processed, even if it goes over the limit by itself.
+    pub max_batched_payload_size: Option<u64>,
+    /// How long the oldest enqueued task may wait for other tasks to be batched with it
+    /// before being processed, `None` to process it right away.
+    pub batch_max_wait_time: Option<Duration>,
+    /// Every task that succeeded or failed is sent through each of
+    /// these channels once its new state has been committed.
+    pub task_notifiers: Vec<crossbeam_channel::Sender<Task>>,
+    /// Set to `true` to never start the run loop, the tasks are then never processed.
+    /// Used by the read-only replicas, which only serve the content of their databases.
+    pub read_only: bool,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -276,6 +319,9 @@ pub struct IndexScheduler {
     /// Store the task ids of tasks which finished at a specific date
     pub(crate) finished_at: Database<OwnedType<BEI128>, CboRoaringBitmapCodec>,
 
+    /// All the processed batches accessible by their Id.
+    pub(crate) batches: Database<OwnedType<BEU32>, SerdeJson<Batch>>,
+
     /// In charge of creating, opening, storing and returning indexes.
     pub(crate) index_mapper: IndexMapper,
 
@@ -285,12 +331,27 @@ pub struct IndexScheduler {
     /// Whether auto-batching is enabled or not.
     pub(crate) autobatching_enabled: bool,
 
+    /// The maximum number of documents added or updated by a single batch.
+    pub(crate) max_batched_documents: Option<u64>,
+
+    /// The maximum total size of the payloads added or updated by a single batch.
+    pub(crate) max_batched_payload_size: Option<u64>,
+
+    /// How long the oldest enqueued task may wait for other tasks to be batched with it.
+    pub(crate) batch_max_wait_time: Option<Duration>,
+
+    /// Where the finished tasks are sent, if anyone is listening.
+    pub(crate) task_notifiers: Vec<crossbeam_channel::Sender<Task>>,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
     /// The path used to create the snapshots.
     pub(crate) snapshots_path: PathBuf,
 
+    /// The number of incremental snapshots allowed after a full one.
+    pub(crate) max_incremental_snapshots: u32,
+
     /// The path to the folder containing the auth LMDB env.
     pub(crate) auth_path: PathBuf,
 
@@ -331,10 +392,16 @@ impl IndexScheduler {
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
+            batches: self.batches,
             index_mapper: self.index_mapper.clone(),
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
+            max_batched_documents: self.max_batched_documents,
+            max_batched_payload_size: self.max_batched_payload_size,
+            batch_max_wait_time: self.batch_max_wait_time,
+            task_notifiers: self.task_notifiers.clone(),
             snapshots_path: self.snapshots_path.clone(),
+            max_incremental_snapshots: self.max_incremental_snapshots,
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
             version_file_path: self.version_file_path.clone(),
@@ -349,7 +416,7 @@ impl IndexScheduler {
 }
 
 impl IndexScheduler {
-    /// Create an index scheduler and start its run loop.
+    /// Create an index scheduler and start its run loop, unless it is read-only.
     pub fn new(
         options: IndexSchedulerOptions,
         #[cfg(test)] test_breakpoint_sdr: crossbeam::channel::Sender<(Breakpoint, bool)>,
@@ -361,7 +428,7 @@ impl IndexScheduler {
         std::fs::create_dir_all(&options.dumps_path)?;
 
         let env = heed::EnvOpenOptions::new()
-            .max_dbs(10)
+            .max_dbs(11)
             .map_size(options.task_db_size)
             .open(options.tasks_path)?;
         let file_store = FileStore::new(&options.update_file_path)?;
@@ -379,6 +446,7 @@ impl IndexScheduler {
             enqueued_at: env.create_database(Some(db_name::ENQUEUED_AT))?,
             started_at: env.create_database(Some(db_name::STARTED_AT))?,
             finished_at: env.create_database(Some(db_name::FINISHED_AT))?,
+            batches: env.create_database(Some(db_name::BATCHES))?,
             index_mapper: IndexMapper::new(
                 &env,
                 options.indexes_path,
@@ -389,8 +457,13 @@ impl IndexScheduler {
             // we want to start the loop right away in case meilisearch was ctrl+Ced while processing things
             wake_up: Arc::new(SignalEvent::auto(true)),
             autobatching_enabled: options.autobatching_enabled,
+            max_batched_documents: options.max_batched_documents,
+            max_batched_payload_size: options.max_batched_payload_size,
+            batch_max_wait_time: options.batch_max_wait_time,
+            task_notifiers: options.task_notifiers,
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
+            max_incremental_snapshots: options.max_incremental_snapshots,
             auth_path: options.auth_path,
             version_file_path: options.version_file_path,
 
@@ -402,7 +475,12 @@ impl IndexScheduler {
             run_loop_iteration: Arc::new(RwLock::new(0)),
         };
 
-        this.run();
+        if !options.read_only {
+            let rtxn = this.env.read_txn()?;
+            this.index_mapper.remove_orphaned_indexes(&rtxn)?;
+            drop(rtxn);
+            this.run();
+        }
         Ok(this)
     }
 
@@ -410,6 +488,11 @@ impl IndexScheduler {
         self.env.read_txn().map_err(|e| e.into())
     }
 
+    /// Return the maximum size, in bytes, an index can reach on disk.
+    pub fn index_map_size(&self) -> usize {
+        self.index_mapper.index_size()
+    }
+
     /// Start the run loop for the given index scheduler.
     ///
     /// This function will execute in a different thread and must be called
@@ -425,6 +508,23 @@ impl IndexScheduler {
                 loop {
                     run.wake_up.wait();
 
+                    if let Some(max_wait_time) = run.batch_max_wait_time {
+                        // Every registered task wakes us up, as it may fill the batch or have
+                        // to be processed right away.
+                        loop {
+                            match run.remaining_batch_wait_time(max_wait_time) {
+                                Ok(Some(wait_time)) => {
+                                    run.wake_up.wait_timeout(wait_time);
+                                }
+                                Ok(None) => break,
+                                Err(e) => {
+                                    log::error!("{}", e);
+                                    break;
+                                }
+                            }
+                        }
+                    }
+
                     match run.tick() {
                         Ok(0) => (),
                         Ok(_) => run.wake_up.signal(),
@@ -448,6 +548,83 @@ impl IndexScheduler {
             .unwrap();
     }
 
+    /// Return how long the scheduler must still wait before creating the next batch, for the
+    /// oldest enqueued task to have waited `max_wait_time`, or `None` if it already did.
+    ///
+    /// The scheduler never waits when the next batch can't grow anymore: when a task that isn't
+    /// batched with the others (a task cancelation or deletion, a snapshot, a dump) is enqueued,
+    /// or when the enqueued document additions already fill a batch.
+    fn remaining_batch_wait_time(&self, max_wait_time: Duration) -> Result<Option<Duration>> {
+        let rtxn = self.env.read_txn()?;
+        let enqueued = self.get_status(&rtxn, Status::Enqueued)?;
+        let oldest = match enqueued.min() {
+            Some(task_id) => self.get_task(&rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?,
+            None => return Ok(None),
+        };
+
+        for kind in [
+            Kind::TaskCancelation,
+            Kind::TaskDeletion,
+            Kind::SnapshotCreation,
+            Kind::DumpCreation,
+            Kind::DumpImport,
+        ] {
+            if !self.get_kind(&rtxn, kind)?.is_disjoint(&enqueued) {
+                return Ok(None);
+            }
+        }
+
+        let index_name = match oldest.indexes().first() {
+            Some(&index_name) => index_name,
+            None => return Ok(None),
+        };
+        if self.autobatching_enabled && self.batch_is_full(&rtxn, index_name, &enqueued)? {
+            return Ok(None);
+        }
+
+        let waited = OffsetDateTime::now_utc() - oldest.enqueued_at;
+        let waited = Duration::try_from(waited).unwrap_or_default();
+        Ok(max_wait_time.checked_sub(waited).filter(|wait_time| !wait_time.is_zero()))
+    }
+
+    /// Return `true` if the enqueued document additions of the index reach the maximum number
+    /// of documents or payload size of a batch.
+    fn batch_is_full(
+        &self,
+        rtxn: &RoTxn,
+        index_name: &str,
+        enqueued: &RoaringBitmap,
+    ) -> Result<bool> {
+        if self.max_batched_documents.is_none() && self.max_batched_payload_size.is_none() {
+            return Ok(false);
+        }
+
+        let mut batched_documents = 0;
+        let mut batched_payload_size = 0;
+        for task_id in self.index_tasks(rtxn, index_name)? & enqueued {
+            let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+            if let KindWithContent::DocumentAdditionOrUpdate {
+                content_file, documents_count, ..
+            } = task.kind
+            {
+                batched_documents += documents_count;
+                if let Some(max_batched_payload_size) = self.max_batched_payload_size {
+                    batched_payload_size += self.file_store.get_size(content_file)?;
+                    if batched_payload_size >= max_batched_payload_size {
+                        return Ok(true);
+                    }
+                }
+                if let Some(max_batched_documents) = self.max_batched_documents {
+                    if batched_documents >= max_batched_documents {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
     pub fn indexer_config(&self) -> &IndexerConfig {
         &self.index_mapper.indexer_config
     }
@@ -467,6 +644,13 @@ impl IndexScheduler {
         self.index_mapper.indexes(&rtxn)
     }
 
+    /// Return the space taken on disk by an index. Its fragmentation is only computed when
+    /// asked, as it requires a compacted copy of the whole index to be written on disk.
+    pub fn index_disk_usage(&self, name: &str, with_fragmentation: bool) -> Result<IndexDiskUsage> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.disk_usage(&rtxn, name, with_fragmentation)
+    }
+
     /// Return the task ids matched by the given query from the index scheduler's point of view.
     pub(crate) fn get_task_ids(&self, rtxn: &RoTxn, query: &Query) -> Result<RoaringBitmap> {
         let ProcessingTasks {
@@ -617,7 +801,7 @@ impl IndexScheduler {
         &self,
         rtxn: &RoTxn,
         query: &Query,
-        authorized_indexes: &Option<Vec<String>>,
+        authorized_indexes: &Option<Vec<IndexUidPattern>>,
     ) -> Result<RoaringBitmap> {
         let mut tasks = self.get_task_ids(rtxn, query)?;
 
@@ -635,7 +819,7 @@ impl IndexScheduler {
             let all_indexes_iter = self.index_tasks.iter(rtxn)?;
             for result in all_indexes_iter {
                 let (index, index_tasks) = result?;
-                if !authorized_indexes.contains(&index.to_owned()) {
+                if !authorized_indexes.iter().any(|pattern| pattern.matches_str(index)) {
                     tasks -= index_tasks;
                 }
             }
@@ -655,7 +839,7 @@ impl IndexScheduler {
     pub fn get_tasks_from_authorized_indexes(
         &self,
         query: Query,
-        authorized_indexes: Option<Vec<String>>,
+        authorized_indexes: Option<Vec<IndexUidPattern>>,
     ) -> Result<Vec<Task>> {
         let rtxn = self.env.read_txn()?;
 
@@ -685,10 +869,61 @@ impl IndexScheduler {
         }
     }
 
+    /// Return the batches matching the query, from the most recent to the oldest.
+    ///
+    /// A batch is only returned if the user has the rights to access all of its tasks.
+    pub fn get_batches_from_authorized_indexes(
+        &self,
+        query: BatchQuery,
+        authorized_indexes: Option<Vec<IndexUidPattern>>,
+    ) -> Result<Vec<Batch>> {
+        let rtxn = self.env.read_txn()?;
+
+        let authorized_tasks = self.get_task_ids_from_authorized_indexes(
+            &rtxn,
+            &Query::default(),
+            &authorized_indexes,
+        )?;
+
+        let limit = query.limit.unwrap_or(u32::MAX) as usize;
+        let mut batches = Vec::new();
+        for result in self.batches.rev_iter(&rtxn)? {
+            if batches.len() == limit {
+                break;
+            }
+
+            let (_, batch) = result?;
+            if query.from.map_or(false, |from| batch.uid > from) {
+                continue;
+            }
+            if query.uids.as_ref().map_or(false, |uids| !uids.contains(&batch.uid)) {
+                continue;
+            }
+            if batch.task_uids.iter().all(|uid| authorized_tasks.contains(*uid)) {
+                batches.push(batch);
+            }
+        }
+
+        Ok(batches)
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
     pub fn register(&self, kind: KindWithContent) -> Result<Task> {
+        let details = kind.default_details();
+        self.register_with_details(kind, details)
+    }
+
+    /// Register a new task in the scheduler with the given details instead of the default
+    /// ones, e.g. to keep track of the documents rejected while receiving the payload.
+    ///
+    /// If it fails and data was associated with the task, it tries to delete the associated data.
+    pub fn register_with_details(
+        &self,
+        kind: KindWithContent,
+        details: Option<Details>,
+    ) -> Result<Task> {
         let mut wtxn = self.env.write_txn()?;
 
         let mut task = Task {
@@ -698,7 +933,8 @@ impl IndexScheduler {
             finished_at: None,
             error: None,
             canceled_by: None,
-            details: kind.default_details(),
+            batch_uid: None,
+            details,
             status: Status::Enqueued,
             kind: kind.clone(),
         };
@@ -708,6 +944,8 @@ impl IndexScheduler {
         // If the register task is an index swap task, verify that it is well-formed
         // (that it does not contain duplicate indexes).
         check_index_swap_validity(&task)?;
+        // If the task sets the maximum size of an index, verify that the index can fit in it.
+        check_index_max_size_validity(&task)?;
 
         // Get rid of the mutability.
         let task = task;
@@ -762,13 +1000,20 @@ impl IndexScheduler {
         let mut wtxn = self.env.write_txn()?;
 
         let content_uuid = match content_file {
-            Some(content_file) if task.status == Status::Enqueued => {
+            Some(mut content_file) if task.status == Status::Enqueued => {
                 let (uuid, mut file) = self.create_update_file()?;
-                let mut builder = DocumentsBatchBuilder::new(file.as_file_mut());
-                for doc in content_file {
-                    builder.append_json_object(&doc?)?;
+                if let KindDump::SettingsImport { .. } = task.kind {
+                    // The delta of a settings import is the only object of its update file.
+                    let delta = content_file.next().ok_or(Error::CorruptedDump)??;
+                    serde_json::to_writer(file.as_file_mut(), &delta)
+                        .map_err(std::io::Error::from)?;
+                } else {
+                    let mut builder = DocumentsBatchBuilder::new(file.as_file_mut());
+                    for doc in content_file {
+                        builder.append_json_object(&doc?)?;
+                    }
+                    builder.into_inner()?;
                 }
-                builder.into_inner()?;
                 file.persist()?;
 
                 Some(uuid)
@@ -786,6 +1031,8 @@ impl IndexScheduler {
             finished_at: task.finished_at,
             error: task.error,
             canceled_by: task.canceled_by,
+            // The batches are not part of the dumps.
+            batch_uid: None,
             details: task.details,
             status: task.status,
             kind: match task.kind {
@@ -809,6 +1056,13 @@ impl IndexScheduler {
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
+                KindDump::DocumentEdition { filter_expr, edition } => {
+                    KindWithContent::DocumentEdition {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        filter_expr,
+                        edition,
+                    }
+                }
                 KindDump::Settings { settings, is_deletion, allow_index_creation } => {
                     KindWithContent::SettingsUpdate {
                         index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
@@ -820,13 +1074,17 @@ impl IndexScheduler {
                 KindDump::IndexDeletion => KindWithContent::IndexDeletion {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
-                KindDump::IndexCreation { primary_key } => KindWithContent::IndexCreation {
-                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
-                    primary_key,
-                },
-                KindDump::IndexUpdate { primary_key } => KindWithContent::IndexUpdate {
+                KindDump::IndexCreation { primary_key, max_size } => {
+                    KindWithContent::IndexCreation {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        primary_key,
+                        max_size,
+                    }
+                }
+                KindDump::IndexUpdate { primary_key, max_size } => KindWithContent::IndexUpdate {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
+                    max_size,
                 },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
                 KindDump::TaskCancelation { query, tasks } => {
@@ -839,6 +1097,18 @@ impl IndexScheduler {
                     KindWithContent::DumpCreation { keys, instance_uid }
                 }
                 KindDump::SnapshotCreation => KindWithContent::SnapshotCreation,
+                // The uploaded dumps are not part of the dumps, an enqueued import can't be restored.
+                KindDump::DumpImport => KindWithContent::DumpImport {
+                    content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                },
+                KindDump::SettingsImport { added_entries, removed_entries } => {
+                    KindWithContent::SettingsImport {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                        added_entries,
+                        removed_entries,
+                    }
+                }
             },
         };
 
@@ -865,13 +1135,79 @@ impl IndexScheduler {
     }
 
     /// Create a new index without any associated task.
-    pub fn create_raw_index(&self, name: &str) -> Result<Index> {
+    pub fn create_raw_index(&self, name: &str, max_size: Option<u64>) -> Result<Index> {
         let wtxn = self.env.write_txn()?;
-        let index = self.index_mapper.create_index(wtxn, name)?;
+        let index = self.index_mapper.create_index(wtxn, name, max_size)?;
 
         Ok(index)
     }
 
+    /// Import the primary key, the settings and the documents of an index of a dump
+    /// into an empty index. Returns the number of imported documents.
+    pub fn import_dumped_index(
+        &self,
+        index: &Index,
+        index_reader: &mut DumpIndexReader,
+    ) -> Result<u64> {
+        let indexer_config = self.indexer_config();
+        let primary_key = index_reader.metadata().primary_key.clone();
+        let mut wtxn = index.write_txn()?;
+
+        let mut builder = milli::update::Settings::new(&mut wtxn, index, indexer_config);
+        // 1. Import the primary key if there is one.
+        if let Some(primary_key) = primary_key {
+            builder.set_primary_key(primary_key);
+        }
+
+        // 2. Import the settings.
+        log::info!("Importing the settings.");
+        let settings = index_reader.settings()?;
+        apply_settings_to_builder(&settings, &mut builder);
+        let must_stop_processing = self.must_stop_processing.clone();
+        builder.execute(
+            |indexing_step| log::debug!("update: {:?}", indexing_step),
+            || must_stop_processing.get(),
+        )?;
+
+        // 3. Import the documents.
+        // 3.1. We need to recreate the grenad+obkv format accepted by the index.
+        log::info!("Importing the documents.");
+        let file = tempfile::tempfile()?;
+        let mut builder = DocumentsBatchBuilder::new(BufWriter::new(file));
+        for document in index_reader.documents()? {
+            builder.append_json_object(&document?)?;
+        }
+
+        // This flush the content of the batch builder.
+        let file = builder.into_inner()?.into_inner().map_err(IntoInnerError::into_error)?;
+
+        // 3.2. We feed it to the milli index.
+        let reader = BufReader::new(file);
+        let reader = DocumentsBatchReader::from_reader(reader).map_err(milli::Error::from)?;
+
+        let must_stop_processing = self.must_stop_processing.clone();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            index,
+            indexer_config,
+            IndexDocumentsConfig {
+                update_method: IndexDocumentsMethod::ReplaceDocuments,
+                ..Default::default()
+            },
+            |indexing_step| log::debug!("update: {:?}", indexing_step),
+            || must_stop_processing.get(),
+        )?;
+
+        let (builder, user_result) = builder.add_documents(reader)?;
+        let documents_count = user_result.map_err(milli::Error::from)?;
+        log::info!("{} documents found.", documents_count);
+        builder.execute()?;
+        wtxn.commit()?;
+        log::info!("All documents successfully imported.");
+
+        Ok(documents_count)
+    }
+
     /// Create a file and register it in the index scheduler.
     ///
     /// The returned file and uuid can be used to associate
@@ -893,6 +1229,23 @@ impl IndexScheduler {
         Ok(self.file_store.delete(uuid)?)
     }
 
+    /// Open the file of a dump created by a `dumpCreation` task.
+    ///
+    /// The dump uid is the one reported in the details of the task.
+    pub fn dump_file(&self, dump_uid: &str) -> Result<std::fs::File> {
+        // The dump uids are dates; rejecting anything else prevents from
+        // escaping the dumps directory with something like `../`.
+        if dump_uid.is_empty() || !dump_uid.chars().all(|c| c.is_ascii_digit() || c == '-') {
+            return Err(Error::DumpNotFound(dump_uid.to_string()));
+        }
+
+        let path = self.dumps_path.join(format!("{dump_uid}.dump"));
+        std::fs::File::open(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::DumpNotFound(dump_uid.to_string()),
+            _ => e.into(),
+        })
+    }
+
     /// Perform one iteration of the run loop.
     ///
     /// 1. Find the next batch of tasks to be processed.
@@ -918,13 +1271,30 @@ impl IndexScheduler {
                 Some(batch) => batch,
                 None => return Ok(0),
             };
-        drop(rtxn);
+        // The uid is taken before processing the batch, to never be reused when
+        // the batch deletes the most recent batches along with their tasks.
+        let batch_uid = self.next_batch_id(&rtxn).map_err(|e| Error::CreateBatch(Box::new(e)))?;
 
         // 1. store the starting date with the bitmap of processing tasks.
         let mut ids = batch.ids();
         ids.sort_unstable();
         let processed_tasks = ids.len();
         let processing_tasks = RoaringBitmap::from_sorted_iter(ids.iter().copied()).unwrap();
+
+        // Everything logged while the batch is processed, even by milli, is attached to its span.
+        // The span of each of its tasks is opened until the outcome of the task is logged.
+        let batch_span = tracing::info_span!("batch", batch_uid, task_uids = ?ids);
+        let mut task_spans: HashMap<TaskId, tracing::Span> = self
+            .get_existing_tasks(&rtxn, ids.iter().copied())
+            .map_err(|e| Error::CreateBatch(Box::new(e)))?
+            .iter()
+            .map(|task| (task.uid, task_span(&batch_span, task)))
+            .collect();
+        drop(rtxn);
+        let mut processed_task_span = |task: &Task| {
+            task_spans.remove(&task.uid).unwrap_or_else(|| task_span(&batch_span, task))
+        };
+
         let started_at = OffsetDateTime::now_utc();
 
         // We reset the must_stop flag to be sure that we don't stop processing tasks
@@ -934,12 +1304,15 @@ impl IndexScheduler {
         #[cfg(test)]
         self.breakpoint(Breakpoint::BatchCreated);
 
+        let _entered = batch_span.clone().entered();
+
         // 2. Process the tasks
         let res = {
             let cloned_index_scheduler = self.private_clone();
+            let batch_span = batch_span.clone();
             let handle = std::thread::Builder::new()
                 .name(String::from("batch-operation"))
-                .spawn(move || cloned_index_scheduler.process_batch(batch))
+                .spawn(move || batch_span.in_scope(|| cloned_index_scheduler.process_batch(batch)))
                 .unwrap();
             handle.join().unwrap_or(Err(Error::ProcessBatchPanicked))
         };
@@ -950,6 +1323,7 @@ impl IndexScheduler {
         let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
 
         let finished_at = OffsetDateTime::now_utc();
+        let mut finished_tasks = Vec::new();
         match res {
             Ok(tasks) => {
                 #[cfg(test)]
@@ -959,6 +1333,7 @@ impl IndexScheduler {
                 for (i, mut task) in tasks.into_iter().enumerate() {
                     task.started_at = Some(started_at);
                     task.finished_at = Some(finished_at);
+                    task.batch_uid = Some(batch_uid);
 
                     #[cfg(test)]
                     self.maybe_fail(
@@ -972,6 +1347,8 @@ impl IndexScheduler {
                     if let Err(e) = self.delete_persisted_task_data(&task) {
                         log::error!("Failure to delete the content files associated with task {}. Error: {e}", task.uid);
                     }
+                    trace_processed_task(processed_task_span(&task), &task);
+                    finished_tasks.push(task);
                 }
                 log::info!("A batch of tasks was successfully completed.");
             }
@@ -1002,6 +1379,7 @@ impl IndexScheduler {
                         .ok_or(Error::CorruptedTaskQueue)?;
                     task.started_at = Some(started_at);
                     task.finished_at = Some(finished_at);
+                    task.batch_uid = Some(batch_uid);
                     task.status = Status::Failed;
                     task.error = Some(error.clone());
                     task.details = task.details.map(|d| d.to_failed());
@@ -1014,10 +1392,29 @@ impl IndexScheduler {
                     }
                     self.update_task(&mut wtxn, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
+                    trace_processed_task(processed_task_span(&task), &task);
+                    finished_tasks.push(task);
                 }
             }
         }
 
+        if !finished_tasks.is_empty() {
+            let mut task_uids: Vec<_> = finished_tasks.iter().map(|task| task.uid).collect();
+            task_uids.sort_unstable();
+            let mut index_uids: Vec<_> = finished_tasks
+                .iter()
+                .flat_map(|task| task.indexes())
+                .map(ToOwned::to_owned)
+                .collect();
+            index_uids.sort_unstable();
+            index_uids.dedup();
+
+            let batch = Batch { uid: batch_uid, task_uids, index_uids, started_at, finished_at };
+            self.batches
+                .put(&mut wtxn, &BEU32::new(batch_uid), &batch)
+                .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e.into())))?;
+        }
+
         self.processing_tasks.write().unwrap().stop_processing();
 
         #[cfg(test)]
@@ -1025,6 +1422,18 @@ impl IndexScheduler {
 
         wtxn.commit().map_err(Error::HeedTransaction)?;
 
+        // The tasks are only notified once we are sure they are stored with their final state.
+        for notifier in &self.task_notifiers {
+            for task in &finished_tasks {
+                if notifier.send(task.clone()).is_err() {
+                    log::error!(
+                        "A task notifier has been dropped; no more tasks will be sent to it."
+                    );
+                    break;
+                }
+            }
+        }
+
         #[cfg(test)]
         self.breakpoint(Breakpoint::AfterProcessing);
 
@@ -1075,6 +1484,7 @@ mod tests {
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
         ReplaceDocuments, UpdateDocuments,
     };
+    use meilisearch_types::milli::update::Setting;
     use meilisearch_types::tasks::IndexSwap;
     use meilisearch_types::VERSION_FILE_NAME;
     use tempfile::TempDir;
@@ -1083,6 +1493,7 @@ mod tests {
     use Breakpoint::*;
 
     use super::*;
+    use crate::index_mapper::MIN_INDEX_MAX_SIZE;
     use crate::insta_snapshot::{snapshot_bitmap, snapshot_index_scheduler};
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1100,23 +1511,39 @@ mod tests {
         pub fn test(
             autobatching_enabled: bool,
             planned_failures: Vec<(usize, FailureLocation)>,
+        ) -> (Self, IndexSchedulerHandle) {
+            Self::test_with_custom_config(planned_failures, |options| {
+                options.autobatching_enabled = autobatching_enabled;
+            })
+        }
+
+        pub fn test_with_custom_config(
+            planned_failures: Vec<(usize, FailureLocation)>,
+            configuration: impl FnOnce(&mut IndexSchedulerOptions),
         ) -> (Self, IndexSchedulerHandle) {
             let tempdir = TempDir::new().unwrap();
             let (sender, receiver) = crossbeam::channel::bounded(0);
 
-            let options = IndexSchedulerOptions {
+            let mut options = IndexSchedulerOptions {
                 version_file_path: tempdir.path().join(VERSION_FILE_NAME),
                 auth_path: tempdir.path().join("auth"),
                 tasks_path: tempdir.path().join("db_path"),
                 update_file_path: tempdir.path().join("file_store"),
                 indexes_path: tempdir.path().join("indexes"),
                 snapshots_path: tempdir.path().join("snapshots"),
+                max_incremental_snapshots: 0,
                 dumps_path: tempdir.path().join("dumps"),
                 task_db_size: 1024 * 1024, // 1 MiB
                 index_size: 1024 * 1024,   // 1 MiB
                 indexer_config: IndexerConfig::default(),
-                autobatching_enabled,
+                autobatching_enabled: true,
+                max_batched_documents: None,
+                max_batched_payload_size: None,
+                batch_max_wait_time: None,
+                task_notifiers: Vec::new(),
+                read_only: false,
             };
+            configuration(&mut options);
 
             let index_scheduler = Self::new(options, sender, planned_failures).unwrap();
 
@@ -1158,7 +1585,11 @@ mod tests {
 
     /// Return a `KindWithContent::IndexCreation` task
     fn index_creation_task(index: &'static str, primary_key: &'static str) -> KindWithContent {
-        KindWithContent::IndexCreation { index_uid: S(index), primary_key: Some(S(primary_key)) }
+        KindWithContent::IndexCreation {
+            index_uid: S(index),
+            primary_key: Some(S(primary_key)),
+            max_size: Setting::NotSet,
+        }
     }
     /// Create a `KindWithContent::DocumentImport` task that imports documents.
     ///
@@ -1386,12 +1817,20 @@ mod tests {
         let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
 
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
 
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("cattos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("cattos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_second_task");
 
@@ -1415,7 +1854,11 @@ mod tests {
         let (index_scheduler, mut handle) = IndexScheduler::test(false, vec![]);
 
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
 
@@ -1618,7 +2061,11 @@ mod tests {
         }"#;
 
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
 
@@ -1660,6 +2107,7 @@ mod tests {
                 .register(KindWithContent::IndexCreation {
                     index_uid: name.to_string(),
                     primary_key: None,
+                    max_size: Setting::NotSet,
                 })
                 .unwrap();
             index_scheduler.assert_internally_consistent();
@@ -2514,7 +2962,11 @@ mod tests {
 
         let query = Query { index_uids: Some(vec!["catto".to_owned()]), ..Default::default() };
         let tasks = index_scheduler
-            .get_task_ids_from_authorized_indexes(&rtxn, &query, &Some(vec!["doggo".to_owned()]))
+            .get_task_ids_from_authorized_indexes(
+                &rtxn,
+                &query,
+                &Some(vec![IndexUidPattern::new_unchecked("doggo")]),
+            )
             .unwrap();
         // we have asked for only the tasks associated with catto, but are only authorized to retrieve the tasks
         // associated with doggo -> empty result
@@ -2522,7 +2974,11 @@ mod tests {
 
         let query = Query::default();
         let tasks = index_scheduler
-            .get_task_ids_from_authorized_indexes(&rtxn, &query, &Some(vec!["doggo".to_owned()]))
+            .get_task_ids_from_authorized_indexes(
+                &rtxn,
+                &query,
+                &Some(vec![IndexUidPattern::new_unchecked("doggo")]),
+            )
             .unwrap();
         // we asked for all the tasks, but we are only authorized to retrieve the doggo tasks
         // -> only the index creation of doggo should be returned
@@ -2533,7 +2989,10 @@ mod tests {
             .get_task_ids_from_authorized_indexes(
                 &rtxn,
                 &query,
-                &Some(vec!["catto".to_owned(), "doggo".to_owned()]),
+                &Some(vec![
+                    IndexUidPattern::new_unchecked("catto"),
+                    IndexUidPattern::new_unchecked("doggo"),
+                ]),
             )
             .unwrap();
         // we asked for all the tasks, but we are only authorized to retrieve the doggo and catto tasks
@@ -2581,7 +3040,11 @@ mod tests {
 
         let query = Query { canceled_by: Some(vec![task_cancelation.uid]), ..Query::default() };
         let tasks = index_scheduler
-            .get_task_ids_from_authorized_indexes(&rtxn, &query, &Some(vec!["doggo".to_string()]))
+            .get_task_ids_from_authorized_indexes(
+                &rtxn,
+                &query,
+                &Some(vec![IndexUidPattern::new_unchecked("doggo")]),
+            )
             .unwrap();
         // Return only 1 because the user is not authorized to see task 2
         snapshot!(snapshot_bitmap(&tasks), @"[1,]");
@@ -2808,7 +3271,11 @@ mod tests {
 
         // Create the index.
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
         handle.advance_one_successful_batch();
@@ -2871,7 +3338,11 @@ mod tests {
 
         // Create the index.
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
         handle.advance_one_successful_batch();
@@ -2938,7 +3409,11 @@ mod tests {
 
         // Create the index.
         index_scheduler
-            .register(KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None })
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::NotSet,
+            })
             .unwrap();
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "registered_the_first_task");
         handle.advance_one_successful_batch();
@@ -3070,4 +3545,285 @@ mod tests {
         // No matter what happens in process_batch, the index_scheduler should be internally consistent
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "index_creation_failed");
     }
+
+    #[test]
+    fn batches_are_recorded() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        for i in 0..3 {
+            let (file, documents_count) = sample_documents(&index_scheduler, i, i as usize);
+            file.persist().unwrap();
+            index_scheduler
+                .register(replace_document_import_task("doggos", Some("id"), i, documents_count))
+                .unwrap();
+        }
+        handle.advance_n_successful_batches(1);
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(BatchQuery::default(), None)
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].uid, 0);
+        assert_eq!(batches[0].task_uids, vec![0, 1, 2]);
+        assert_eq!(batches[0].index_uids, vec![S("doggos")]);
+        let rtxn = index_scheduler.read_txn().unwrap();
+        for task_uid in 0..3 {
+            let task = index_scheduler.get_task(&rtxn, task_uid).unwrap().unwrap();
+            assert_eq!(task.batch_uid, Some(0));
+        }
+        drop(rtxn);
+
+        index_scheduler
+            .register(KindWithContent::DocumentClear { index_uid: S("doggos") })
+            .unwrap();
+        handle.advance_n_successful_batches(1);
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(BatchQuery::default(), None)
+            .unwrap();
+        let uids: Vec<_> = batches.iter().map(|batch| batch.uid).collect();
+        assert_eq!(uids, vec![1, 0]);
+        let query = BatchQuery { from: Some(0), ..BatchQuery::default() };
+        let batches = index_scheduler.get_batches_from_authorized_indexes(query, None).unwrap();
+        assert_eq!(batches.len(), 1);
+        let authorized_indexes = Some(vec![IndexUidPattern::new_unchecked("catto")]);
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(BatchQuery::default(), authorized_indexes)
+            .unwrap();
+        assert!(batches.is_empty());
+
+        // Deleting every task of a batch deletes the batch.
+        index_scheduler
+            .register(KindWithContent::TaskDeletion {
+                query: S("test_query"),
+                tasks: RoaringBitmap::from_iter([0, 1, 2]),
+            })
+            .unwrap();
+        handle.advance_n_successful_batches(1);
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(BatchQuery::default(), None)
+            .unwrap();
+        let uids: Vec<_> = batches.iter().map(|batch| batch.uid).collect();
+        assert_eq!(uids, vec![2, 1]);
+        index_scheduler.assert_internally_consistent();
+    }
+
+    #[test]
+    fn max_batched_documents() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |options| {
+                options.max_batched_documents = Some(2);
+            });
+
+        for i in 0..3 {
+            let (file, documents_count) = sample_documents(&index_scheduler, i, i as usize);
+            file.persist().unwrap();
+            index_scheduler
+                .register(replace_document_import_task("doggos", Some("id"), i, documents_count))
+                .unwrap();
+        }
+        handle.advance_n_successful_batches(2);
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(BatchQuery::default(), None)
+            .unwrap();
+        let task_uids: Vec<_> = batches.iter().map(|batch| batch.task_uids.clone()).collect();
+        assert_eq!(task_uids, vec![vec![2], vec![0, 1]]);
+    }
+
+    #[test]
+    fn batch_wait_time_ends_when_the_batch_is_full() {
+        let (index_scheduler, _handle) =
+            IndexScheduler::test_with_custom_config(vec![], |options| {
+                options.max_batched_documents = Some(2);
+            });
+        let max_wait_time = std::time::Duration::from_secs(3600);
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+        file.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("doggos", Some("id"), 0, documents_count))
+            .unwrap();
+        assert!(index_scheduler.remaining_batch_wait_time(max_wait_time).unwrap().is_some());
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 1, 1);
+        file.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("doggos", Some("id"), 1, documents_count))
+            .unwrap();
+        assert_eq!(index_scheduler.remaining_batch_wait_time(max_wait_time).unwrap(), None);
+    }
+
+    #[test]
+    fn batch_wait_time_ends_when_a_priority_task_is_enqueued() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+        let max_wait_time = std::time::Duration::from_secs(3600);
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+        file.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("doggos", Some("id"), 0, documents_count))
+            .unwrap();
+        assert!(index_scheduler.remaining_batch_wait_time(max_wait_time).unwrap().is_some());
+
+        index_scheduler
+            .register(KindWithContent::TaskCancelation {
+                query: S("test_query"),
+                tasks: RoaringBitmap::from_iter([0]),
+            })
+            .unwrap();
+        assert_eq!(index_scheduler.remaining_batch_wait_time(max_wait_time).unwrap(), None);
+    }
+
+    #[test]
+    fn register_an_index_max_size_below_the_minimum() {
+        let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+        let err = index_scheduler
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::Set(0),
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidIndexMaxSize { max_size: 0, .. }));
+
+        let err = index_scheduler
+            .register(KindWithContent::IndexUpdate {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::Set(MIN_INDEX_MAX_SIZE - 1),
+            })
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidIndexMaxSize { .. }));
+    }
+
+    #[test]
+    fn index_is_resized_when_its_max_size_is_updated() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |options| {
+                options.index_size = 16 * 1024 * 1024;
+            });
+
+        index_scheduler
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::Set(2 * 1024 * 1024),
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        index_scheduler.index("doggos").unwrap();
+        let usage = index_scheduler.index_disk_usage("doggos", false).unwrap();
+        assert_eq!(usage.max_size, Some(2 * 1024 * 1024));
+        assert_eq!(usage.map_size, 2 * 1024 * 1024);
+
+        index_scheduler
+            .register(KindWithContent::IndexUpdate {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::Set(4 * 1024 * 1024),
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        // Nobody uses the index anymore, it is reopened with its new size.
+        index_scheduler.index("doggos").unwrap();
+        let usage = index_scheduler.index_disk_usage("doggos", false).unwrap();
+        assert_eq!(usage.max_size, Some(4 * 1024 * 1024));
+        assert_eq!(usage.map_size, 4 * 1024 * 1024);
+
+        index_scheduler
+            .register(KindWithContent::IndexUpdate {
+                index_uid: S("doggos"),
+                primary_key: None,
+                max_size: Setting::Reset,
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        index_scheduler.index("doggos").unwrap();
+        let usage = index_scheduler.index_disk_usage("doggos", false).unwrap();
+        assert_eq!(usage.max_size, None);
+        assert_eq!(usage.map_size, 16 * 1024 * 1024);
+    }
+
+    #[test]
+    fn document_addition_fails_when_the_index_max_size_is_reached() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |options| {
+                options.index_size = 16 * 1024 * 1024;
+            });
+
+        index_scheduler
+            .register(KindWithContent::IndexCreation {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                max_size: Setting::Set(MIN_INDEX_MAX_SIZE),
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let documents: Vec<_> = (0..5000)
+            .map(|i| {
+                let words: Vec<_> = (0..20).map(|j| format!("word{i}x{j}")).collect();
+                serde_json::json!({ "id": i, "description": words.join(" ") })
+            })
+            .collect();
+        let content = serde_json::to_string(&documents).unwrap();
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count =
+            meilisearch_types::document_formats::read_json(content.as_bytes(), file.as_file_mut())
+                .unwrap() as u64;
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: false,
+            })
+            .unwrap();
+        handle.advance_one_failed_batch();
+
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+        assert_eq!(task.status, Status::Failed);
+        let error = serde_json::to_value(task.error.unwrap()).unwrap();
+        assert_eq!(error["code"], "index_size_limit_reached");
+    }
+
+    #[test]
+    fn dump_import_waits_for_the_tasks_enqueued_before_it() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+        file.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("doggos", Some("id"), 0, documents_count))
+            .unwrap();
+        let (uuid, file) = index_scheduler.create_update_file_with_uuid(1).unwrap();
+        file.persist().unwrap();
+        index_scheduler.register(KindWithContent::DumpImport { content_file: uuid }).unwrap();
+        let (file, documents_count) = sample_documents(&index_scheduler, 2, 2);
+        file.persist().unwrap();
+        index_scheduler
+            .register(replace_document_import_task("doggos", Some("id"), 2, documents_count))
+            .unwrap();
+
+        handle.advance_one_successful_batch();
+        // The update file isn't a dump.
+        handle.advance_one_failed_batch();
+        handle.advance_one_successful_batch();
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(BatchQuery::default(), None)
+            .unwrap();
+        let task_uids: Vec<_> = batches.iter().map(|batch| batch.task_uids.clone()).collect();
+        assert_eq!(task_uids, vec![vec![2], vec![1], vec![0]]);
+    }
 }
@@ -0,0 +1,233 @@
+/*!
+Incremental snapshots.
+
+A full snapshot, named `<db>.snapshot`, contains everything needed to restart
+an instance. When incremental snapshots are enabled, the next snapshots of the
+chain are named `<db>.snapshot.1`, `<db>.snapshot.2`, etc. and only contain
+the indexes that were modified since the previous snapshot of the chain,
+along with the task queue and the auth database which are always small.
+
+Snapshots are only incremental per index: a modified index is copied in full,
+however small its modification is. Only copying the modified pages of an index
+is not supported yet.
+
+Every snapshot contains a manifest listing all the indexes of the database,
+this is how the indexes deleted between two snapshots are detected when
+the chain is replayed.
+*/
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use meilisearch_types::compression;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// The name of the manifest file, at the root of each snapshot.
+pub(crate) const MANIFEST_FILE_NAME: &str = "snapshot-manifest.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SnapshotManifest {
+    /// The position of the snapshot in its chain, `0` being the full snapshot.
+    pub position: u32,
+    /// Every index of the database when the snapshot was made.
+    pub indexes: Vec<IndexManifest>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IndexManifest {
+    pub uuid: Uuid,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl SnapshotManifest {
+    /// Returns `true` if the index changed since this manifest was made.
+    pub fn index_changed(&self, uuid: Uuid, updated_at: OffsetDateTime) -> bool {
+        !self.indexes.iter().any(|index| index.uuid == uuid && index.updated_at == updated_at)
+    }
+
+    pub fn read_from(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let file = fs::File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    pub fn write_to(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Returns the path of the snapshot at the given position of the chain starting at `base`.
+pub(crate) fn snapshot_in_chain(base: &Path, position: u32) -> PathBuf {
+    if position == 0 {
+        base.to_path_buf()
+    } else {
+        let mut path = base.as_os_str().to_owned();
+        path.push(format!(".{position}"));
+        PathBuf::from(path)
+    }
+}
+
+/// Import the full snapshot at `snapshot_path` in `db_path`, then replay
+/// the incremental snapshots that were made after it, in order.
+pub fn import_snapshot(snapshot_path: &Path, db_path: &Path) -> anyhow::Result<()> {
+    compression::from_tar_gz(snapshot_path, db_path)?;
+    // The manifest is only useful to the snapshots, it has nothing to do in a database.
+    let manifest_path = db_path.join(MANIFEST_FILE_NAME);
+    if manifest_path.exists() {
+        fs::remove_file(manifest_path)?;
+    }
+
+    for position in 1.. {
+        let incremental = snapshot_in_chain(snapshot_path, position);
+        if !incremental.exists() {
+            break;
+        }
+
+        let temp_dir = tempfile::tempdir()?;
+        compression::from_tar_gz(&incremental, temp_dir.path())?;
+        apply_incremental_snapshot(temp_dir.path(), db_path, position)
+            .with_context(|| format!("while replaying the snapshot {}", incremental.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Apply the content of an extracted incremental snapshot on top of `db_path`.
+fn apply_incremental_snapshot(src: &Path, db_path: &Path, position: u32) -> anyhow::Result<()> {
+    let manifest = SnapshotManifest::read_from(src.join(MANIFEST_FILE_NAME))?;
+    if manifest.position != position {
+        bail!(
+            "expected the snapshot at position {position} of the chain, found the one at position {}",
+            manifest.position
+        );
+    }
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let dst = db_path.join(&name);
+        if name == MANIFEST_FILE_NAME {
+            continue;
+        } else if name == "indexes" {
+            // Only the modified indexes are in the snapshot, the other ones must be kept.
+            for index in fs::read_dir(entry.path())? {
+                let index = index?;
+                replace(&index.path(), &dst.join(index.file_name()))?;
+            }
+        } else {
+            replace(&entry.path(), &dst)?;
+        }
+    }
+
+    // Remove the indexes that were deleted since the previous snapshot.
+    let indexes_path = db_path.join("indexes");
+    if indexes_path.exists() {
+        for index in fs::read_dir(&indexes_path)? {
+            let index = index?;
+            let uuid = index.file_name().to_str().and_then(|name| Uuid::parse_str(name).ok());
+            if !matches!(uuid, Some(uuid) if manifest.indexes.iter().any(|i| i.uuid == uuid)) {
+                fs::remove_dir_all(index.path())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace the file or directory at `dst` by a copy of `src`.
+fn replace(src: &Path, dst: &Path) -> anyhow::Result<()> {
+    if dst.is_dir() {
+        fs::remove_dir_all(dst)?;
+    } else if dst.exists() {
+        fs::remove_file(dst)?;
+    }
+
+    if src.is_dir() {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            replace(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(src, dst)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(position: u32, indexes: &[Uuid]) -> SnapshotManifest {
+        let updated_at = OffsetDateTime::UNIX_EPOCH;
+        SnapshotManifest {
+            position,
+            indexes: indexes.iter().map(|&uuid| IndexManifest { uuid, updated_at }).collect(),
+        }
+    }
+
+    fn write(path: PathBuf, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn snapshot_chain_naming() {
+        let base = Path::new("snapshots/data.ms.snapshot");
+        assert_eq!(snapshot_in_chain(base, 0), base);
+        assert_eq!(snapshot_in_chain(base, 2), Path::new("snapshots/data.ms.snapshot.2"));
+    }
+
+    #[test]
+    fn apply_an_incremental_snapshot() {
+        let (kept, modified, deleted, created) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let db = tempfile::tempdir().unwrap();
+        write(db.path().join("tasks/data.mdb"), "old tasks");
+        write(db.path().join(format!("indexes/{kept}/data.mdb")), "kept");
+        write(db.path().join(format!("indexes/{modified}/data.mdb")), "old modified");
+        write(db.path().join(format!("indexes/{deleted}/data.mdb")), "deleted");
+        write(db.path().join("update_files/old"), "old update file");
+
+        let incremental = tempfile::tempdir().unwrap();
+        manifest(1, &[kept, modified, created])
+            .write_to(incremental.path().join(MANIFEST_FILE_NAME))
+            .unwrap();
+        write(incremental.path().join("tasks/data.mdb"), "new tasks");
+        write(incremental.path().join(format!("indexes/{modified}/data.mdb")), "new modified");
+        write(incremental.path().join(format!("indexes/{created}/data.mdb")), "created");
+        fs::create_dir_all(incremental.path().join("update_files")).unwrap();
+
+        apply_incremental_snapshot(incremental.path(), db.path(), 1).unwrap();
+
+        let read = |path: String| fs::read_to_string(db.path().join(path)).unwrap();
+        assert_eq!(read(String::from("tasks/data.mdb")), "new tasks");
+        assert_eq!(read(format!("indexes/{kept}/data.mdb")), "kept");
+        assert_eq!(read(format!("indexes/{modified}/data.mdb")), "new modified");
+        assert_eq!(read(format!("indexes/{created}/data.mdb")), "created");
+        assert!(!db.path().join(format!("indexes/{deleted}")).exists());
+        assert!(!db.path().join("update_files/old").exists());
+        assert!(!db.path().join(MANIFEST_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn refuse_a_snapshot_out_of_order() {
+        let db = tempfile::tempdir().unwrap();
+        let incremental = tempfile::tempdir().unwrap();
+        manifest(2, &[]).write_to(incremental.path().join(MANIFEST_FILE_NAME)).unwrap();
+
+        let error = apply_incremental_snapshot(incremental.path(), db.path(), 1).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected the snapshot at position 1 of the chain, found the one at position 2"
+        );
+    }
+}
@@ -3,13 +3,16 @@
 use std::collections::{BTreeSet, HashSet};
 use std::ops::Bound;
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::heed::types::{DecodeIgnore, OwnedType};
 use meilisearch_types::heed::{Database, RoTxn, RwTxn};
+use meilisearch_types::milli::update::Setting;
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
 use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status};
 use roaring::{MultiOps, RoaringBitmap};
 use time::OffsetDateTime;
 
+use crate::index_mapper::MIN_INDEX_MAX_SIZE;
 use crate::{Error, IndexScheduler, Result, Task, TaskId, BEI128};
 
 impl IndexScheduler {
@@ -25,6 +28,15 @@ impl IndexScheduler {
         Ok(self.last_task_id(rtxn)?.unwrap_or_default())
     }
 
+    pub(crate) fn next_batch_id(&self, rtxn: &RoTxn) -> Result<BatchId> {
+        Ok(self
+            .batches
+            .remap_data_type::<DecodeIgnore>()
+            .last(rtxn)?
+            .map(|(k, _)| k.get() + 1)
+            .unwrap_or_default())
+    }
+
     pub(crate) fn get_task(&self, rtxn: &RoTxn, task_id: TaskId) -> Result<Option<Task>> {
         Ok(self.all_tasks.get(rtxn, &BEU32::new(task_id))?)
     }
@@ -240,6 +252,8 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
+        K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
+        K::SettingsImport { index_uid, .. } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
@@ -257,6 +271,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
+        | K::DumpImport { .. }
         | K::SnapshotCreation => (),
     };
     if let Some(Details::IndexSwap { swaps }) = &mut task.details {
@@ -324,6 +339,46 @@ pub(crate) fn check_index_swap_validity(task: &Task) -> Result<()> {
     Ok(())
 }
 
+/// Checks that the maximum size given to an index leaves room for at least an empty index.
+pub(crate) fn check_index_max_size_validity(task: &Task) -> Result<()> {
+    match task.kind {
+        KindWithContent::IndexCreation { max_size: Setting::Set(max_size), .. }
+        | KindWithContent::IndexUpdate { max_size: Setting::Set(max_size), .. }
+            if max_size < MIN_INDEX_MAX_SIZE =>
+        {
+            Err(Error::InvalidIndexMaxSize { max_size, min_size: MIN_INDEX_MAX_SIZE })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Opens the span describing a task of the batch, before the batch is processed. It is closed
+/// once the outcome of the task is logged by [`trace_processed_task`].
+pub(crate) fn task_span(batch_span: &tracing::Span, task: &Task) -> tracing::Span {
+    tracing::info_span!(
+        parent: batch_span,
+        "task",
+        task_uid = task.uid,
+        index_uid = task.index_uid(),
+        task_type = %task.kind.as_kind(),
+        duration_ms = tracing::field::Empty,
+    )
+}
+
+/// Logs the outcome of a processed task inside of the span describing it.
+pub(crate) fn trace_processed_task(span: tracing::Span, task: &Task) {
+    let duration_ms = match (task.started_at, task.finished_at) {
+        (Some(started_at), Some(finished_at)) => (finished_at - started_at).whole_milliseconds(),
+        _ => 0,
+    };
+    span.record("duration_ms", duration_ms as u64);
+    let _entered = span.enter();
+    match &task.error {
+        Some(error) => tracing::warn!(error = %error, "The task failed."),
+        None => tracing::info!("The task succeeded."),
+    }
+}
+
 #[cfg(test)]
 impl IndexScheduler {
     /// Asserts that the index scheduler's content is internally consistent.
@@ -342,6 +397,7 @@ impl IndexScheduler {
                 finished_at,
                 error: _,
                 canceled_by,
+                batch_uid,
                 details,
                 status,
                 kind,
@@ -377,6 +433,10 @@ impl IndexScheduler {
                     .unwrap();
                 assert!(db_finished_at.contains(task_id));
             }
+            if let Some(batch_uid) = batch_uid {
+                let batch = self.batches.get(&rtxn, &BEU32::new(batch_uid)).unwrap().unwrap();
+                assert!(batch.task_uids.contains(&uid));
+            }
             if let Some(canceled_by) = canceled_by {
                 let db_canceled_tasks = self.get_status(&rtxn, Status::Canceled).unwrap();
                 assert!(db_canceled_tasks.contains(uid));
@@ -396,7 +456,11 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        ..
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(0) => assert_ne!(status, Status::Enqueued),
@@ -414,15 +478,38 @@ impl IndexScheduler {
                     Details::SettingsUpdate { settings: _ } => {
                         assert_eq!(kind.as_kind(), Kind::SettingsUpdate);
                     }
-                    Details::IndexInfo { primary_key: pk1 } => match &kind {
-                        KindWithContent::IndexCreation { index_uid, primary_key: pk2 }
-                        | KindWithContent::IndexUpdate { index_uid, primary_key: pk2 } => {
+                    Details::SettingsImport { added_entries, removed_entries } => {
+                        assert_eq!(kind.as_kind(), Kind::SettingsImport);
+                        match &kind {
+                            KindWithContent::SettingsImport {
+                                added_entries: added,
+                                removed_entries: removed,
+                                ..
+                            } => {
+                                assert_eq!(*added, added_entries);
+                                assert_eq!(*removed, removed_entries);
+                            }
+                            _ => panic!(),
+                        }
+                    }
+                    Details::IndexInfo { primary_key: pk1, max_size: size1 } => match &kind {
+                        KindWithContent::IndexCreation {
+                            index_uid,
+                            primary_key: pk2,
+                            max_size: size2,
+                        }
+                        | KindWithContent::IndexUpdate {
+                            index_uid,
+                            primary_key: pk2,
+                            max_size: size2,
+                        } => {
                             self.index_tasks
                                 .get(&rtxn, index_uid.as_str())
                                 .unwrap()
                                 .unwrap()
                                 .contains(uid);
                             assert_eq!(&pk1, pk2);
+                            assert_eq!(&size1, size2);
                         }
                         _ => panic!(),
                     },
@@ -457,6 +544,20 @@ impl IndexScheduler {
                             assert_ne!(status, Status::Succeeded);
                         }
                     }
+                    Details::DocumentEdition { edited_documents, original_filter } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentEdition);
+                        match &kind {
+                            KindWithContent::DocumentEdition { filter_expr, .. } => {
+                                assert_eq!(filter_expr, &original_filter);
+                            }
+                            _ => panic!(),
+                        }
+                        if edited_documents.is_some() {
+                            assert_ne!(status, Status::Enqueued);
+                        } else {
+                            assert_ne!(status, Status::Succeeded);
+                        }
+                    }
                     Details::TaskCancelation { matched_tasks, canceled_tasks, original_filter } => {
                         if let Some(canceled_tasks) = canceled_tasks {
                             assert_eq!(status, Status::Succeeded);
@@ -490,6 +591,14 @@ impl IndexScheduler {
                     Details::Dump { dump_uid: _ } => {
                         assert_eq!(kind.as_kind(), Kind::DumpCreation);
                     }
+                    Details::DumpImport { imported_indexes } => {
+                        assert_eq!(kind.as_kind(), Kind::DumpImport);
+                        if imported_indexes.is_some() {
+                            assert_ne!(status, Status::Enqueued);
+                        } else {
+                            assert_ne!(status, Status::Succeeded);
+                        }
+                    }
                 }
             }
 
@@ -3,13 +3,12 @@ pub mod error;
 mod store;
 
 use std::collections::{HashMap, HashSet};
-use std::ops::Deref;
 use std::path::Path;
 use std::sync::Arc;
 
 use error::{AuthControllerError, Result};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
 use meilisearch_types::keys::{Action, Key};
-use meilisearch_types::star_or::StarOr;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 pub use store::open_auth_store_env;
@@ -79,25 +78,19 @@ impl AuthController {
             .get_api_key(uid)?
             .ok_or_else(|| AuthControllerError::ApiKeyNotFound(uid.to_string()))?;
 
-        if !key.indexes.iter().any(|i| i == &StarOr::Star) {
-            filters.search_rules = match search_rules {
+        filters.search_rules = if key.indexes.iter().any(IndexUidPattern::matches_all) {
+            search_rules.unwrap_or_default()
+        } else {
+            match search_rules {
                 // Intersect search_rules with parent key authorized indexes.
-                Some(search_rules) => SearchRules::Map(
-                    key.indexes
-                        .into_iter()
-                        .filter_map(|index| {
-                            search_rules.get_index_search_rules(index.deref()).map(
-                                |index_search_rules| {
-                                    (String::from(index), Some(index_search_rules))
-                                },
-                            )
-                        })
-                        .collect(),
-                ),
-                None => SearchRules::Set(key.indexes.into_iter().map(String::from).collect()),
-            };
-        } else if let Some(search_rules) = search_rules {
-            filters.search_rules = search_rules;
+                Some(search_rules) => search_rules.intersection(&key.indexes),
+                None => SearchRules::Set(key.indexes.into_iter().collect()),
+            }
+        };
+
+        // The filter of the key is applied on top of the tenant token search rules.
+        if let Some(filter) = key.filter {
+            filters.search_rules = filters.search_rules.with_filter(filter);
         }
 
         filters.allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
@@ -141,9 +134,7 @@ impl AuthController {
             .get_expiration_date(uid, action, None)?
             .or(match index {
                 // else check if the key has access to the requested index.
-                Some(index) => {
-                    self.store.get_expiration_date(uid, action, Some(index.as_bytes()))?
-                }
+                Some(index) => self.store.get_expiration_date(uid, action, Some(index))?,
                 // or to any index if no index has been requested.
                 None => self.store.prefix_first_expiration_date(uid, action)?,
             }) {
@@ -179,56 +170,53 @@ impl Default for AuthFilter {
     }
 }
 
-/// Transparent wrapper around a list of allowed indexes with the search rules to apply for each.
+/// Transparent wrapper around a list of allowed index uid patterns with the search rules to apply for each.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum SearchRules {
-    Set(HashSet<String>),
-    Map(HashMap<String, Option<IndexSearchRules>>),
+    Set(HashSet<IndexUidPattern>),
+    Map(HashMap<IndexUidPattern, Option<IndexSearchRules>>),
 }
 
 impl Default for SearchRules {
     fn default() -> Self {
-        Self::Set(Some("*".to_string()).into_iter().collect())
+        Self::Set(Some(IndexUidPattern::all()).into_iter().collect())
     }
 }
 
 impl SearchRules {
     pub fn is_index_authorized(&self, index: &str) -> bool {
         match self {
-            Self::Set(set) => set.contains("*") || set.contains(index),
-            Self::Map(map) => map.contains_key("*") || map.contains_key(index),
+            Self::Set(set) => set.iter().any(|pattern| pattern.matches_str(index)),
+            Self::Map(map) => map.keys().any(|pattern| pattern.matches_str(index)),
         }
     }
 
+    /// Return the search rules of the most specific pattern matching the index.
     pub fn get_index_search_rules(&self, index: &str) -> Option<IndexSearchRules> {
         match self {
-            Self::Set(set) => {
-                if set.contains("*") || set.contains(index) {
-                    Some(IndexSearchRules::default())
-                } else {
-                    None
-                }
-            }
-            Self::Map(map) => {
-                map.get(index).or_else(|| map.get("*")).map(|isr| isr.clone().unwrap_or_default())
-            }
+            Self::Set(_) => self.is_index_authorized(index).then(IndexSearchRules::default),
+            Self::Map(map) => map
+                .iter()
+                .filter(|(pattern, _)| pattern.matches_str(index))
+                .max_by_key(|(pattern, _)| specificity(pattern))
+                .map(|(_, isr)| isr.clone().unwrap_or_default()),
         }
     }
 
-    /// Return the list of indexes such that `self.is_index_authorized(index) == true`,
-    /// or `None` if all indexes satisfy this condition.
-    pub fn authorized_indexes(&self) -> Option<Vec<String>> {
+    /// Return the list of patterns such that `self.is_index_authorized(index) == true`
+    /// for every index they match, or `None` if all indexes satisfy this condition.
+    pub fn authorized_indexes(&self) -> Option<Vec<IndexUidPattern>> {
         match self {
             SearchRules::Set(set) => {
-                if set.contains("*") {
+                if set.iter().any(IndexUidPattern::matches_all) {
                     None
                 } else {
                     Some(set.iter().cloned().collect())
                 }
             }
             SearchRules::Map(map) => {
-                if map.contains_key("*") {
+                if map.keys().any(IndexUidPattern::matches_all) {
                     None
                 } else {
                     Some(map.keys().cloned().collect())
@@ -236,10 +224,43 @@ impl SearchRules {
             }
         }
     }
+
+    /// Restrict the search rules to the indexes matched by at least one of the patterns.
+    fn intersection(self, patterns: &[IndexUidPattern]) -> Self {
+        let mut rules: Vec<_> = self.into_iter().collect();
+        // The rules of the most specific patterns are inserted last to take precedence.
+        rules.sort_by_key(|(pattern, _)| specificity(pattern));
+
+        let mut map = HashMap::new();
+        for (rules_pattern, isr) in rules {
+            for pattern in patterns {
+                if let Some(intersection) = rules_pattern.intersection(pattern) {
+                    map.insert(intersection, Some(isr.clone()));
+                }
+            }
+        }
+
+        SearchRules::Map(map)
+    }
+
+    /// Add the filter on top of the search rules of every index.
+    fn with_filter(self, filter: serde_json::Value) -> Self {
+        SearchRules::Map(
+            self.into_iter()
+                .map(|(pattern, isr)| (pattern, Some(isr.with_filter(filter.clone()))))
+                .collect(),
+        )
+    }
+}
+
+/// The exact patterns are more specific than the prefix ones, and the longest prefixes are
+/// more specific than the shortest ones.
+fn specificity(pattern: &IndexUidPattern) -> (bool, usize) {
+    (pattern.is_exact(), pattern.len())
 }
 
 impl IntoIterator for SearchRules {
-    type Item = (String, IndexSearchRules);
+    type Item = (IndexUidPattern, IndexSearchRules);
     type IntoIter = Box<dyn Iterator<Item = Self::Item>>;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -262,6 +283,28 @@ pub struct IndexSearchRules {
     pub filter: Option<serde_json::Value>,
 }
 
+impl IndexSearchRules {
+    /// Return the rules with the filter AND-ed to the existing one.
+    fn with_filter(self, filter: serde_json::Value) -> Self {
+        let filter = match self.filter {
+            None => filter,
+            Some(existing) => {
+                let existing = match existing {
+                    serde_json::Value::Array(existing) => existing,
+                    existing => vec![existing],
+                };
+                let filter = match filter {
+                    serde_json::Value::Array(filter) => filter,
+                    filter => vec![filter],
+                };
+                serde_json::Value::Array([existing, filter].concat())
+            }
+        };
+
+        Self { filter: Some(filter) }
+    }
+}
+
 fn generate_default_keys(store: &HeedAuthStore) -> Result<()> {
     store.put_api_key(Key::default_admin())?;
     store.put_api_key(Key::default_search())?;
@@ -3,17 +3,16 @@ use std::cmp::Reverse;
 use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 use std::fs::create_dir_all;
-use std::ops::Deref;
 use std::path::Path;
 use std::str;
 use std::sync::Arc;
 
 use hmac::{Hmac, Mac};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
 use meilisearch_types::keys::KeyId;
 use meilisearch_types::milli;
 use meilisearch_types::milli::heed::types::{ByteSlice, DecodeIgnore, SerdeJson};
 use meilisearch_types::milli::heed::{Database, Env, EnvOpenOptions, RwTxn};
-use meilisearch_types::star_or::StarOr;
 use sha2::Sha256;
 use time::OffsetDateTime;
 use uuid::fmt::Hyphenated;
@@ -108,7 +107,7 @@ impl HeedAuthStore {
                     actions.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
                 }
                 Action::DumpsAll => {
-                    actions.insert(Action::DumpsCreate);
+                    actions.extend([Action::DumpsCreate, Action::DumpsImport, Action::DumpsGet]);
                 }
                 Action::TasksAll => {
                     actions.extend([Action::TasksGet, Action::TasksDelete, Action::TasksCancel]);
@@ -125,17 +124,17 @@ impl HeedAuthStore {
             }
         }
 
-        let no_index_restriction = key.indexes.contains(&StarOr::Star);
+        let no_index_restriction = key.indexes.iter().any(IndexUidPattern::matches_all);
         for action in actions {
             if no_index_restriction {
                 // If there is no index restriction we put None.
                 db.put(&mut wtxn, &(&uid, &action, None), &key.expires_at)?;
             } else {
-                // else we create a key for each index.
+                // else we create a key for each index uid pattern.
                 for index in key.indexes.iter() {
                     db.put(
                         &mut wtxn,
-                        &(&uid, &action, Some(index.deref().as_bytes())),
+                        &(&uid, &action, Some(index.as_str().as_bytes())),
                         &key.expires_at,
                     )?;
                 }
@@ -210,11 +209,27 @@ impl HeedAuthStore {
         &self,
         uid: Uuid,
         action: Action,
-        index: Option<&[u8]>,
+        index: Option<&str>,
     ) -> Result<Option<Option<OffsetDateTime>>> {
         let rtxn = self.env.read_txn()?;
-        let tuple = (&uid, &action, index);
-        Ok(self.action_keyid_index_expiration.get(&rtxn, &tuple)?)
+        let tuple = (&uid, &action, index.map(str::as_bytes));
+        if let Some(expiration) = self.action_keyid_index_expiration.get(&rtxn, &tuple)? {
+            return Ok(Some(expiration));
+        }
+
+        // The index may also be matched by one of the index uid patterns of the key.
+        if let Some(index) = index {
+            let tuple = (&uid, &action, None);
+            for result in self.action_keyid_index_expiration.prefix_iter(&rtxn, &tuple)? {
+                let ((_, _, pattern), expiration) = result?;
+                let pattern = pattern.and_then(|pattern| str::from_utf8(pattern).ok());
+                if pattern.map_or(false, |p| IndexUidPattern::new_unchecked(p).matches_str(index)) {
+                    return Ok(Some(expiration));
+                }
+            }
+        }
+
+        Ok(None)
     }
 
     pub fn prefix_first_expiration_date(
@@ -1,10 +1,12 @@
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use actix_web::http::header::USER_AGENT;
+use actix_web::web::Data;
 use actix_web::HttpRequest;
 use byte_unit::Byte;
 use http::header::CONTENT_TYPE;
@@ -25,7 +27,11 @@ use uuid::Uuid;
 
 use super::{config_user_id_path, DocumentDeletionKind, MEILISEARCH_CONFIG_PATH};
 use crate::analytics::Analytics;
-use crate::option::{default_http_addr, IndexerOpts, MaxMemory, MaxThreads, SchedulerConfig};
+use crate::option::{
+    default_http_addr, IndexerOpts, LogFormat, MaxMemory, MaxThreads, RateLimitOpts,
+    SchedulerConfig, SearchOpts,
+};
+use crate::replica::ReadOnlyReplica;
 use crate::routes::indexes::documents::UpdateDocumentsQuery;
 use crate::routes::tasks::TasksFilterQueryRaw;
 use crate::routes::{create_all_stats, Stats};
@@ -82,7 +88,7 @@ pub struct SegmentAnalytics {
 }
 
 impl SegmentAnalytics {
-    pub async fn new(opt: &Opt, index_scheduler: Arc<IndexScheduler>) -> Arc<dyn Analytics> {
+    pub async fn new(opt: &Opt, replica: ReadOnlyReplica) -> Arc<dyn Analytics> {
         let instance_uid = super::find_user_id(&opt.db_path);
         let first_time_run = instance_uid.is_none();
         let instance_uid = instance_uid.unwrap_or_else(|| Uuid::new_v4());
@@ -136,7 +142,7 @@ impl SegmentAnalytics {
             get_tasks_aggregator: TasksAggregator::default(),
             health_aggregator: HealthAggregator::default(),
         });
-        tokio::spawn(segment.run(index_scheduler.clone()));
+        tokio::spawn(segment.run(replica));
 
         let this = Self { instance_uid, sender, user: user.clone() };
 
@@ -214,6 +220,7 @@ impl super::Analytics for SegmentAnalytics {
 #[derive(Debug, Clone, Serialize)]
 struct Infos {
     env: String,
+    experimental_enable_metrics: bool,
     db_path: bool,
     import_dump: bool,
     dumps_dir: bool,
@@ -223,6 +230,7 @@ struct Infos {
     schedule_snapshot: bool,
     snapshot_dir: bool,
     snapshot_interval_sec: u64,
+    max_incremental_snapshots: u32,
     ignore_missing_snapshot: bool,
     ignore_snapshot_if_db_exists: bool,
     http_addr: bool,
@@ -230,7 +238,11 @@ struct Infos {
     max_task_db_size: Byte,
     http_payload_size_limit: Byte,
     disable_auto_batching: bool,
+    max_batched_documents: Option<u64>,
+    max_batched_payload_size: Option<Byte>,
+    batch_max_wait_time_ms: Option<u64>,
     log_level: String,
+    log_format: LogFormat,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
     with_configuration_file: bool,
@@ -241,6 +253,15 @@ struct Infos {
     ssl_require_auth: bool,
     ssl_resumption: bool,
     ssl_tickets: bool,
+    ssl_client_keys_path: bool,
+    task_webhook_url: bool,
+    read_only_replica: bool,
+    read_only_replica_refresh_interval_sec: u64,
+    search_rate_limit: Option<NonZeroU32>,
+    indexing_rate_limit: Option<NonZeroU32>,
+    admin_rate_limit: Option<NonZeroU32>,
+    search_threads: Option<NonZeroUsize>,
+    search_time_budget_ms: Option<u64>,
 }
 
 impl From<Opt> for Infos {
@@ -263,25 +284,45 @@ impl From<Opt> for Infos {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_keys_path,
             import_snapshot,
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
             snapshot_dir,
             schedule_snapshot,
             snapshot_interval_sec,
+            max_incremental_snapshots,
             import_dump,
             ignore_missing_dump,
             ignore_dump_if_db_exists,
             dumps_dir,
+            read_only_replica,
+            read_only_replica_refresh_interval_sec,
             log_level,
+            log_format,
+            task_webhook_url,
+            task_webhook_secret: _,
             indexer_options,
             scheduler_options,
+            rate_limit_options,
+            search_options,
             config_file_path,
+            experimental_enable_metrics,
+            // Already merged into `experimental_enable_metrics`.
+            enable_metrics_route: _,
             #[cfg(all(not(debug_assertions), feature = "analytics"))]
                 no_analytics: _,
         } = options;
 
-        let SchedulerConfig { disable_auto_batching } = scheduler_options;
+        let SchedulerConfig {
+            disable_auto_batching,
+            max_batched_documents,
+            max_batched_payload_size,
+            batch_max_wait_time_ms,
+        } = scheduler_options;
+        let RateLimitOpts { search_rate_limit, indexing_rate_limit, admin_rate_limit } =
+            rate_limit_options;
+        let SearchOpts { search_threads, search_time_budget_ms } = search_options;
         let IndexerOpts {
             log_every_n: _,
             max_nb_chunks: _,
@@ -293,6 +334,7 @@ impl From<Opt> for Infos {
         // We consider information sensible if it contains a path, an address, or a key.
         Self {
             env,
+            experimental_enable_metrics,
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
             dumps_dir: dumps_dir != PathBuf::from("dumps/"),
@@ -302,6 +344,7 @@ impl From<Opt> for Infos {
             schedule_snapshot,
             snapshot_dir: snapshot_dir != PathBuf::from("snapshots/"),
             snapshot_interval_sec,
+            max_incremental_snapshots,
             ignore_missing_snapshot,
             ignore_snapshot_if_db_exists,
             http_addr: http_addr != default_http_addr(),
@@ -309,7 +352,11 @@ impl From<Opt> for Infos {
             max_task_db_size,
             http_payload_size_limit,
             disable_auto_batching,
+            max_batched_documents,
+            max_batched_payload_size,
+            batch_max_wait_time_ms,
             log_level,
+            log_format,
             max_indexing_memory,
             max_indexing_threads,
             with_configuration_file: config_file_path.is_some(),
@@ -320,6 +367,15 @@ impl From<Opt> for Infos {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_keys_path: ssl_client_keys_path.is_some(),
+            task_webhook_url: !task_webhook_url.is_empty(),
+            read_only_replica: read_only_replica.is_some(),
+            read_only_replica_refresh_interval_sec,
+            search_rate_limit,
+            indexing_rate_limit,
+            admin_rate_limit,
+            search_threads,
+            search_time_budget_ms,
         }
     }
 }
@@ -372,7 +428,7 @@ impl Segment {
         })
     }
 
-    async fn run(mut self, index_scheduler: Arc<IndexScheduler>) {
+    async fn run(mut self, replica: ReadOnlyReplica) {
         const INTERVAL: Duration = Duration::from_secs(60 * 60); // one hour
                                                                  // The first batch must be sent after one hour.
         let mut interval =
@@ -381,7 +437,8 @@ impl Segment {
         loop {
             select! {
                 _ = interval.tick() => {
-                    self.tick(index_scheduler.clone()).await;
+                    // The databases of a read-only replica change with its refreshes.
+                    self.tick(replica.index_scheduler()).await;
                 },
                 msg = self.inbox.recv() => {
                     match msg {
@@ -400,8 +457,8 @@ impl Segment {
         }
     }
 
-    async fn tick(&mut self, index_scheduler: Arc<IndexScheduler>) {
-        if let Ok(stats) = create_all_stats(index_scheduler.into(), &SearchRules::default()) {
+    async fn tick(&mut self, index_scheduler: Data<IndexScheduler>) {
+        if let Ok(stats) = create_all_stats(index_scheduler, &SearchRules::default(), false) {
             let _ = self
                 .batcher
                 .push(Identify {
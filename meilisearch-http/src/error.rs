@@ -1,3 +1,6 @@
+use std::collections::BTreeSet;
+use std::num::NonZeroU32;
+
 use actix_web as aweb;
 use aweb::error::{JsonPayloadError, QueryPayloadError};
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
@@ -6,6 +9,8 @@ use meilisearch_types::index_uid::IndexUidFormatError;
 use serde_json::Value;
 use tokio::task::JoinError;
 
+use crate::rate_limit::RouteGroup;
+
 #[derive(Debug, thiserror::Error)]
 pub enum MeilisearchHttpError {
     #[error("A Content-Type header is missing. Accepted values for the Content-Type header are: {}",
@@ -16,18 +21,65 @@ pub enum MeilisearchHttpError {
         .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
     )]
     InvalidContentType(String, Vec<String>),
+    #[error(
+        "The Content-Encoding `{0}` is not supported. Accepted values for the Content-Encoding header are: {}",
+        .1.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
+    )]
+    InvalidContentEncoding(String, Vec<String>),
+    #[error("The CSV delimiter `{0}` is invalid. It must be a single ASCII character that is neither a double quote nor a line break.")]
+    InvalidCsvDelimiter(String),
+    #[error("The Content-Type `{0}` does not support the use of a CSV delimiter. The CSV delimiter can only be used with the Content-Type `text/csv`.")]
+    CsvDelimiterWithWrongContentType(String),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
+    #[error("A document edition must have a filter selecting the documents to edit.")]
+    MissingDocumentEditionFilter,
+    #[error("A document edition must set, unset or increment at least one field.")]
+    EmptyDocumentEdition,
+    #[error(
+        "The field `{0}` can't be modified by several operations of the same document edition."
+    )]
+    DocumentEditionConflictingField(String),
     #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
     InvalidExpression(&'static [&'static str], Value),
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
     #[error("The provided payload reached the size limit.")]
     PayloadTooLarge,
+    #[error("The provided payload could not be received: {0}")]
+    ReceivePayload(aweb::error::PayloadError),
+    #[error("The provided payload could not be decompressed from `{0}`: {1}")]
+    InvalidPayloadEncoding(String, aweb::error::PayloadError),
+    #[error("The provided multipart payload could not be received: {0}")]
+    ReceiveMultipart(actix_multipart::MultipartError),
+    #[error("The dump must be sent in the `dump` field of the multipart form.")]
+    MissingDumpField,
+    #[error("The entry `{0}` of the imported file is invalid: {1}")]
+    InvalidSettingsImport(usize, String),
     #[error("Two indexes must be given for each swap. The list `{:?}` contains {} indexes.",
         .0, .0.len()
     )]
     SwapIndexPayloadWrongLength(Vec<String>),
+    #[error("Attribute `{0}` is not filterable. Available filterable attributes are: `{}`.",
+        .1.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+    )]
+    InvalidFacetSearchFacetName(String, BTreeSet<String>),
+    #[error("The document id `{0}` is invalid. It must be a string or an integer.")]
+    InvalidSimilarDocumentId(Value),
+    #[error("Too many requests. The {group} routes are limited to {limit} requests per second.")]
+    TooManyRequests { group: RouteGroup, limit: NonZeroU32 },
+    #[error("This instance is a read-only replica. The requests modifying the database must be sent to the primary instance.")]
+    ReadOnlyReplica,
+    #[error("The search could not start within its time budget of {0}ms. Too many searches are being processed.")]
+    SearchTimeout(u64),
+    #[error("The search exceeded its time budget of {0}ms before its hits could be found.")]
+    SearchTimeExceeded(u64),
+    #[error("An unexpected crash occurred when processing the search.")]
+    SearchPanicked,
+    #[error("The log level `{0}` is invalid. Available levels are `error`, `warn`, `info`, `debug` and `trace`.")]
+    InvalidLogStreamLevel(String),
+    #[error("The webhook url `{0}` is invalid. It must be an absolute `http` or `https` url.")]
+    InvalidWebhookUrl(String),
     #[error(transparent)]
     IndexUid(#[from] IndexUidFormatError),
     #[error(transparent)]
@@ -45,6 +97,8 @@ pub enum MeilisearchHttpError {
     #[error(transparent)]
     DocumentFormat(#[from] DocumentFormatError),
     #[error(transparent)]
+    Dump(#[from] dump::Error),
+    #[error(transparent)]
     Join(#[from] JoinError),
 }
 
@@ -54,10 +108,39 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::MissingContentType(_) => Code::MissingContentType,
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
+            MeilisearchHttpError::InvalidContentEncoding(_, _) => Code::UnsupportedMediaType,
+            MeilisearchHttpError::InvalidCsvDelimiter(_) => Code::InvalidDocumentCsvDelimiter,
+            MeilisearchHttpError::CsvDelimiterWithWrongContentType(_) => {
+                Code::InvalidDocumentCsvDelimiter
+            }
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
+            MeilisearchHttpError::MissingDocumentEditionFilter
+            | MeilisearchHttpError::EmptyDocumentEdition
+            | MeilisearchHttpError::DocumentEditionConflictingField(_) => {
+                Code::InvalidDocumentEdition
+            }
             MeilisearchHttpError::InvalidExpression(_, _) => Code::Filter,
             MeilisearchHttpError::PayloadTooLarge => Code::PayloadTooLarge,
+            MeilisearchHttpError::ReceivePayload(e) => match e {
+                aweb::error::PayloadError::Overflow => Code::PayloadTooLarge,
+                _ => Code::BadRequest,
+            },
+            MeilisearchHttpError::InvalidPayloadEncoding(_, _) => Code::MalformedPayload,
+            MeilisearchHttpError::ReceiveMultipart(_) => Code::MalformedPayload,
+            MeilisearchHttpError::MissingDumpField => Code::MissingPayload,
+            MeilisearchHttpError::InvalidSettingsImport(_, _) => Code::MalformedPayload,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::BadRequest,
+            MeilisearchHttpError::InvalidFacetSearchFacetName(_, _) => {
+                Code::InvalidFacetSearchFacetName
+            }
+            MeilisearchHttpError::InvalidSimilarDocumentId(_) => Code::InvalidDocumentId,
+            MeilisearchHttpError::TooManyRequests { .. } => Code::TooManyRequests,
+            MeilisearchHttpError::ReadOnlyReplica => Code::ReadOnlyReplica,
+            MeilisearchHttpError::InvalidLogStreamLevel(_) => Code::InvalidLogStreamLevel,
+            MeilisearchHttpError::InvalidWebhookUrl(_) => Code::InvalidWebhookUrl,
+            MeilisearchHttpError::SearchTimeout(_)
+            | MeilisearchHttpError::SearchTimeExceeded(_) => Code::SearchTimeout,
+            MeilisearchHttpError::SearchPanicked => Code::Internal,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
             MeilisearchHttpError::HeedError(_) => Code::Internal,
@@ -66,6 +149,7 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::Payload(e) => e.error_code(),
             MeilisearchHttpError::FileStore(_) => Code::Internal,
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
+            MeilisearchHttpError::Dump(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
         }
     }
@@ -77,16 +161,28 @@ impl From<MeilisearchHttpError> for aweb::Error {
     }
 }
 
+impl From<actix_multipart::MultipartError> for MeilisearchHttpError {
+    fn from(error: actix_multipart::MultipartError) -> Self {
+        match error {
+            actix_multipart::MultipartError::Payload(aweb::error::PayloadError::Overflow) => {
+                MeilisearchHttpError::PayloadTooLarge
+            }
+            error => MeilisearchHttpError::ReceiveMultipart(error),
+        }
+    }
+}
+
 impl From<aweb::error::PayloadError> for MeilisearchHttpError {
     fn from(error: aweb::error::PayloadError) -> Self {
-        MeilisearchHttpError::Payload(PayloadError::Payload(error))
+        match error {
+            aweb::error::PayloadError::Overflow => MeilisearchHttpError::PayloadTooLarge,
+            error => MeilisearchHttpError::ReceivePayload(error),
+        }
     }
 }
 
 #[derive(Debug, thiserror::Error)]
 pub enum PayloadError {
-    #[error(transparent)]
-    Payload(aweb::error::PayloadError),
     #[error(transparent)]
     Json(JsonPayloadError),
     #[error(transparent)]
@@ -100,17 +196,9 @@ pub enum PayloadError {
 impl ErrorCode for PayloadError {
     fn error_code(&self) -> Code {
         match self {
-            PayloadError::Payload(e) => match e {
-                aweb::error::PayloadError::Incomplete(_) => Code::Internal,
-                aweb::error::PayloadError::EncodingCorrupted => Code::Internal,
-                aweb::error::PayloadError::Overflow => Code::PayloadTooLarge,
-                aweb::error::PayloadError::UnknownLength => Code::Internal,
-                aweb::error::PayloadError::Http2Payload(_) => Code::Internal,
-                aweb::error::PayloadError::Io(_) => Code::Internal,
-                _ => todo!(),
-            },
             PayloadError::Json(err) => match err {
                 JsonPayloadError::Overflow { .. } => Code::PayloadTooLarge,
+                JsonPayloadError::OverflowKnownLength { .. } => Code::PayloadTooLarge,
                 JsonPayloadError::ContentType => Code::UnsupportedMediaType,
                 JsonPayloadError::Payload(aweb::error::PayloadError::Overflow) => {
                     Code::PayloadTooLarge
@@ -11,6 +11,8 @@ use futures::Future;
 use meilisearch_auth::{AuthController, AuthFilter};
 use meilisearch_types::error::{Code, ResponseError};
 
+use crate::tls::ClientIdentity;
+
 pub struct GuardedData<P, D> {
     data: D,
     filters: AuthFilter,
@@ -114,7 +116,16 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
                         Box::pin(err(AuthenticationError::MissingAuthorizationHeader.into()))
                     }
                 },
-                None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                None => match req.conn_data::<ClientIdentity>().and_then(|id| id.api_key(&auth)) {
+                    // The key mapped to the certificate of the client is used by default.
+                    Some(token) => Box::pin(Self::auth_bearer(
+                        auth,
+                        token,
+                        req.match_info().get("index_uid").map(String::from),
+                        req.app_data::<D>().cloned(),
+                    )),
+                    None => Box::pin(Self::auth_token(auth, req.app_data::<D>().cloned())),
+                },
             },
             None => Box::pin(err(AuthenticationError::IrretrievableState.into())),
         }
@@ -156,6 +167,25 @@ pub mod policies {
         Some(api_key_uid)
     }
 
+    /// Returns the uid of the API key the token is, or the tenant token is signed with, without
+    /// checking what the key is allowed to do.
+    pub fn authenticated_key_uid(auth: &AuthController, token: &str) -> Option<Uuid> {
+        if let Ok(Some(uid)) = auth.get_optional_uid_from_encoded_key(token.as_bytes()) {
+            return Some(uid);
+        }
+
+        // The uid of a tenant token is only trusted once its signature is checked.
+        let uid = extract_key_id(token)?;
+        let key = auth.generate_key(uid)?;
+        decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(key.as_bytes()),
+            &tenant_token_validation(),
+        )
+        .ok()?;
+        Some(uid)
+    }
+
     fn is_keys_action(action: u8) -> bool {
         use actions::*;
         matches!(action, KEYS_GET | KEYS_CREATE | KEYS_UPDATE | KEYS_DELETE)
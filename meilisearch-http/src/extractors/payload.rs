@@ -2,15 +2,23 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use actix_http::encoding::Decoder as Decompress;
+use actix_web::error::PayloadError;
+use actix_web::http::header::CONTENT_ENCODING;
 use actix_web::{dev, web, FromRequest, HttpRequest};
 use futures::future::{ready, Ready};
 use futures::Stream;
 
 use crate::error::MeilisearchHttpError;
 
+/// The values of the Content-Encoding header the payloads can be decompressed from.
+const SUPPORTED_ENCODINGS: &[&str] = &["gzip", "deflate", "br", "zstd", "identity"];
+
 pub struct Payload {
     payload: Decompress<dev::Payload>,
+    /// The limit applies to the decompressed payload, not to the bytes received.
     limit: usize,
+    /// The encoding of the payload, `None` if it is not compressed.
+    encoding: Option<String>,
 }
 
 pub struct PayloadConfig {
@@ -40,9 +48,25 @@ impl FromRequest for Payload {
             .app_data::<PayloadConfig>()
             .map(|c| c.limit)
             .unwrap_or(PayloadConfig::default().limit);
+
+        let encoding = match req.headers().get(CONTENT_ENCODING) {
+            Some(header) => {
+                let encoding = header.to_str().unwrap_or("unknown").trim().to_lowercase();
+                if !SUPPORTED_ENCODINGS.contains(&encoding.as_str()) {
+                    return ready(Err(MeilisearchHttpError::InvalidContentEncoding(
+                        encoding,
+                        SUPPORTED_ENCODINGS.iter().map(ToString::to_string).collect(),
+                    )));
+                }
+                Some(encoding).filter(|encoding| encoding != "identity")
+            }
+            None => None,
+        };
+
         ready(Ok(Payload {
             payload: Decompress::from_headers(payload.take(), req.headers()),
             limit,
+            encoding,
         }))
     }
 }
@@ -61,7 +85,17 @@ impl Stream for Payload {
                     }
                     None => Poll::Ready(Some(Err(MeilisearchHttpError::PayloadTooLarge))),
                 },
-                x => Poll::Ready(Some(x.map_err(MeilisearchHttpError::from))),
+                Err(error @ (PayloadError::Io(_) | PayloadError::EncodingCorrupted)) => {
+                    // With a compressed payload, this is how the decoder reports a corrupted stream.
+                    let error = match self.encoding.clone() {
+                        Some(encoding) => {
+                            MeilisearchHttpError::InvalidPayloadEncoding(encoding, error)
+                        }
+                        None => MeilisearchHttpError::from(error),
+                    };
+                    Poll::Ready(Some(Err(error)))
+                }
+                Err(error) => Poll::Ready(Some(Err(MeilisearchHttpError::from(error)))),
             },
             otherwise => otherwise.map(|o| o.map(|o| o.map_err(MeilisearchHttpError::from))),
         }
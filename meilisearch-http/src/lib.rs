@@ -4,17 +4,20 @@ pub mod error;
 pub mod analytics;
 #[macro_use]
 pub mod extractors;
+pub mod logs;
+pub mod metrics;
 pub mod option;
+pub mod rate_limit;
+pub mod replica;
+pub mod request_id;
+pub mod route_metrics;
 pub mod routes;
 pub mod search;
-
-#[cfg(feature = "metrics")]
-pub mod metrics;
-#[cfg(feature = "metrics")]
-pub mod route_metrics;
+pub mod search_pool;
+pub mod tls;
+pub mod webhook;
 
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
@@ -25,6 +28,7 @@ use actix_cors::Cors;
 use actix_http::body::MessageBody;
 use actix_web::dev::{ServiceFactory, ServiceResponse};
 use actix_web::error::JsonPayloadError;
+use actix_web::middleware::Condition;
 use actix_web::web::Data;
 use actix_web::{middleware, web, HttpRequest};
 use analytics::Analytics;
@@ -35,13 +39,15 @@ use http::header::CONTENT_TYPE;
 use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
 use log::error;
 use meilisearch_auth::AuthController;
-use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchReader};
-use meilisearch_types::milli::update::{IndexDocumentsConfig, IndexDocumentsMethod};
-use meilisearch_types::settings::apply_settings_to_builder;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::tasks::{KindWithContent, Status};
 use meilisearch_types::versioning::{check_version_file, create_version_file};
-use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
+use meilisearch_types::VERSION_FILE_NAME;
 pub use option::Opt;
+use rate_limit::RateLimiter;
+use replica::ReadOnlyReplica;
+use request_id::RequestId;
+use search_pool::SearchPool;
+use webhook::Webhooks;
 
 use crate::error::MeilisearchHttpError;
 
@@ -64,10 +70,12 @@ fn is_empty_db(db_path: impl AsRef<Path>) -> bool {
 }
 
 pub fn create_app(
-    index_scheduler: Data<IndexScheduler>,
-    auth_controller: AuthController,
     opt: Opt,
     analytics: Arc<dyn Analytics>,
+    rate_limiter: RateLimiter,
+    replica: ReadOnlyReplica,
+    search_pool: SearchPool,
+    webhooks: Webhooks,
     enable_dashboard: bool,
 ) -> actix_web::App<
     impl ServiceFactory<
@@ -79,22 +87,16 @@ pub fn create_app(
     >,
 > {
     let app = actix_web::App::new()
-        .configure(|s| {
-            configure_data(
-                s,
-                index_scheduler.clone(),
-                auth_controller.clone(),
-                &opt,
-                analytics.clone(),
-            )
-        })
+        .configure(|s| configure_data(s, &replica, &opt, analytics.clone()))
+        .app_data(Data::new(search_pool))
+        .app_data(Data::new(webhooks))
         .configure(routes::configure)
-        .configure(|s| dashboard(s, enable_dashboard));
-    #[cfg(feature = "metrics")]
-    let app = app.configure(|s| configure_metrics_route(s, opt.enable_metrics_route));
+        .configure(|s| dashboard(s, enable_dashboard))
+        .configure(|s| configure_metrics_route(s, opt.experimental_enable_metrics));
 
-    #[cfg(feature = "metrics")]
-    let app = app.wrap(Condition::new(opt.enable_metrics_route, route_metrics::RouteMetrics));
+    let app =
+        app.wrap(Condition::new(opt.experimental_enable_metrics, route_metrics::RouteMetrics));
+    let app = app.wrap(rate_limiter);
     app.wrap(
         Cors::default()
             .send_wildcard()
@@ -104,29 +106,70 @@ pub fn create_app(
             .max_age(86_400), // 24h
     )
     .wrap(middleware::Logger::default())
+    // Wraps the logger so that the line logged for each request belongs to the request span.
+    .wrap(RequestId)
     .wrap(middleware::Compress::default())
+    // The replica switches the databases the requests are served from, it must
+    // see them once their path is normalized and before any other middleware.
+    .wrap(replica)
     .wrap(middleware::NormalizePath::new(middleware::TrailingSlash::Trim))
 }
 
+/// Returns the options of an index scheduler storing its databases in `db_path`.
+pub(crate) fn index_scheduler_options(
+    opt: &Opt,
+    db_path: &Path,
+) -> anyhow::Result<IndexSchedulerOptions> {
+    Ok(IndexSchedulerOptions {
+        version_file_path: db_path.join(VERSION_FILE_NAME),
+        auth_path: db_path.join("auth"),
+        tasks_path: db_path.join("tasks"),
+        update_file_path: db_path.join("update_files"),
+        indexes_path: db_path.join("indexes"),
+        snapshots_path: opt.snapshot_dir.clone(),
+        max_incremental_snapshots: opt.max_incremental_snapshots,
+        dumps_path: opt.dumps_dir.clone(),
+        task_db_size: opt.max_task_db_size.get_bytes() as usize,
+        index_size: opt.max_index_size.get_bytes() as usize,
+        indexer_config: (&opt.indexer_options).try_into()?,
+        autobatching_enabled: !opt.scheduler_options.disable_auto_batching,
+        max_batched_documents: opt.scheduler_options.max_batched_documents,
+        max_batched_payload_size: opt
+            .scheduler_options
+            .max_batched_payload_size
+            .map(|size| size.get_bytes()),
+        batch_max_wait_time: opt
+            .scheduler_options
+            .batch_max_wait_time_ms
+            .map(Duration::from_millis),
+        task_notifiers: Vec::new(),
+        read_only: false,
+    })
+}
+
 // TODO: TAMO: Finish setting up things
-pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, AuthController)> {
+pub fn setup_meilisearch(
+    opt: &Opt,
+    webhooks: &Webhooks,
+) -> anyhow::Result<(Arc<IndexScheduler>, AuthController)> {
+    if let Some(ref source) = opt.read_only_replica {
+        let (index_scheduler, auth_controller) = replica::setup_replica(opt, source)?;
+        return Ok((Arc::new(index_scheduler), auth_controller));
+    }
+
     // we don't want to create anything in the data.ms yet, thus we
     // wrap our two builders in a closure that'll be executed later.
     let auth_controller_builder = || AuthController::new(&opt.db_path, &opt.master_key);
-    let index_scheduler_builder = || {
-        IndexScheduler::new(IndexSchedulerOptions {
-            version_file_path: opt.db_path.join(VERSION_FILE_NAME),
-            auth_path: opt.db_path.join("auth"),
-            tasks_path: opt.db_path.join("tasks"),
-            update_file_path: opt.db_path.join("update_files"),
-            indexes_path: opt.db_path.join("indexes"),
-            snapshots_path: opt.snapshot_dir.clone(),
-            dumps_path: opt.dumps_dir.clone(),
-            task_db_size: opt.max_task_db_size.get_bytes() as usize,
-            index_size: opt.max_index_size.get_bytes() as usize,
-            indexer_config: (&opt.indexer_options).try_into()?,
-            autobatching_enabled: !opt.scheduler_options.disable_auto_batching,
-        })
+    let mut task_notifiers = Vec::new();
+    task_notifiers.push(webhook::spawn_webhook_sender(webhooks.clone())?);
+    if opt.experimental_enable_metrics {
+        task_notifiers.push(metrics::spawn_task_observer()?);
+    }
+    let index_scheduler_builder = || -> anyhow::Result<_> {
+        Ok(IndexScheduler::new(IndexSchedulerOptions {
+            task_notifiers: task_notifiers.clone(),
+            ..index_scheduler_options(opt, &opt.db_path)?
+        })?)
     };
 
     enum OnFailure {
@@ -155,7 +198,7 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Auth
     let (index_scheduler, auth_controller) = if let Some(ref snapshot_path) = opt.import_snapshot {
         let snapshot_path_exists = snapshot_path.exists();
         if empty_db && snapshot_path_exists {
-            match compression::from_tar_gz(snapshot_path, &opt.db_path) {
+            match index_scheduler::import_snapshot(snapshot_path, &opt.db_path) {
                 Ok(()) => meilisearch_builder(OnFailure::RemoveDb)?,
                 Err(e) => {
                     std::fs::remove_dir_all(&opt.db_path)?;
@@ -228,7 +271,7 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Auth
     Ok((index_scheduler, auth_controller))
 }
 
-fn import_dump(
+pub(crate) fn import_dump(
     db_path: &Path,
     dump_path: &Path,
     index_scheduler: &mut IndexScheduler,
@@ -267,8 +310,6 @@ fn import_dump(
         keys.push(key);
     }
 
-    let indexer_config = index_scheduler.indexer_config();
-
     // /!\ The tasks must be imported AFTER importing the indexes or else the scheduler might
     // try to process tasks while we're trying to import the indexes.
 
@@ -277,60 +318,21 @@ fn import_dump(
         let mut index_reader = index_reader?;
         let metadata = index_reader.metadata();
         log::info!("Importing index `{}`.", metadata.uid);
-        let index = index_scheduler.create_raw_index(&metadata.uid)?;
-
-        let mut wtxn = index.write_txn()?;
-
-        let mut builder = milli::update::Settings::new(&mut wtxn, &index, indexer_config);
-        // 3.1 Import the primary key if there is one.
-        if let Some(ref primary_key) = metadata.primary_key {
-            builder.set_primary_key(primary_key.to_string());
-        }
-
-        // 3.2 Import the settings.
-        log::info!("Importing the settings.");
-        let settings = index_reader.settings()?;
-        apply_settings_to_builder(&settings, &mut builder);
-        builder.execute(|indexing_step| log::debug!("update: {:?}", indexing_step), || false)?;
-
-        // 3.3 Import the documents.
-        // 3.3.1 We need to recreate the grenad+obkv format accepted by the index.
-        log::info!("Importing the documents.");
-        let file = tempfile::tempfile()?;
-        let mut builder = DocumentsBatchBuilder::new(BufWriter::new(file));
-        for document in index_reader.documents()? {
-            builder.append_json_object(&document?)?;
-        }
-
-        // This flush the content of the batch builder.
-        let file = builder.into_inner()?.into_inner()?;
-
-        // 3.3.2 We feed it to the milli index.
-        let reader = BufReader::new(file);
-        let reader = DocumentsBatchReader::from_reader(reader)?;
-
-        let builder = milli::update::IndexDocuments::new(
-            &mut wtxn,
-            &index,
-            indexer_config,
-            IndexDocumentsConfig {
-                update_method: IndexDocumentsMethod::ReplaceDocuments,
-                ..Default::default()
-            },
-            |indexing_step| log::debug!("update: {:?}", indexing_step),
-            || false,
-        )?;
-
-        let (builder, user_result) = builder.add_documents(reader)?;
-        log::info!("{} documents found.", user_result?);
-        builder.execute()?;
-        wtxn.commit()?;
-        log::info!("All documents successfully imported.");
+        let index = index_scheduler.create_raw_index(&metadata.uid, metadata.max_size)?;
+        index_scheduler.import_dumped_index(&index, &mut index_reader)?;
     }
 
     // 4. Import the tasks.
     for ret in dump_reader.tasks()? {
         let (task, file) = ret?;
+        // The uploaded dumps are not part of the dumps, an enqueued import can't be processed.
+        if task.status == Status::Enqueued && matches!(task.kind, dump::KindDump::DumpImport) {
+            log::warn!(
+                "Dropping the enqueued dump import `{}`, its dump isn't part of the dump.",
+                task.uid
+            );
+            continue;
+        }
         index_scheduler.register_dumped_task(task, file)?;
     }
     Ok(())
@@ -338,15 +340,17 @@ fn import_dump(
 
 pub fn configure_data(
     config: &mut web::ServiceConfig,
-    index_scheduler: Data<IndexScheduler>,
-    auth: AuthController,
+    replica: &ReadOnlyReplica,
     opt: &Opt,
     analytics: Arc<dyn Analytics>,
 ) {
+    // The middleware of a read-only replica gives its latest databases to every request.
+    if !replica.is_enabled() {
+        config.app_data(replica.index_scheduler()).app_data(replica.auth_controller());
+    }
+
     let http_payload_size_limit = opt.http_payload_size_limit.get_bytes() as usize;
     config
-        .app_data(index_scheduler)
-        .app_data(auth)
         .app_data(web::Data::from(analytics))
         .app_data(
             web::JsonConfig::default()
@@ -407,7 +411,6 @@ pub fn dashboard(config: &mut web::ServiceConfig, _enable_frontend: bool) {
     config.service(web::resource("/").route(web::get().to(routes::running)));
 }
 
-#[cfg(feature = "metrics")]
 pub fn configure_metrics_route(config: &mut web::ServiceConfig, enable_metrics_route: bool) {
     if enable_metrics_route {
         config.service(
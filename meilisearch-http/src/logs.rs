@@ -0,0 +1,375 @@
+//! The logs are emitted with `tracing`, the records of the crates still using `log` being
+//! converted on the fly. They are printed on the standard error in the format chosen with
+//! `--log-format` and can be tailed over HTTP at any level with the `POST /logs/stream` route.
+
+use std::fmt::{self, Write as _};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+
+use actix_web::web::Bytes;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde_json::{json, Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::filter::{DynFilterFn, EnvFilter, LevelFilter};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, Layer};
+
+use crate::option::{LogFormat, Opt};
+
+/// The streams opened with the `POST /logs/stream` route.
+pub static LOG_STREAMS: Lazy<LogStreams> = Lazy::new(LogStreams::default);
+
+/// The number of lines buffered for a stream before the new ones are dropped.
+const STREAM_CAPACITY: usize = 1024;
+
+/// The levels a stream can be opened at, indexed by the value stored in `LogStreams::max_level`.
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::OFF,
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
+/// Installs the global subscriber printing the logs on the standard error and feeding the
+/// log streams.
+pub fn setup_logs(opt: &Opt) -> anyhow::Result<()> {
+    let mut filter = EnvFilter::try_new(&opt.log_level)?;
+    if opt.log_level == "info" {
+        // if we are in info we only allow the warn log_level for milli
+        filter = filter.add_directive("milli=warn".parse()?);
+    }
+
+    let stderr = match opt.log_format {
+        LogFormat::Human => fmt::layer().with_writer(std::io::stderr).boxed(),
+        LogFormat::Json => fmt::layer().json().with_writer(std::io::stderr).boxed(),
+    };
+
+    // The streams have their own filter so that they can be more verbose than the standard error.
+    let streams = LOG_STREAMS.clone();
+    let streams_filter = DynFilterFn::new(move |metadata, _| streams.enabled(metadata.level()));
+
+    tracing_subscriber::registry()
+        .with(stderr.with_filter(filter))
+        .with(LOG_STREAMS.clone().with_filter(streams_filter))
+        .try_init()?;
+
+    Ok(())
+}
+
+/// Parses the level of a log stream, the most verbose level it will receive the logs of.
+pub fn parse_stream_level(level: &str) -> Option<LevelFilter> {
+    match level.to_lowercase().as_str() {
+        "error" => Some(LevelFilter::ERROR),
+        "warn" => Some(LevelFilter::WARN),
+        "info" => Some(LevelFilter::INFO),
+        "debug" => Some(LevelFilter::DEBUG),
+        "trace" => Some(LevelFilter::TRACE),
+        _ => None,
+    }
+}
+
+/// A layer sending the logs to all the opened streams, one line per event.
+#[derive(Clone, Default)]
+pub struct LogStreams {
+    streams: Arc<Mutex<Vec<LogStream>>>,
+    /// The most verbose level of the opened streams, to skip the events nobody listens to
+    /// without locking the streams.
+    max_level: Arc<AtomicU8>,
+}
+
+struct LogStream {
+    level: LevelFilter,
+    format: LogFormat,
+    sender: Sender<Bytes>,
+}
+
+impl LogStreams {
+    /// Opens a new stream receiving the logs up to the given level. The stream is closed
+    /// as soon as the receiver is dropped.
+    pub fn subscribe(&self, level: LevelFilter, format: LogFormat) -> Receiver<Bytes> {
+        let (sender, receiver) = mpsc::channel(STREAM_CAPACITY);
+        let mut streams = self.streams.lock();
+        streams.push(LogStream { level, format, sender });
+        self.update_max_level(&streams);
+        drop(streams);
+        // The callsites disabled until now must be asked again if they are enabled.
+        tracing::callsite::rebuild_interest_cache();
+        receiver
+    }
+
+    fn enabled(&self, level: &Level) -> bool {
+        *level <= LEVELS[self.max_level.load(Ordering::Relaxed) as usize]
+    }
+
+    fn update_max_level(&self, streams: &[LogStream]) {
+        let max_level = streams.iter().map(|stream| stream.level).max().unwrap_or(LevelFilter::OFF);
+        let position = LEVELS.iter().position(|level| *level == max_level).unwrap_or_default();
+        self.max_level.store(position as u8, Ordering::Relaxed);
+    }
+}
+
+impl<S> Layer<S> for LogStreams
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut fields = Map::new();
+            attrs.record(&mut FieldsVisitor(&mut fields));
+            span.extensions_mut().insert(SpanFields(fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(SpanFields(fields)) = span.extensions_mut().get_mut::<SpanFields>() {
+                values.record(&mut FieldsVisitor(fields));
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut streams = self.streams.lock();
+        let line = LogLine::new(event, &ctx);
+        let (mut json, mut human) = (None, None);
+
+        let before = streams.len();
+        streams.retain(|stream| {
+            if line.level > stream.level {
+                return true;
+            }
+            let bytes = match stream.format {
+                LogFormat::Json => json.get_or_insert_with(|| line.to_json()),
+                LogFormat::Human => human.get_or_insert_with(|| line.to_human()),
+            };
+            // A stream that doesn't keep up loses lines instead of slowing down the engine.
+            !matches!(stream.sender.try_send(bytes.clone()), Err(TrySendError::Closed(_)))
+        });
+
+        if streams.len() != before {
+            self.update_max_level(&streams);
+        }
+    }
+}
+
+/// The fields of a span, stored in its extensions when it is created.
+struct SpanFields(Map<String, Value>);
+
+/// An event, with the spans it happened in, ready to be sent to the streams.
+struct LogLine {
+    timestamp: OffsetDateTime,
+    level: Level,
+    target: String,
+    fields: Map<String, Value>,
+    spans: Vec<(&'static str, Map<String, Value>)>,
+}
+
+impl LogLine {
+    fn new<S>(event: &Event<'_>, ctx: &Context<'_, S>) -> LogLine
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        // The records of the `log` crate carry their real metadata in their fields.
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut fields = Map::new();
+        event.record(&mut FieldsVisitor(&mut fields));
+
+        let spans = ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+            .map(|span| {
+                let fields = match span.extensions().get::<SpanFields>() {
+                    Some(SpanFields(fields)) => fields.clone(),
+                    None => Map::new(),
+                };
+                (span.name(), fields)
+            })
+            .collect();
+
+        LogLine {
+            timestamp: OffsetDateTime::now_utc(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            fields,
+            spans,
+        }
+    }
+
+    fn timestamp(&self) -> String {
+        self.timestamp.format(&Rfc3339).unwrap_or_default()
+    }
+
+    fn to_json(&self) -> Bytes {
+        let spans: Vec<_> = self
+            .spans
+            .iter()
+            .map(|(name, fields)| {
+                let mut span = fields.clone();
+                span.insert("name".to_string(), json!(name));
+                Value::Object(span)
+            })
+            .collect();
+        let line = json!({
+            "timestamp": self.timestamp(),
+            "level": self.level.as_str(),
+            "target": self.target,
+            "fields": self.fields,
+            "spans": spans,
+        });
+
+        let mut bytes = serde_json::to_vec(&line).unwrap_or_default();
+        bytes.push(b'\n');
+        Bytes::from(bytes)
+    }
+
+    fn to_human(&self) -> Bytes {
+        let mut line = format!("{} {:>5} ", self.timestamp(), self.level.as_str());
+        for (name, fields) in &self.spans {
+            line.push_str(name);
+            if !fields.is_empty() {
+                let _ = write!(line, "{{{}}}", DisplayFields(fields));
+            }
+            line.push(':');
+        }
+        if !self.spans.is_empty() {
+            line.push(' ');
+        }
+        let _ = write!(line, "{}: ", self.target);
+
+        let mut fields = self.fields.clone();
+        if let Some(message) = fields.remove("message") {
+            match message {
+                Value::String(message) => line.push_str(&message),
+                message => line.push_str(&message.to_string()),
+            }
+            if !fields.is_empty() {
+                line.push(' ');
+            }
+        }
+        let _ = writeln!(line, "{}", DisplayFields(&fields));
+
+        Bytes::from(line)
+    }
+}
+
+/// Displays fields as space-separated `key=value` pairs.
+struct DisplayFields<'a>(&'a Map<String, Value>);
+
+impl fmt::Display for DisplayFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_char(' ')?;
+            }
+            match value {
+                Value::String(value) => write!(f, "{key}={value}")?,
+                value => write!(f, "{key}={value}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Records the fields of an event or a span as JSON values.
+struct FieldsVisitor<'a>(&'a mut Map<String, Value>);
+
+impl FieldsVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        // The metadata of the `log` records is already part of the line.
+        if !field.name().starts_with("log.") {
+            self.0.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, json!(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, json!(format!("{:?}", value)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_receive_the_events_up_to_their_level() {
+        let streams = LogStreams::default();
+        let subscriber = tracing_subscriber::registry().with(streams.clone());
+
+        let mut info = streams.subscribe(LevelFilter::INFO, LogFormat::Json);
+        let mut debug = streams.subscribe(LevelFilter::DEBUG, LogFormat::Human);
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request", request_id = "abc");
+            let _entered = span.enter();
+            tracing::info!(task_uid = 12, "Task processed.");
+            tracing::debug!("Details.");
+        });
+
+        let line = info.try_recv().unwrap();
+        let line: Value = serde_json::from_slice(&line).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["fields"], json!({ "message": "Task processed.", "task_uid": 12 }));
+        assert_eq!(line["spans"], json!([{ "name": "request", "request_id": "abc" }]));
+        assert!(info.try_recv().is_err());
+
+        let line = debug.try_recv().unwrap();
+        let line = String::from_utf8(line.to_vec()).unwrap();
+        let expected = " INFO request{request_id=abc}: meilisearch_http::logs::tests: Task processed. task_uid=12\n";
+        assert!(line.ends_with(expected), "{line}");
+
+        let line = debug.try_recv().unwrap();
+        let line = String::from_utf8(line.to_vec()).unwrap();
+        let expected = "DEBUG request{request_id=abc}: meilisearch_http::logs::tests: Details.\n";
+        assert!(line.ends_with(expected), "{line}");
+    }
+
+    #[test]
+    fn closed_streams_are_removed() {
+        let streams = LogStreams::default();
+        let subscriber = tracing_subscriber::registry().with(streams.clone());
+
+        let receiver = streams.subscribe(LevelFilter::TRACE, LogFormat::Json);
+        assert!(streams.enabled(&Level::TRACE));
+        drop(receiver);
+
+        tracing::subscriber::with_default(subscriber, || tracing::error!("Nobody listens."));
+        assert!(streams.streams.lock().is_empty());
+        assert!(!streams.enabled(&Level::ERROR));
+    }
+}
@@ -3,11 +3,14 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use actix_web::http::KeepAlive;
-use actix_web::web::Data;
 use actix_web::HttpServer;
-use index_scheduler::IndexScheduler;
-use meilisearch_auth::AuthController;
 use meilisearch_http::analytics::Analytics;
+use meilisearch_http::logs::setup_logs;
+use meilisearch_http::rate_limit::RateLimiter;
+use meilisearch_http::replica::ReadOnlyReplica;
+use meilisearch_http::search_pool::SearchPool;
+use meilisearch_http::tls::{self, ClientKeys};
+use meilisearch_http::webhook::Webhooks;
 use meilisearch_http::{analytics, create_app, setup_meilisearch, Opt};
 
 #[global_allocator]
@@ -15,14 +18,13 @@ static ALLOC: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 /// does all the setup before meilisearch is launched
 fn setup(opt: &Opt) -> anyhow::Result<()> {
-    let mut log_builder = env_logger::Builder::new();
-    log_builder.parse_filters(&opt.log_level);
-    if opt.log_level == "info" {
-        // if we are in info we only allow the warn log_level for milli
-        log_builder.filter_module("milli", log::LevelFilter::Warn);
-    }
+    setup_logs(opt)?;
 
-    log_builder.init();
+    if opt.enable_metrics_route {
+        log::warn!(
+            "The `--enable-metrics-route` option and the `MEILI_ENABLE_METRICS_ROUTE` environment variable are deprecated, use `--experimental-enable-metrics` and `MEILI_EXPERIMENTAL_ENABLE_METRICS` instead."
+        );
+    }
 
     Ok(())
 }
@@ -45,11 +47,16 @@ async fn main() -> anyhow::Result<()> {
         _ => unreachable!(),
     }
 
-    let (index_scheduler, auth_controller) = setup_meilisearch(&opt)?;
+    // The webhooks can be changed at runtime, the handle is shared with the HTTP workers.
+    let webhooks = Webhooks::new(&opt)?;
+    let (index_scheduler, auth_controller) = setup_meilisearch(&opt, &webhooks)?;
+    // Every access to the databases goes through the replica, which swaps them when it is
+    // refreshed. It must only be started once.
+    let replica = ReadOnlyReplica::new(&opt, index_scheduler, auth_controller)?;
 
     #[cfg(all(not(debug_assertions), feature = "analytics"))]
     let analytics = if !opt.no_analytics {
-        analytics::SegmentAnalytics::new(&opt, index_scheduler.clone()).await
+        analytics::SegmentAnalytics::new(&opt, replica.clone()).await
     } else {
         analytics::MockAnalytics::new(&opt)
     };
@@ -58,27 +65,32 @@ async fn main() -> anyhow::Result<()> {
 
     print_launch_resume(&opt, analytics.clone(), config_read_from);
 
-    run_http(index_scheduler, auth_controller, opt, analytics).await?;
+    run_http(replica, opt, analytics, webhooks).await?;
 
     Ok(())
 }
 
 async fn run_http(
-    index_scheduler: Arc<IndexScheduler>,
-    auth_controller: AuthController,
+    replica: ReadOnlyReplica,
     opt: Opt,
     analytics: Arc<dyn Analytics>,
+    webhooks: Webhooks,
 ) -> anyhow::Result<()> {
     let enable_dashboard = &opt.env == "development";
     let opt_clone = opt.clone();
-    let index_scheduler = Data::from(index_scheduler);
+    // The rate limiter is shared so that the limits apply to all the workers together.
+    let rate_limiter = RateLimiter::new(&opt.rate_limit_options);
+    // The searches of all the workers are processed on the same pool.
+    let search_pool = SearchPool::new(&opt)?;
 
     let http_server = HttpServer::new(move || {
         create_app(
-            index_scheduler.clone(),
-            auth_controller.clone(),
             opt.clone(),
             analytics.clone(),
+            rate_limiter.clone(),
+            replica.clone(),
+            search_pool.clone(),
+            webhooks.clone(),
             enable_dashboard,
         )
     })
@@ -86,8 +98,16 @@ async fn run_http(
     .disable_signals()
     .keep_alive(KeepAlive::Os);
 
-    if let Some(config) = opt_clone.get_ssl_config()? {
-        http_server.bind_rustls(opt_clone.http_addr, config)?.run().await?;
+    if let Some((config, certificate)) = opt_clone.get_ssl_config()? {
+        let client_keys = ClientKeys::load(opt_clone.ssl_client_keys_path.clone())?;
+        tls::watch_reloads(certificate, client_keys.clone())?;
+        http_server
+            .on_connect(move |connection, data| {
+                tls::store_client_identity(connection, data, &client_keys)
+            })
+            .bind_rustls(opt_clone.http_addr, config)?
+            .run()
+            .await?;
     } else {
         http_server.bind(&opt_clone.http_addr)?.run().await?;
     }
@@ -1,7 +1,10 @@
+use crossbeam_channel::{unbounded, Sender};
 use lazy_static::lazy_static;
+use meilisearch_types::tasks::Task;
 use prometheus::{
-    opts, register_histogram_vec, register_int_counter_vec, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    exponential_buckets, opts, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, register_int_gauge, register_int_gauge_vec, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
 };
 
 const HTTP_RESPONSE_TIME_CUSTOM_BUCKETS: &[f64; 14] = &[
@@ -9,12 +12,24 @@ const HTTP_RESPONSE_TIME_CUSTOM_BUCKETS: &[f64; 14] = &[
     0.002, 0.003, 1.0,
 ];
 
+const SEARCH_TIME_CUSTOM_BUCKETS: &[f64; 12] =
+    &[0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+const TASK_TIME_CUSTOM_BUCKETS: &[f64; 12] =
+    &[0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 900.0, 3600.0];
+
 lazy_static! {
     pub static ref HTTP_REQUESTS_TOTAL: IntCounterVec = register_int_counter_vec!(
         opts!("http_requests_total", "HTTP requests total"),
         &["method", "path"]
     )
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_WEBHOOK_DROPPED_NOTIFICATIONS_TOTAL: IntCounter =
+        register_int_counter!(opts!(
+            "meilisearch_webhook_dropped_notifications_total",
+            "Meilisearch Webhook Notifications Dropped Because Of A Full Queue"
+        ))
+        .expect("Can't create a metric");
     pub static ref MEILISEARCH_DB_SIZE_BYTES: IntGauge =
         register_int_gauge!(opts!("meilisearch_db_size_bytes", "Meilisearch Db Size In Bytes"))
             .expect("Can't create a metric");
@@ -26,6 +41,34 @@ lazy_static! {
         &["index"]
     )
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEX_SIZE_BYTES: IntGaugeVec = register_int_gauge_vec!(
+        opts!("meilisearch_index_size_bytes", "Meilisearch Index Size In Bytes"),
+        &["index"]
+    )
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEX_MAP_SIZE_BYTES: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_index_map_size_bytes",
+        "Meilisearch Maximum Index Size In Bytes"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_TASK_QUEUE_LENGTH: IntGaugeVec = register_int_gauge_vec!(
+        opts!("meilisearch_task_queue_length", "Meilisearch Task Queue Length"),
+        &["status"]
+    )
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_TASK_DURATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "meilisearch_task_duration_seconds",
+        "Meilisearch Task Processing Times",
+        &["type", "status"],
+        TASK_TIME_CUSTOM_BUCKETS.to_vec()
+    )
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_SEARCH_DURATION_SECONDS: Histogram = register_histogram!(
+        "meilisearch_search_duration_seconds",
+        "Meilisearch Search Times",
+        SEARCH_TIME_CUSTOM_BUCKETS.to_vec()
+    )
+    .expect("Can't create a metric");
     pub static ref HTTP_RESPONSE_TIME_SECONDS: HistogramVec = register_histogram_vec!(
         "http_response_time_seconds",
         "HTTP response times",
@@ -33,4 +76,31 @@ lazy_static! {
         HTTP_RESPONSE_TIME_CUSTOM_BUCKETS.to_vec()
     )
     .expect("Can't create a metric");
+    pub static ref HTTP_PAYLOAD_SIZE_BYTES: HistogramVec = register_histogram_vec!(
+        "http_payload_size_bytes",
+        "HTTP request payload sizes",
+        &["method", "path"],
+        // from 1KiB to 1GiB
+        exponential_buckets(1024.0, 4.0, 11).expect("Can't create the buckets")
+    )
+    .expect("Can't create a metric");
+}
+
+/// Spawn the thread recording the processing time of the tasks and return
+/// the channel the index scheduler must send the finished tasks to.
+pub fn spawn_task_observer() -> anyhow::Result<Sender<Task>> {
+    let (sender, receiver) = unbounded::<Task>();
+    std::thread::Builder::new().name(String::from("task-metrics")).spawn(move || {
+        for task in receiver {
+            if let (Some(started_at), Some(finished_at)) = (task.started_at, task.finished_at) {
+                let kind = task.kind.as_kind().to_string();
+                let status = task.status.to_string();
+                MEILISEARCH_TASK_DURATION_SECONDS
+                    .with_label_values(&[&kind, &status])
+                    .observe((finished_at - started_at).as_seconds_f64());
+            }
+        }
+    })?;
+
+    Ok(sender)
 }
@@ -2,7 +2,7 @@ use std::convert::TryFrom;
 use std::env::VarError;
 use std::ffi::OsStr;
 use std::io::{BufReader, Read};
-use std::num::ParseIntError;
+use std::num::{NonZeroU32, NonZeroUsize, ParseIntError};
 use std::ops::Deref;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -20,6 +20,8 @@ use rustls_pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
 use serde::{Deserialize, Serialize};
 use sysinfo::{RefreshKind, System, SystemExt};
 
+use crate::tls::ReloadableCertificate;
+
 const POSSIBLE_ENV: [&str; 2] = ["development", "production"];
 
 const MEILI_DB_PATH: &str = "MEILI_DB_PATH";
@@ -38,18 +40,26 @@ const MEILI_SSL_OCSP_PATH: &str = "MEILI_SSL_OCSP_PATH";
 const MEILI_SSL_REQUIRE_AUTH: &str = "MEILI_SSL_REQUIRE_AUTH";
 const MEILI_SSL_RESUMPTION: &str = "MEILI_SSL_RESUMPTION";
 const MEILI_SSL_TICKETS: &str = "MEILI_SSL_TICKETS";
+const MEILI_SSL_CLIENT_KEYS_PATH: &str = "MEILI_SSL_CLIENT_KEYS_PATH";
 const MEILI_IMPORT_SNAPSHOT: &str = "MEILI_IMPORT_SNAPSHOT";
 const MEILI_IGNORE_MISSING_SNAPSHOT: &str = "MEILI_IGNORE_MISSING_SNAPSHOT";
 const MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS: &str = "MEILI_IGNORE_SNAPSHOT_IF_DB_EXISTS";
 const MEILI_SNAPSHOT_DIR: &str = "MEILI_SNAPSHOT_DIR";
 const MEILI_SCHEDULE_SNAPSHOT: &str = "MEILI_SCHEDULE_SNAPSHOT";
 const MEILI_SNAPSHOT_INTERVAL_SEC: &str = "MEILI_SNAPSHOT_INTERVAL_SEC";
+const MEILI_MAX_INCREMENTAL_SNAPSHOTS: &str = "MEILI_MAX_INCREMENTAL_SNAPSHOTS";
 const MEILI_IMPORT_DUMP: &str = "MEILI_IMPORT_DUMP";
 const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMPS_DIR: &str = "MEILI_DUMPS_DIR";
+const MEILI_READ_ONLY_REPLICA: &str = "MEILI_READ_ONLY_REPLICA";
+const MEILI_READ_ONLY_REPLICA_REFRESH_INTERVAL_SEC: &str =
+    "MEILI_READ_ONLY_REPLICA_REFRESH_INTERVAL_SEC";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
-#[cfg(feature = "metrics")]
+const MEILI_LOG_FORMAT: &str = "MEILI_LOG_FORMAT";
+const MEILI_TASK_WEBHOOK_URL: &str = "MEILI_TASK_WEBHOOK_URL";
+const MEILI_TASK_WEBHOOK_SECRET: &str = "MEILI_TASK_WEBHOOK_SECRET";
+const MEILI_EXPERIMENTAL_ENABLE_METRICS: &str = "MEILI_EXPERIMENTAL_ENABLE_METRICS";
 const MEILI_ENABLE_METRICS_ROUTE: &str = "MEILI_ENABLE_METRICS_ROUTE";
 
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
@@ -62,11 +72,20 @@ const DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT: &str = "100 MB";
 const DEFAULT_SNAPSHOT_DIR: &str = "snapshots/";
 const DEFAULT_SNAPSHOT_INTERVAL_SEC: u64 = 86400;
 const DEFAULT_DUMPS_DIR: &str = "dumps/";
+const DEFAULT_READ_ONLY_REPLICA_REFRESH_INTERVAL_SEC: u64 = 300;
 const DEFAULT_LOG_LEVEL: &str = "INFO";
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
 const DISABLE_AUTO_BATCHING: &str = "DISABLE_AUTO_BATCHING";
+const MEILI_MAX_BATCHED_DOCUMENTS: &str = "MEILI_MAX_BATCHED_DOCUMENTS";
+const MEILI_MAX_BATCHED_PAYLOAD_SIZE: &str = "MEILI_MAX_BATCHED_PAYLOAD_SIZE";
+const MEILI_BATCH_MAX_WAIT_TIME_MS: &str = "MEILI_BATCH_MAX_WAIT_TIME_MS";
+const MEILI_SEARCH_RATE_LIMIT: &str = "MEILI_SEARCH_RATE_LIMIT";
+const MEILI_INDEXING_RATE_LIMIT: &str = "MEILI_INDEXING_RATE_LIMIT";
+const MEILI_ADMIN_RATE_LIMIT: &str = "MEILI_ADMIN_RATE_LIMIT";
+const MEILI_SEARCH_THREADS: &str = "MEILI_SEARCH_THREADS";
+const MEILI_SEARCH_TIME_BUDGET_MS: &str = "MEILI_SEARCH_TIME_BUDGET_MS";
 const DEFAULT_LOG_EVERY_N: usize = 100000;
 
 #[derive(Debug, Clone, Parser, Deserialize)]
@@ -119,7 +138,9 @@ pub struct Opt {
     #[serde(default = "default_http_payload_size_limit")]
     pub http_payload_size_limit: Byte,
 
-    /// Sets the server's SSL certificates.
+    /// Sets the server's SSL certificates. The certificates and the key are reloaded without
+    /// dropping the open connections when their files are modified or when Meilisearch receives
+    /// a `SIGHUP`.
     #[clap(long, env = MEILI_SSL_CERT_PATH, value_parser)]
     pub ssl_cert_path: Option<PathBuf>,
 
@@ -152,6 +173,13 @@ pub struct Opt {
     #[clap(long, env = MEILI_SSL_TICKETS)]
     pub ssl_tickets: bool,
 
+    /// Sets the path of a JSON file mapping the common names of the client certificates to the
+    /// uids of API keys. The clients presenting one of these certificates are authenticated with
+    /// the mapped key when their requests have no `Authorization` header. Requires the client
+    /// authentication to be enabled with `--ssl-auth-path`.
+    #[clap(long, env = MEILI_SSL_CLIENT_KEYS_PATH, value_parser, requires = "ssl_auth_path")]
+    pub ssl_client_keys_path: Option<PathBuf>,
+
     /// Launches Meilisearch after importing a previously-generated snapshot at the given filepath.
     #[clap(long, env = MEILI_IMPORT_SNAPSHOT)]
     pub import_snapshot: Option<PathBuf>,
@@ -196,6 +224,15 @@ pub struct Opt {
     #[serde(default = "default_snapshot_interval_sec")]
     pub snapshot_interval_sec: u64,
 
+    /// Sets the number of incremental snapshots created after a full snapshot. An incremental
+    /// snapshot only contains the indexes modified since the previous snapshot, each of them copied
+    /// in full. When importing a full snapshot with `--import-snapshot`, the incremental snapshots
+    /// that were made after it are replayed too. Set to 0, the default, to only create full
+    /// snapshots.
+    #[clap(long, env = MEILI_MAX_INCREMENTAL_SNAPSHOTS, default_value_t)]
+    #[serde(default)]
+    pub max_incremental_snapshots: u32,
+
     /// Imports the dump file located at the specified path. Path must point to a `.dump` file.
     /// If a database already exists, Meilisearch will throw an error and abort launch.
     #[clap(long, env = MEILI_IMPORT_DUMP, conflicts_with = "import_snapshot")]
@@ -223,6 +260,20 @@ pub struct Opt {
     #[serde(default = "default_dumps_dir")]
     pub dumps_dir: PathBuf,
 
+    /// Launches Meilisearch as a read-only replica of a primary instance. Value must be the URL
+    /// or the filepath of the latest snapshot, or `.dump` file, of the primary instance.
+    ///
+    /// A read-only replica rejects every request that would modify its database, periodically
+    /// pulls the snapshot again and atomically switches to its content. The database path
+    /// must either be empty or have only been used by a read-only replica.
+    #[clap(long, env = MEILI_READ_ONLY_REPLICA, conflicts_with_all = ["import_snapshot", "import_dump"])]
+    pub read_only_replica: Option<String>,
+
+    /// Defines the interval between each refresh of a read-only replica. Value must be given in seconds.
+    #[clap(long, env = MEILI_READ_ONLY_REPLICA_REFRESH_INTERVAL_SEC, default_value_t = default_read_only_replica_refresh_interval_sec())]
+    #[serde(default = "default_read_only_replica_refresh_interval_sec")]
+    pub read_only_replica_refresh_interval_sec: u64,
+
     /// Defines how much detail should be present in Meilisearch's logs.
     ///
     /// Meilisearch currently supports five log levels, listed in order of increasing verbosity: ERROR, WARN, INFO, DEBUG, TRACE.
@@ -230,9 +281,34 @@ pub struct Opt {
     #[serde(default = "default_log_level")]
     pub log_level: String,
 
-    /// Enables Prometheus metrics and /metrics route.
-    #[cfg(feature = "metrics")]
-    #[clap(long, env = MEILI_ENABLE_METRICS_ROUTE)]
+    /// Defines the format of Meilisearch's logs, either `human` or `json`.
+    ///
+    /// The `json` format prints one JSON object per line, with the spans the event belongs
+    /// to, such as the request id of an HTTP request or the uid of a processed task.
+    #[clap(long, env = MEILI_LOG_FORMAT, default_value_t)]
+    #[serde(default)]
+    pub log_format: LogFormat,
+
+    /// Registers a URL that will receive a `POST` request, containing the task as JSON, every time
+    /// a task succeeds or fails. Can be provided multiple times, or as a comma-separated list.
+    #[clap(long, env = MEILI_TASK_WEBHOOK_URL, value_delimiter = ',')]
+    #[serde(default)]
+    pub task_webhook_url: Vec<String>,
+
+    /// Sets the secret used to sign the requests sent to the task webhooks. The signature is sent in
+    /// the `X-Meilisearch-Signature` header as `sha256=<signature>`, where `<signature>` is the
+    /// hex-encoded HMAC-SHA256 of the body.
+    #[clap(long, env = MEILI_TASK_WEBHOOK_SECRET)]
+    pub task_webhook_secret: Option<String>,
+
+    /// Experimental: enables the instrumentation of the HTTP routes, the searches and the task
+    /// scheduler, and exposes the collected metrics in the Prometheus format on the /metrics route.
+    #[clap(long, env = MEILI_EXPERIMENTAL_ENABLE_METRICS)]
+    #[serde(default)]
+    pub experimental_enable_metrics: bool,
+
+    /// Deprecated: use `--experimental-enable-metrics` instead, which it enables.
+    #[clap(long, env = MEILI_ENABLE_METRICS_ROUTE, hide = true)]
     #[serde(default)]
     pub enable_metrics_route: bool,
 
@@ -244,6 +320,14 @@ pub struct Opt {
     #[clap(flatten)]
     pub scheduler_options: SchedulerConfig,
 
+    #[serde(flatten)]
+    #[clap(flatten)]
+    pub rate_limit_options: RateLimitOpts,
+
+    #[serde(flatten)]
+    #[clap(flatten)]
+    pub search_options: SearchOpts,
+
     /// Set the path to a configuration file that should be used to setup the engine.
     /// Format must be TOML.
     #[clap(long)]
@@ -297,6 +381,11 @@ impl Opt {
             }
         }
 
+        // The deprecated option is only kept as an alias, a warning is logged at startup.
+        if opts.enable_metrics_route {
+            opts.experimental_enable_metrics = true;
+        }
+
         Ok((opts, config_read_from))
     }
 
@@ -317,13 +406,22 @@ impl Opt {
             ssl_require_auth,
             ssl_resumption,
             ssl_tickets,
+            ssl_client_keys_path,
             snapshot_dir,
             schedule_snapshot,
             snapshot_interval_sec,
+            max_incremental_snapshots,
             dumps_dir,
+            read_only_replica,
+            read_only_replica_refresh_interval_sec,
             log_level,
+            log_format,
+            task_webhook_url,
+            task_webhook_secret,
             indexer_options,
             scheduler_options,
+            rate_limit_options,
+            search_options,
             import_snapshot: _,
             ignore_missing_snapshot: _,
             ignore_snapshot_if_db_exists: _,
@@ -333,7 +431,7 @@ impl Opt {
             config_file_path: _,
             #[cfg(all(not(debug_assertions), feature = "analytics"))]
             no_analytics,
-            #[cfg(feature = "metrics")]
+            experimental_enable_metrics,
             enable_metrics_route,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
@@ -367,26 +465,51 @@ impl Opt {
         export_to_env_if_not_present(MEILI_SSL_REQUIRE_AUTH, ssl_require_auth.to_string());
         export_to_env_if_not_present(MEILI_SSL_RESUMPTION, ssl_resumption.to_string());
         export_to_env_if_not_present(MEILI_SSL_TICKETS, ssl_tickets.to_string());
+        if let Some(ssl_client_keys_path) = ssl_client_keys_path {
+            export_to_env_if_not_present(MEILI_SSL_CLIENT_KEYS_PATH, ssl_client_keys_path);
+        }
         export_to_env_if_not_present(MEILI_SNAPSHOT_DIR, snapshot_dir);
         export_to_env_if_not_present(MEILI_SCHEDULE_SNAPSHOT, schedule_snapshot.to_string());
         export_to_env_if_not_present(
             MEILI_SNAPSHOT_INTERVAL_SEC,
             snapshot_interval_sec.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_MAX_INCREMENTAL_SNAPSHOTS,
+            max_incremental_snapshots.to_string(),
+        );
         export_to_env_if_not_present(MEILI_DUMPS_DIR, dumps_dir);
+        if let Some(read_only_replica) = read_only_replica {
+            export_to_env_if_not_present(MEILI_READ_ONLY_REPLICA, read_only_replica);
+        }
+        export_to_env_if_not_present(
+            MEILI_READ_ONLY_REPLICA_REFRESH_INTERVAL_SEC,
+            read_only_replica_refresh_interval_sec.to_string(),
+        );
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level);
-        #[cfg(feature = "metrics")]
-        {
-            export_to_env_if_not_present(
-                MEILI_ENABLE_METRICS_ROUTE,
-                enable_metrics_route.to_string(),
-            );
+        export_to_env_if_not_present(MEILI_LOG_FORMAT, log_format.to_string());
+        if !task_webhook_url.is_empty() {
+            export_to_env_if_not_present(MEILI_TASK_WEBHOOK_URL, task_webhook_url.join(","));
         }
+        if let Some(task_webhook_secret) = task_webhook_secret {
+            export_to_env_if_not_present(MEILI_TASK_WEBHOOK_SECRET, task_webhook_secret);
+        }
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_ENABLE_METRICS,
+            experimental_enable_metrics.to_string(),
+        );
+        export_to_env_if_not_present(MEILI_ENABLE_METRICS_ROUTE, enable_metrics_route.to_string());
         indexer_options.export_to_env();
         scheduler_options.export_to_env();
+        rate_limit_options.export_to_env();
+        search_options.export_to_env();
     }
 
-    pub fn get_ssl_config(&self) -> anyhow::Result<Option<rustls::ServerConfig>> {
+    /// Returns the TLS configuration of the server along with its certificate, which can be
+    /// reloaded while the server is running.
+    pub fn get_ssl_config(
+        &self,
+    ) -> anyhow::Result<Option<(rustls::ServerConfig, Arc<ReloadableCertificate>)>> {
         if let (Some(cert_path), Some(key_path)) = (&self.ssl_cert_path, &self.ssl_key_path) {
             let config = rustls::ServerConfig::builder().with_safe_defaults();
 
@@ -409,12 +532,12 @@ impl Opt {
                 None => config.with_no_client_auth(),
             };
 
-            let certs = load_certs(cert_path.to_path_buf())?;
-            let privkey = load_private_key(key_path.to_path_buf())?;
-            let ocsp = load_ocsp(&self.ssl_ocsp_path)?;
-            let mut config = config
-                .with_single_cert_with_ocsp_and_sct(certs, privkey, ocsp, vec![])
-                .map_err(|_| anyhow::anyhow!("bad certificates/private key"))?;
+            let certificate = Arc::new(ReloadableCertificate::load(
+                cert_path.to_path_buf(),
+                key_path.to_path_buf(),
+                self.ssl_ocsp_path.clone(),
+            )?);
+            let mut config = config.with_cert_resolver(certificate.clone());
 
             config.key_log = Arc::new(rustls::KeyLogFile::new());
 
@@ -426,7 +549,7 @@ impl Opt {
                 config.ticketer = rustls::Ticketer::new().unwrap();
             }
 
-            Ok(Some(config))
+            Ok(Some((config, certificate)))
         } else {
             Ok(None)
         }
@@ -488,12 +611,119 @@ pub struct SchedulerConfig {
     #[clap(long, env = DISABLE_AUTO_BATCHING)]
     #[serde(default)]
     pub disable_auto_batching: bool,
+
+    /// Sets the maximum number of documents added or updated in a single batch. A task is always
+    /// processed, even if it goes over the limit by itself. Unlimited by default.
+    #[clap(long, env = MEILI_MAX_BATCHED_DOCUMENTS)]
+    pub max_batched_documents: Option<u64>,
+
+    /// Sets the maximum total size of the documents payloads processed in a single batch. Value must
+    /// be given in bytes or explicitly stating a base unit (for instance: 104857600, '100Mb').
+    /// A task is always processed, even if it goes over the limit by itself. Unlimited by default.
+    #[clap(long, env = MEILI_MAX_BATCHED_PAYLOAD_SIZE)]
+    pub max_batched_payload_size: Option<Byte>,
+
+    /// Sets how long, in milliseconds, the oldest enqueued task may wait for other tasks to be
+    /// batched with it before being processed. The tasks are processed right away by default.
+    #[clap(long, env = MEILI_BATCH_MAX_WAIT_TIME_MS)]
+    pub batch_max_wait_time_ms: Option<u64>,
 }
 
 impl SchedulerConfig {
     pub fn export_to_env(self) {
-        let SchedulerConfig { disable_auto_batching } = self;
+        let SchedulerConfig {
+            disable_auto_batching,
+            max_batched_documents,
+            max_batched_payload_size,
+            batch_max_wait_time_ms,
+        } = self;
         export_to_env_if_not_present(DISABLE_AUTO_BATCHING, disable_auto_batching.to_string());
+        if let Some(max_batched_documents) = max_batched_documents {
+            export_to_env_if_not_present(
+                MEILI_MAX_BATCHED_DOCUMENTS,
+                max_batched_documents.to_string(),
+            );
+        }
+        if let Some(max_batched_payload_size) = max_batched_payload_size {
+            export_to_env_if_not_present(
+                MEILI_MAX_BATCHED_PAYLOAD_SIZE,
+                max_batched_payload_size.to_string(),
+            );
+        }
+        if let Some(batch_max_wait_time_ms) = batch_max_wait_time_ms {
+            export_to_env_if_not_present(
+                MEILI_BATCH_MAX_WAIT_TIME_MS,
+                batch_max_wait_time_ms.to_string(),
+            );
+        }
+    }
+}
+
+#[derive(Debug, Clone, Parser, Default, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct RateLimitOpts {
+    /// Sets the maximum number of search requests per second accepted from each API key, or from
+    /// each IP address for the requests made without an API key. Unlimited by default.
+    #[clap(long, env = MEILI_SEARCH_RATE_LIMIT)]
+    pub search_rate_limit: Option<NonZeroU32>,
+
+    /// Sets the maximum number of requests per second accepted on the indexes, documents and
+    /// settings routes from each API key, or from each IP address. Unlimited by default.
+    #[clap(long, env = MEILI_INDEXING_RATE_LIMIT)]
+    pub indexing_rate_limit: Option<NonZeroU32>,
+
+    /// Sets the maximum number of requests per second accepted on the keys, tasks, dumps, stats
+    /// and version routes from each API key, or from each IP address. Unlimited by default.
+    #[clap(long, env = MEILI_ADMIN_RATE_LIMIT)]
+    pub admin_rate_limit: Option<NonZeroU32>,
+}
+
+impl RateLimitOpts {
+    pub fn export_to_env(self) {
+        let RateLimitOpts { search_rate_limit, indexing_rate_limit, admin_rate_limit } = self;
+        if let Some(search_rate_limit) = search_rate_limit {
+            export_to_env_if_not_present(MEILI_SEARCH_RATE_LIMIT, search_rate_limit.to_string());
+        }
+        if let Some(indexing_rate_limit) = indexing_rate_limit {
+            export_to_env_if_not_present(
+                MEILI_INDEXING_RATE_LIMIT,
+                indexing_rate_limit.to_string(),
+            );
+        }
+        if let Some(admin_rate_limit) = admin_rate_limit {
+            export_to_env_if_not_present(MEILI_ADMIN_RATE_LIMIT, admin_rate_limit.to_string());
+        }
+    }
+}
+
+#[derive(Debug, Clone, Parser, Default, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct SearchOpts {
+    /// Sets the number of threads the searches are processed on, the other searches waiting for
+    /// one of them to be available. Defaults to the number of available CPUs.
+    #[clap(long, env = MEILI_SEARCH_THREADS)]
+    pub search_threads: Option<NonZeroUsize>,
+
+    /// Sets the time budget of a search request, in milliseconds. A search that can't start, or
+    /// find its hits, within this budget fails, and one that exceeds it afterwards returns its
+    /// hits without formatting them nor computing the facet distribution, flagged with
+    /// `"degraded": true`. Unlimited by default.
+    #[clap(long, env = MEILI_SEARCH_TIME_BUDGET_MS)]
+    pub search_time_budget_ms: Option<u64>,
+}
+
+impl SearchOpts {
+    pub fn export_to_env(self) {
+        let SearchOpts { search_threads, search_time_budget_ms } = self;
+        if let Some(search_threads) = search_threads {
+            export_to_env_if_not_present(MEILI_SEARCH_THREADS, search_threads.to_string());
+        }
+        if let Some(search_time_budget_ms) = search_time_budget_ms {
+            export_to_env_if_not_present(
+                MEILI_SEARCH_TIME_BUDGET_MS,
+                search_time_budget_ms.to_string(),
+            );
+        }
     }
 }
 
@@ -581,6 +811,43 @@ fn total_memory_bytes() -> Option<u64> {
     }
 }
 
+/// The format in which the logs are printed on the standard error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Human,
+    Json,
+}
+
+impl Default for LogFormat {
+    fn default() -> Self {
+        LogFormat::Human
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "human" => Ok(LogFormat::Human),
+            "json" => Ok(LogFormat::Json),
+            _ => anyhow::bail!(
+                "Unsupported log format `{s}`. Available formats are `human` and `json`."
+            ),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LogFormat::Human => f.write_str("human"),
+            LogFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct MaxThreads(usize);
 
@@ -612,7 +879,7 @@ impl Deref for MaxThreads {
     }
 }
 
-fn load_certs(filename: PathBuf) -> anyhow::Result<Vec<rustls::Certificate>> {
+pub(crate) fn load_certs(filename: PathBuf) -> anyhow::Result<Vec<rustls::Certificate>> {
     let certfile =
         fs::File::open(filename).map_err(|_| anyhow::anyhow!("cannot open certificate file"))?;
     let mut reader = BufReader::new(certfile);
@@ -621,7 +888,7 @@ fn load_certs(filename: PathBuf) -> anyhow::Result<Vec<rustls::Certificate>> {
         .map_err(|_| anyhow::anyhow!("cannot read certificate file"))
 }
 
-fn load_private_key(filename: PathBuf) -> anyhow::Result<rustls::PrivateKey> {
+pub(crate) fn load_private_key(filename: PathBuf) -> anyhow::Result<rustls::PrivateKey> {
     let rsa_keys = {
         let keyfile = fs::File::open(filename.clone())
             .map_err(|_| anyhow::anyhow!("cannot open private key file"))?;
@@ -650,7 +917,7 @@ fn load_private_key(filename: PathBuf) -> anyhow::Result<rustls::PrivateKey> {
     }
 }
 
-fn load_ocsp(filename: &Option<PathBuf>) -> anyhow::Result<Vec<u8>> {
+pub(crate) fn load_ocsp(filename: &Option<PathBuf>) -> anyhow::Result<Vec<u8>> {
     let mut ret = Vec::new();
 
     if let Some(ref name) = filename {
@@ -712,6 +979,10 @@ fn default_dumps_dir() -> PathBuf {
     PathBuf::from(DEFAULT_DUMPS_DIR)
 }
 
+fn default_read_only_replica_refresh_interval_sec() -> u64 {
+    DEFAULT_READ_ONLY_REPLICA_REFRESH_INTERVAL_SEC
+}
+
 fn default_log_level() -> String {
     DEFAULT_LOG_LEVEL.to_string()
 }
@@ -730,6 +1001,32 @@ mod test {
         assert!(Opt::try_parse_from(Some("")).is_ok());
     }
 
+    #[test]
+    fn test_ssl_client_keys_path_requires_ssl_auth_path() {
+        assert!(
+            Opt::try_parse_from(["meilisearch", "--ssl-client-keys-path", "keys.json"]).is_err()
+        );
+        assert!(Opt::try_parse_from([
+            "meilisearch",
+            "--ssl-client-keys-path",
+            "keys.json",
+            "--ssl-auth-path",
+            "ca.pem",
+        ])
+        .is_ok());
+    }
+
+    #[test]
+    fn test_deprecated_enable_metrics_route() {
+        let opt = Opt::try_parse_from(["meilisearch", "--enable-metrics-route"]).unwrap();
+        assert!(opt.enable_metrics_route);
+
+        temp_env::with_vars(vec![(MEILI_ENABLE_METRICS_ROUTE, Some("true"))], || {
+            let opt = Opt::try_parse_from(Some("")).unwrap();
+            assert!(opt.enable_metrics_route);
+        });
+    }
+
     #[test]
     #[ignore]
     fn test_meilli_config_file_path_valid() {
@@ -0,0 +1,289 @@
+use std::fmt;
+use std::future::{ready, Ready};
+use std::net::IpAddr;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{self, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderValue, AUTHORIZATION, RETRY_AFTER};
+use actix_web::{Error, ResponseError as _};
+use futures_util::future::LocalBoxFuture;
+use lru::LruCache;
+use meilisearch_auth::AuthController;
+use meilisearch_types::error::ResponseError;
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::authenticated_key_uid;
+use crate::option::RateLimitOpts;
+use crate::tls::ClientIdentity;
+
+/// Past this number of tracked clients, the least recently seen ones are forgotten.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// The groups of routes sharing a rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    Search,
+    Indexing,
+    Admin,
+}
+
+impl RouteGroup {
+    /// Returns the group the route belongs to, or `None` if the route is never limited.
+    pub(crate) fn from_path(path: &str) -> Option<RouteGroup> {
+        let first_segment = path.trim_start_matches('/').split('/').next().unwrap_or_default();
+        match first_segment {
+            "multi-search" => Some(RouteGroup::Search),
+            "indexes"
+                if (path.ends_with("/search")
+                    || path.ends_with("/facet-search")
+                    || path.ends_with("/similar"))
+                    && path.matches('/').count() == 3 =>
+            {
+                Some(RouteGroup::Search)
+            }
+            "indexes" | "swap-indexes" => Some(RouteGroup::Indexing),
+            "keys" | "tasks" | "batches" | "dumps" | "stats" | "version" | "metrics" | "logs"
+            | "webhooks" => Some(RouteGroup::Admin),
+            // The health route and the dashboard are never limited.
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RouteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RouteGroup::Search => write!(f, "search"),
+            RouteGroup::Indexing => write!(f, "indexing"),
+            RouteGroup::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// The client the requests are accounted to. The requests that are not authenticated by a key
+/// are accounted to their IP address, so that sending a random key doesn't bypass the limits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Client {
+    MasterKey,
+    Key(Uuid),
+    Ip(Option<IpAddr>),
+}
+
+impl Client {
+    fn from_request(req: &ServiceRequest) -> Client {
+        let token = match req.headers().get(AUTHORIZATION).and_then(|h| h.to_str().ok()) {
+            Some(authorization) => authorization.strip_prefix("Bearer ").map(ToOwned::to_owned),
+            None => req.app_data::<AuthController>().and_then(|auth| {
+                req.conn_data::<ClientIdentity>().and_then(|identity| identity.api_key(auth))
+            }),
+        };
+
+        let auth = req.app_data::<AuthController>();
+        match token.zip(auth) {
+            Some((token, auth)) if auth.get_master_key() == Some(&token) => Client::MasterKey,
+            Some((token, auth)) => match authenticated_key_uid(auth, &token) {
+                Some(uid) => Client::Key(uid),
+                None => Client::Ip(req.peer_addr().map(|addr| addr.ip())),
+            },
+            None => Client::Ip(req.peer_addr().map(|addr| addr.ip())),
+        }
+    }
+}
+
+/// A token bucket allowing `limit` requests per second, with bursts of at most `limit` requests.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(limit: NonZeroU32, now: Instant) -> Bucket {
+        Bucket { tokens: limit.get() as f64, last_refill: now }
+    }
+
+    /// Takes a token from the bucket, or returns the time to wait before one is available.
+    fn acquire(&mut self, limit: NonZeroU32, now: Instant) -> Result<(), Duration> {
+        let limit = limit.get() as f64;
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit).min(limit);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / limit))
+        }
+    }
+}
+
+/// The state of the rate limiting, shared between all the HTTP workers.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    search: Option<NonZeroU32>,
+    indexing: Option<NonZeroU32>,
+    admin: Option<NonZeroU32>,
+    buckets: Arc<Mutex<LruCache<(RouteGroup, Client), Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(options: &RateLimitOpts) -> RateLimiter {
+        RateLimiter {
+            search: options.search_rate_limit,
+            indexing: options.indexing_rate_limit,
+            admin: options.admin_rate_limit,
+            buckets: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_TRACKED_CLIENTS).unwrap(),
+            ))),
+        }
+    }
+
+    fn limit(&self, group: RouteGroup) -> Option<NonZeroU32> {
+        match group {
+            RouteGroup::Search => self.search,
+            RouteGroup::Indexing => self.indexing,
+            RouteGroup::Admin => self.admin,
+        }
+    }
+
+    /// Registers a request made by `client` on a route of the `group`, returns the time the
+    /// client must wait before retrying if the request goes over the limit.
+    fn check(&self, group: RouteGroup, client: Client) -> Result<(), Duration> {
+        let limit = match self.limit(group) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock();
+        let key = (group, client);
+        if let Some(bucket) = buckets.get_mut(&key) {
+            return bucket.acquire(limit, now);
+        }
+        let mut bucket = Bucket::new(limit, now);
+        let result = bucket.acquire(limit, now);
+        buckets.put(key, bucket);
+        result
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimiterMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimiterMiddleware { service, limiter: self.clone() }))
+    }
+}
+
+pub struct RateLimiterMiddleware<S> {
+    service: S,
+    limiter: RateLimiter,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimiterMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        if let Some(group) = RouteGroup::from_path(req.path()) {
+            // The requests are limited per API key, or per IP address when there is none.
+            let client = Client::from_request(&req);
+            if let Err(retry_after) = self.limiter.check(group, client) {
+                let limit = self.limiter.limit(group).unwrap();
+                let error: ResponseError =
+                    MeilisearchHttpError::TooManyRequests { group, limit }.into();
+                let mut response = error.error_response();
+                // Retry-After is expressed in seconds, we round up to not make the client retry too soon.
+                let retry_after = retry_after.as_secs_f64().ceil() as u64;
+                response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_after.max(1)));
+
+                let response = req.into_response(response).map_into_right_body();
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move { Ok(fut.await?.map_into_left_body()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_groups() {
+        let group = RouteGroup::from_path;
+
+        assert_eq!(group("/indexes/movies/search"), Some(RouteGroup::Search));
+        assert_eq!(group("/indexes/movies/facet-search"), Some(RouteGroup::Search));
+        assert_eq!(group("/indexes/movies/similar"), Some(RouteGroup::Search));
+        assert_eq!(group("/multi-search"), Some(RouteGroup::Search));
+        assert_eq!(group("/indexes/movies/documents"), Some(RouteGroup::Indexing));
+        assert_eq!(group("/indexes/search"), Some(RouteGroup::Indexing));
+        assert_eq!(group("/indexes/similar"), Some(RouteGroup::Indexing));
+        assert_eq!(group("/swap-indexes"), Some(RouteGroup::Indexing));
+        assert_eq!(group("/tasks"), Some(RouteGroup::Admin));
+        assert_eq!(group("/batches/0"), Some(RouteGroup::Admin));
+        assert_eq!(group("/keys"), Some(RouteGroup::Admin));
+        assert_eq!(group("/health"), None);
+        assert_eq!(group("/"), None);
+    }
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let limit = NonZeroU32::new(2).unwrap();
+        let start = Instant::now();
+        let mut bucket = Bucket::new(limit, start);
+
+        assert_eq!(bucket.acquire(limit, start), Ok(()));
+        assert_eq!(bucket.acquire(limit, start), Ok(()));
+        assert_eq!(bucket.acquire(limit, start), Err(Duration::from_millis(500)));
+
+        let later = start + Duration::from_millis(500);
+        assert_eq!(bucket.acquire(limit, later), Ok(()));
+        assert_eq!(bucket.acquire(limit, later), Err(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn least_recently_seen_clients_are_forgotten() {
+        let limiter = RateLimiter::new(&RateLimitOpts {
+            admin_rate_limit: NonZeroU32::new(1),
+            ..RateLimitOpts::default()
+        });
+        let client = |i: u8| Client::Ip(Some(IpAddr::from([127, 0, 0, i])));
+
+        assert!(limiter.check(RouteGroup::Admin, client(0)).is_ok());
+        assert!(limiter.check(RouteGroup::Admin, client(0)).is_err());
+        for i in 0..MAX_TRACKED_CLIENTS {
+            let uid = Uuid::from_u128(i as u128);
+            assert!(limiter.check(RouteGroup::Admin, Client::Key(uid)).is_ok());
+        }
+        assert_eq!(limiter.buckets.lock().len(), MAX_TRACKED_CLIENTS);
+        // The client has been forgotten, it starts again with a full bucket.
+        assert!(limiter.check(RouteGroup::Admin, client(0)).is_ok());
+    }
+}
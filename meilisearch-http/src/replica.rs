@@ -0,0 +1,398 @@
+//! Serve the content of a primary instance, see `--read-only-replica`.
+//!
+//! A read-only replica imports the latest snapshot, or dump, of the primary instance in a
+//! new directory of its database path, named `replica-<generation>`. When the source changed
+//! since the last refresh, the next generation is imported and opened in the background, then
+//! the requests are atomically switched to its databases and the previous generation is removed.
+
+use std::future::{ready, Ready};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use std::{fs, thread};
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{self, Extensions, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{ETAG, IF_NONE_MATCH, LAST_MODIFIED};
+use actix_web::http::Method;
+use actix_web::web::Data;
+use actix_web::{Error, ResponseError as _};
+use anyhow::{anyhow, bail, Context};
+use futures_util::future::LocalBoxFuture;
+use index_scheduler::{IndexScheduler, IndexSchedulerOptions};
+use log::{error, info};
+use meilisearch_auth::AuthController;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::versioning::create_version_file;
+use parking_lot::RwLock;
+use reqwest::Url;
+use tokio::io::AsyncWriteExt;
+
+use crate::error::MeilisearchHttpError;
+use crate::rate_limit::RouteGroup;
+use crate::{import_dump, index_scheduler_options, Opt};
+
+/// The prefix of the directories containing the generations of a replica.
+const GENERATION_PREFIX: &str = "replica-";
+
+/// The databases a replica serves the requests from.
+type Databases = (Data<IndexScheduler>, AuthController);
+
+/// The file of a generation storing the version of the source it was imported from.
+const VERSION_FILE: &str = "replica-version";
+
+/// Where the snapshot, or dump, of the primary instance is pulled from.
+#[derive(Debug, Clone)]
+enum Source {
+    Url(Url),
+    Path(PathBuf),
+}
+
+impl Source {
+    fn parse(source: &str) -> anyhow::Result<Source> {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            let url = Url::parse(source)
+                .with_context(|| format!("invalid read-only replica url `{source}`"))?;
+            Ok(Source::Url(url))
+        } else {
+            Ok(Source::Path(PathBuf::from(source)))
+        }
+    }
+
+    fn is_dump(&self) -> bool {
+        match self {
+            Source::Url(url) => url.path().ends_with(".dump"),
+            Source::Path(path) => path.extension().map_or(false, |extension| extension == "dump"),
+        }
+    }
+
+    /// Returns a value that changes every time the content of the source changes.
+    fn version(&self) -> anyhow::Result<Option<String>> {
+        match self {
+            // The version of a remote source is only known once it is downloaded.
+            Source::Url(_) => Ok(None),
+            Source::Path(path) => {
+                // The incremental snapshots are created next to the full snapshot.
+                let mut version = String::new();
+                let mut position = 0;
+                let mut file = path.clone();
+                while file.exists() {
+                    let metadata = fs::metadata(&file)?;
+                    version.push_str(&format!("{:?}-{};", metadata.modified()?, metadata.len()));
+                    position += 1;
+                    let mut name = path.as_os_str().to_owned();
+                    name.push(format!(".{position}"));
+                    file = PathBuf::from(name);
+                }
+                if version.is_empty() {
+                    bail!("the read-only replica source `{}` doesn't exist", path.display());
+                }
+                Ok(Some(version))
+            }
+        }
+    }
+}
+
+/// The middleware rejecting the requests that would modify the database of a read-only
+/// replica, and serving the other ones from the latest generation of its databases.
+///
+/// It holds the only handle to the databases the app is served from, so that the previous
+/// generations of a replica are dropped once the requests using them are over. It does nothing
+/// when the instance is not a read-only replica, and always serves the databases opened at
+/// startup.
+#[derive(Clone)]
+pub struct ReadOnlyReplica {
+    databases: Arc<RwLock<Databases>>,
+    enabled: bool,
+}
+
+impl ReadOnlyReplica {
+    /// Starts refreshing the databases of the replica periodically, if the instance is one.
+    ///
+    /// The databases must have been opened by [`crate::setup_meilisearch`].
+    pub fn new(
+        opt: &Opt,
+        index_scheduler: Arc<IndexScheduler>,
+        auth_controller: AuthController,
+    ) -> anyhow::Result<ReadOnlyReplica> {
+        let databases = Arc::new(RwLock::new((Data::from(index_scheduler), auth_controller)));
+        let source = match &opt.read_only_replica {
+            Some(source) => Source::parse(source)?,
+            None => return Ok(ReadOnlyReplica { databases, enabled: false }),
+        };
+
+        let mut generation = generations(&opt.db_path)?
+            .last()
+            .copied()
+            .context("the read-only replica has not been set up")?;
+        let generation_version = generation_path(&opt.db_path, generation).join(VERSION_FILE);
+        let mut version = fs::read_to_string(generation_version).ok();
+
+        let opt = opt.clone();
+        let refresh_interval = Duration::from_secs(opt.read_only_replica_refresh_interval_sec);
+        let shared_databases = databases.clone();
+        thread::Builder::new().name(String::from("read-only-replica")).spawn(move || loop {
+            thread::sleep(refresh_interval);
+
+            let next_generation = generation + 1;
+            let next_path = generation_path(&opt.db_path, next_generation);
+            match import(&opt, &source, &next_path, version.as_deref()) {
+                Ok(Some((index_scheduler, auth_controller, next_version))) => {
+                    *shared_databases.write() = (Data::new(index_scheduler), auth_controller);
+                    // The files of the previous generation are only freed once the requests
+                    // still using its databases are over.
+                    let previous_path = generation_path(&opt.db_path, generation);
+                    if let Err(e) = fs::remove_dir_all(&previous_path) {
+                        error!("Could not remove the previous databases of the replica: {e}");
+                    }
+                    info!("The read-only replica now serves the generation {next_generation}.");
+                    generation = next_generation;
+                    version = next_version;
+                }
+                Ok(None) => (),
+                Err(e) => {
+                    error!("Could not refresh the read-only replica: {e:#}");
+                    let _ = fs::remove_dir_all(&next_path);
+                }
+            }
+        })?;
+
+        Ok(ReadOnlyReplica { databases, enabled: true })
+    }
+
+    /// Returns `true` if the instance is a read-only replica.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the index scheduler the requests are currently served from.
+    ///
+    /// It must not be kept longer than needed, as it may belong to a previous generation.
+    pub fn index_scheduler(&self) -> Data<IndexScheduler> {
+        self.databases.read().0.clone()
+    }
+
+    /// Returns the auth controller the requests are currently served from.
+    ///
+    /// It must not be kept longer than needed, as it may belong to a previous generation.
+    pub fn auth_controller(&self) -> AuthController {
+        self.databases.read().1.clone()
+    }
+}
+
+/// Imports the first generation of a read-only replica in the database path and opens it.
+///
+/// The database path must either be empty or only contain the generations of a previous replica.
+pub(crate) fn setup_replica(
+    opt: &Opt,
+    source: &str,
+) -> anyhow::Result<(IndexScheduler, AuthController)> {
+    let source = Source::parse(source)?;
+    if opt.db_path.exists() {
+        // The files of the database path, like the instance uid, are kept.
+        let generations = generations(&opt.db_path)?;
+        let directories = fs::read_dir(&opt.db_path)?
+            .filter(|entry| entry.as_ref().map_or(true, |entry| entry.path().is_dir()))
+            .count();
+        if directories != generations.len() {
+            bail!(
+                "database already exists at {:?} and is not a read-only replica, try to delete it or rename it",
+                opt.db_path.canonicalize().unwrap_or_else(|_| opt.db_path.to_owned())
+            )
+        }
+        for generation in generations {
+            fs::remove_dir_all(generation_path(&opt.db_path, generation))?;
+        }
+    }
+
+    let path = generation_path(&opt.db_path, 0);
+    match import(opt, &source, &path, None) {
+        Ok(Some((index_scheduler, auth_controller, _version))) => {
+            Ok((index_scheduler, auth_controller))
+        }
+        Ok(None) => unreachable!("the first generation is always imported"),
+        Err(e) => {
+            let _ = fs::remove_dir_all(&path);
+            Err(e)
+        }
+    }
+}
+
+/// Returns the generations found in the database path, in ascending order.
+fn generations(db_path: &Path) -> anyhow::Result<Vec<u64>> {
+    let mut generations = Vec::new();
+    for entry in fs::read_dir(db_path)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let generation = name.to_str().and_then(|name| name.strip_prefix(GENERATION_PREFIX));
+        if let Some(generation) = generation.and_then(|generation| generation.parse().ok()) {
+            generations.push(generation);
+        }
+    }
+    generations.sort_unstable();
+    Ok(generations)
+}
+
+fn generation_path(db_path: &Path, generation: u64) -> PathBuf {
+    db_path.join(format!("{GENERATION_PREFIX}{generation}"))
+}
+
+/// Imports the source in `path` and opens its databases, returns `None` if the source
+/// didn't change since it was at the given version.
+fn import(
+    opt: &Opt,
+    source: &Source,
+    path: &Path,
+    version: Option<&str>,
+) -> anyhow::Result<Option<(IndexScheduler, AuthController, Option<String>)>> {
+    let mut new_version = source.version()?;
+    if new_version.is_some() && new_version.as_deref() == version {
+        return Ok(None);
+    }
+
+    fs::create_dir_all(path)?;
+    let file = match source {
+        Source::Url(url) => {
+            let file = path.join("replica-source");
+            match download(url, &file, version)? {
+                Some(version) => new_version = version,
+                None => {
+                    fs::remove_dir_all(path)?;
+                    return Ok(None);
+                }
+            }
+            file
+        }
+        Source::Path(file) => file.clone(),
+    };
+
+    if !source.is_dump() {
+        index_scheduler::import_snapshot(&file, path)?;
+    }
+    let mut index_scheduler = IndexScheduler::new(IndexSchedulerOptions {
+        read_only: true,
+        ..index_scheduler_options(opt, path)?
+    })?;
+    let mut auth_controller = AuthController::new(path, &opt.master_key)?;
+    if source.is_dump() {
+        import_dump(path, &file, &mut index_scheduler, &mut auth_controller)?;
+    }
+    create_version_file(path)?;
+    if let Source::Url(_) = source {
+        fs::remove_file(&file)?;
+    }
+    if let Some(version) = &new_version {
+        fs::write(path.join(VERSION_FILE), version)?;
+    }
+
+    Ok(Some((index_scheduler, auth_controller, new_version)))
+}
+
+/// Downloads the file at `url` in `path`, returns `None` if it didn't change since it was at
+/// the given version, or the new version of the file, if the server provided one.
+fn download(
+    url: &Url,
+    path: &Path,
+    version: Option<&str>,
+) -> anyhow::Result<Option<Option<String>>> {
+    let (url, path, version) = (url.clone(), path.to_owned(), version.map(ToOwned::to_owned));
+    // The download needs its own runtime, which can't be started from an asynchronous context.
+    thread::spawn(move || -> anyhow::Result<Option<Option<String>>> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        runtime.block_on(async move {
+            let mut request = reqwest::Client::new().get(url.clone());
+            if let Some(version) = version {
+                request = request.header(IF_NONE_MATCH.as_str(), version);
+            }
+            let response = request.send().await?;
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                return Ok(None);
+            }
+            let mut response = response.error_for_status()?;
+            let version = response
+                .headers()
+                .get(ETAG.as_str())
+                .or_else(|| response.headers().get(LAST_MODIFIED.as_str()))
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned);
+
+            let mut file = tokio::fs::File::create(&path).await?;
+            while let Some(chunk) = response.chunk().await? {
+                file.write_all(&chunk).await?;
+            }
+            file.flush().await?;
+            Ok::<_, anyhow::Error>(Some(version))
+        })
+    })
+    .join()
+    .map_err(|_| anyhow!("the download of `{url}` panicked"))?
+}
+
+/// Returns `true` if the request can't modify the database.
+fn is_read_only(req: &ServiceRequest) -> bool {
+    match *req.method() {
+        Method::GET | Method::HEAD | Method::OPTIONS => true,
+        Method::POST => {
+            RouteGroup::from_path(req.path()) == Some(RouteGroup::Search)
+                || req.path() == "/logs/stream"
+        }
+        _ => false,
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ReadOnlyReplica
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ReadOnlyReplicaMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        let databases = self.enabled.then(|| self.databases.clone());
+        ready(Ok(ReadOnlyReplicaMiddleware { service, databases }))
+    }
+}
+
+pub struct ReadOnlyReplicaMiddleware<S> {
+    service: S,
+    databases: Option<Arc<RwLock<Databases>>>,
+}
+
+impl<S, B> Service<ServiceRequest> for ReadOnlyReplicaMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        if let Some(databases) = &self.databases {
+            if !is_read_only(&req) {
+                let error: ResponseError = MeilisearchHttpError::ReadOnlyReplica.into();
+                let response = req.into_response(error.error_response()).map_into_right_body();
+                return Box::pin(async move { Ok(response) });
+            }
+
+            // The data containers added to a request take precedence over the ones of the app.
+            let (index_scheduler, auth_controller) = databases.read().clone();
+            let mut extensions = Extensions::new();
+            extensions.insert(index_scheduler);
+            extensions.insert(auth_controller);
+            req.add_data_container(Rc::new(extensions));
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move { Ok(fut.await?.map_into_left_body()) })
+    }
+}
@@ -0,0 +1,82 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::{self, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::Error;
+use futures_util::future::LocalBoxFuture;
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// The header in which the id of a request is read and sent back.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// The maximum length of a request id provided by a client.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Gives an id to every request, reused from the `X-Request-Id` header when the client provides
+/// one, and processes the request in a span holding it so that everything logged meanwhile can
+/// be related to the request.
+pub struct RequestId;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequestIdMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddleware { service }))
+    }
+}
+
+pub struct RequestIdMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+            .and_then(|id| id.to_str().ok())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        let span = tracing::info_span!(
+            "request",
+            request_id = %request_id,
+            method = %req.method(),
+            path = req.path(),
+        );
+        let fut = span.in_scope(|| self.service.call(req));
+
+        Box::pin(
+            async move {
+                let mut res = fut.await?;
+                // The id is either a valid header value sent by the client or an uuid.
+                let request_id = HeaderValue::from_str(&request_id).unwrap();
+                res.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), request_id);
+                Ok(res)
+            }
+            .instrument(span),
+        )
+    }
+}
@@ -2,21 +2,24 @@ use std::future::{ready, Ready};
 
 use actix_web::dev::{self, Service, ServiceRequest, ServiceResponse, Transform};
 use actix_web::http::header;
+use actix_web::web::Data;
 use actix_web::{Error, HttpResponse};
 use futures_util::future::LocalBoxFuture;
+use index_scheduler::{IndexScheduler, Query};
 use meilisearch_auth::actions;
-use meilisearch_lib::MeiliSearch;
 use meilisearch_types::error::ResponseError;
+use meilisearch_types::tasks::Status;
 use prometheus::{Encoder, HistogramTimer, TextEncoder};
 
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::GuardedData;
+use crate::routes::create_all_stats;
 
 pub async fn get_metrics(
-    meilisearch: GuardedData<ActionPolicy<{ actions::METRICS_GET }>, MeiliSearch>,
+    index_scheduler: GuardedData<ActionPolicy<{ actions::METRICS_GET }>, Data<IndexScheduler>>,
 ) -> Result<HttpResponse, ResponseError> {
-    let search_rules = &meilisearch.filters().search_rules;
-    let response = meilisearch.get_all_stats(search_rules).await?;
+    let search_rules = &index_scheduler.filters().search_rules;
+    let response = create_all_stats((*index_scheduler).clone(), search_rules, false)?;
 
     crate::metrics::MEILISEARCH_DB_SIZE_BYTES.set(response.database_size as i64);
     crate::metrics::MEILISEARCH_INDEX_COUNT.set(response.indexes.len() as i64);
@@ -27,6 +30,30 @@ pub async fn get_metrics(
             .set(value.number_of_documents as i64);
     }
 
+    for (name, index) in index_scheduler.indexes()? {
+        if search_rules.is_index_authorized(&name) {
+            crate::metrics::MEILISEARCH_INDEX_SIZE_BYTES
+                .with_label_values(&[&name])
+                .set(index.on_disk_size()? as i64);
+        }
+    }
+    crate::metrics::MEILISEARCH_INDEX_MAP_SIZE_BYTES.set(index_scheduler.index_map_size() as i64);
+
+    let authorized_indexes = search_rules.authorized_indexes();
+    let rtxn = index_scheduler.read_txn()?;
+    for status in [Status::Enqueued, Status::Processing] {
+        let query = Query { statuses: Some(vec![status]), ..Query::default() };
+        let tasks = index_scheduler.get_task_ids_from_authorized_indexes(
+            &rtxn,
+            &query,
+            &authorized_indexes,
+        )?;
+        crate::metrics::MEILISEARCH_TASK_QUEUE_LENGTH
+            .with_label_values(&[&status.to_string()])
+            .set(tasks.len() as i64);
+    }
+    drop(rtxn);
+
     let encoder = TextEncoder::new();
     let mut buffer = vec![];
     encoder.encode(&prometheus::gather(), &mut buffer).expect("Failed to encode metrics");
@@ -88,6 +115,17 @@ where
             crate::metrics::HTTP_REQUESTS_TOTAL
                 .with_label_values(&[&request_method, request_path])
                 .inc();
+
+            let payload_size = req
+                .headers()
+                .get(header::CONTENT_LENGTH)
+                .and_then(|length| length.to_str().ok())
+                .and_then(|length| length.parse::<u64>().ok());
+            if let Some(payload_size) = payload_size {
+                crate::metrics::HTTP_PAYLOAD_SIZE_BYTES
+                    .with_label_values(&[&request_method, request_path])
+                    .observe(payload_size as f64);
+            }
         }
 
         let fut = self.service.call(req);
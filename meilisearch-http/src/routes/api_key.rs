@@ -131,6 +131,8 @@ struct KeyView {
     uid: Uuid,
     actions: Vec<Action>,
     indexes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<Value>,
     #[serde(serialize_with = "time::serde::rfc3339::option::serialize")]
     expires_at: Option<OffsetDateTime>,
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
@@ -150,6 +152,7 @@ impl KeyView {
             uid: key.uid,
             actions: key.actions,
             indexes: key.indexes.into_iter().map(String::from).collect(),
+            filter: key.filter,
             expires_at: key.expires_at,
             created_at: key.created_at,
             updated_at: key.updated_at,
@@ -0,0 +1,127 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use index_scheduler::{BatchQuery, IndexScheduler};
+use meilisearch_types::batches::{Batch, BatchId};
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::tasks::{serialize_duration, TaskId};
+use serde::{Deserialize, Serialize};
+use time::{Duration, OffsetDateTime};
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+const DEFAULT_LIMIT: fn() -> u32 = || 20;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_batches))))
+        .service(web::resource("/{batch_uid}").route(web::get().to(SeqHandler(get_batch))));
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchView {
+    pub uid: BatchId,
+    pub task_uids: Vec<TaskId>,
+    pub index_uids: Vec<String>,
+    #[serde(serialize_with = "serialize_duration")]
+    pub duration: Option<Duration>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
+
+impl BatchView {
+    pub fn from_batch(batch: &Batch) -> BatchView {
+        BatchView {
+            uid: batch.uid,
+            task_uids: batch.task_uids.clone(),
+            index_uids: batch.index_uids.clone(),
+            duration: Some(batch.finished_at - batch.started_at),
+            started_at: batch.started_at,
+            finished_at: batch.finished_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BatchesFilterQuery {
+    #[serde(default = "DEFAULT_LIMIT")]
+    limit: u32,
+    from: Option<BatchId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AllBatches {
+    results: Vec<BatchView>,
+    limit: u32,
+    from: Option<u32>,
+    next: Option<u32>,
+}
+
+async fn get_batches(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: web::Query<BatchesFilterQuery>,
+) -> Result<HttpResponse, ResponseError> {
+    let BatchesFilterQuery { limit, from } = params.into_inner();
+
+    // We +1 just to know if there is more after this "page" or not.
+    let limit = limit.saturating_add(1);
+
+    let query = BatchQuery { limit: Some(limit), from, uids: None };
+
+    let mut batches_results: Vec<BatchView> = index_scheduler
+        .get_batches_from_authorized_indexes(
+            query,
+            index_scheduler.filters().search_rules.authorized_indexes(),
+        )?
+        .iter()
+        .map(BatchView::from_batch)
+        .collect();
+
+    // If we were able to fetch the number +1 batches we asked
+    // it means that there is more to come.
+    let next = if batches_results.len() == limit as usize {
+        batches_results.pop().map(|b| b.uid)
+    } else {
+        None
+    };
+
+    let from = batches_results.first().map(|b| b.uid);
+
+    let batches =
+        AllBatches { results: batches_results, limit: limit.saturating_sub(1), from, next };
+    Ok(HttpResponse::Ok().json(batches))
+}
+
+async fn get_batch(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    batch_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let batch_uid_string = batch_uid.into_inner();
+
+    let batch_uid: BatchId = match batch_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(
+                index_scheduler::Error::InvalidBatchUid { batch_uid: batch_uid_string }.into()
+            )
+        }
+    };
+
+    let query = BatchQuery { uids: Some(vec![batch_uid]), ..BatchQuery::default() };
+
+    if let Some(batch) = index_scheduler
+        .get_batches_from_authorized_indexes(
+            query,
+            index_scheduler.filters().search_rules.authorized_indexes(),
+        )?
+        .first()
+    {
+        Ok(HttpResponse::Ok().json(BatchView::from_batch(batch)))
+    } else {
+        Err(index_scheduler::Error::BatchNotFound(batch_uid).into())
+    }
+}
@@ -1,22 +1,40 @@
-use actix_web::web::Data;
-use actix_web::{web, HttpRequest, HttpResponse};
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+
+use actix_multipart::Multipart;
+use actix_web::body::SizedStream;
+use actix_web::error::PayloadError;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::{Bytes, Data};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use index_scheduler::IndexScheduler;
 use log::debug;
 use meilisearch_auth::AuthController;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
+use tokio::io::AsyncReadExt;
 
 use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::extractors::payload::Payload;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::SummarizedTaskView;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))));
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_dump))))
+        .service(web::resource("/import").route(web::post().to(SeqHandler(import_dump))))
+        .service(
+            web::resource("/{dump_uid}/download").route(web::get().to(SeqHandler(download_dump))),
+        );
 }
 
+/// The size of the chunks sent while streaming a dump.
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
 pub async fn create_dump(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, Data<IndexScheduler>>,
     auth_controller: GuardedData<ActionPolicy<{ actions::DUMPS_CREATE }>, AuthController>,
@@ -35,3 +53,123 @@ pub async fn create_dump(
     debug!("returns: {:?}", task);
     Ok(HttpResponse::Accepted().json(task))
 }
+
+/// Sends a dump that was created by a dump creation task.
+///
+/// The dump is written on the disk of the instance first, it can only be downloaded once its
+/// task succeeded. Streaming a dump while it is being created is not supported.
+pub async fn download_dump(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_GET }>, Data<IndexScheduler>>,
+    dump_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let dump_uid = dump_uid.into_inner();
+    let file = index_scheduler.dump_file(&dump_uid)?;
+    let size = file.metadata().map_err(index_scheduler::Error::IoError)?.len();
+
+    // The dump is sent chunk by chunk so that it never has to fit in memory.
+    let file = tokio::fs::File::from_std(file);
+    let chunks = stream::try_unfold(file, |mut file| async move {
+        let mut chunk = vec![0; DOWNLOAD_CHUNK_SIZE];
+        match file.read(&mut chunk).await? {
+            0 => Ok::<_, std::io::Error>(None),
+            read => {
+                chunk.truncate(read);
+                Ok(Some((Bytes::from(chunk), file)))
+            }
+        }
+    });
+
+    debug!("streaming the dump {}", dump_uid);
+    Ok(HttpResponse::Ok()
+        .content_type("application/gzip")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("{dump_uid}.dump"))],
+        })
+        .body(SizedStream::new(size, chunks)))
+}
+
+pub async fn import_dump(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DUMPS_IMPORT }>, Data<IndexScheduler>>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    analytics.publish("Dump Imported".to_string(), json!({}), Some(&req));
+
+    let (uuid, mut update_file) = index_scheduler.create_update_file()?;
+
+    // The dump is either sent as the raw body or as the `dump` field of a multipart form.
+    let is_multipart = matches!(
+        req.mime_type(),
+        Ok(Some(mime)) if mime.type_() == mime::MULTIPART && mime.subtype() == mime::FORM_DATA
+    );
+    if is_multipart {
+        let body = body.map_err(|error| match error {
+            MeilisearchHttpError::PayloadTooLarge => PayloadError::Overflow,
+            error => PayloadError::Io(io::Error::new(io::ErrorKind::Other, error.to_string())),
+        });
+        let mut form = Multipart::new(req.headers(), body);
+        let mut field = loop {
+            match form.try_next().await.map_err(MeilisearchHttpError::from)? {
+                Some(field) if field.content_disposition().get_name() == Some("dump") => {
+                    break field
+                }
+                // The other fields must be consumed before reading the next one.
+                Some(mut field) => {
+                    while field.try_next().await.map_err(MeilisearchHttpError::from)?.is_some() {}
+                }
+                None => return Err(MeilisearchHttpError::MissingDumpField.into()),
+            }
+        };
+        write_payload(&mut field, update_file.as_file()).await?;
+    } else {
+        write_payload(body, update_file.as_file()).await?;
+    }
+
+    // Only the version of the dump is checked here, its content is read when the task is processed.
+    let version = tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
+        let file = update_file.as_file_mut();
+        file.seek(SeekFrom::Start(0)).map_err(index_scheduler::Error::IoError)?;
+        let version = dump::DumpReader::read_version(file)?;
+        update_file.persist()?;
+        Ok(version)
+    })
+    .await??;
+    debug!("importing a dump of version {:?}", version);
+
+    let task = KindWithContent::DumpImport { content_file: uuid };
+    let scheduler = index_scheduler.clone();
+    let task = match tokio::task::spawn_blocking(move || scheduler.register(task)).await? {
+        Ok(task) => task,
+        Err(e) => {
+            index_scheduler.delete_update_file(uuid)?;
+            return Err(e.into());
+        }
+    };
+
+    let task: SummarizedTaskView = task.into();
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Writes the chunks of a payload to the file, without ever holding the whole payload in memory.
+///
+/// The chunks are written on a blocking thread so that the disk never stalls the HTTP workers.
+async fn write_payload<E>(
+    mut payload: impl Stream<Item = Result<Bytes, E>> + Unpin,
+    file: &File,
+) -> Result<(), MeilisearchHttpError>
+where
+    MeilisearchHttpError: From<E>,
+{
+    let mut file = file.try_clone().map_err(index_scheduler::Error::IoError)?;
+    while let Some(bytes) = payload.next().await {
+        let bytes = bytes?;
+        file = tokio::task::spawn_blocking(move || {
+            file.write_all(&bytes).map(|()| file).map_err(index_scheduler::Error::IoError)
+        })
+        .await??;
+    }
+    Ok(())
+}
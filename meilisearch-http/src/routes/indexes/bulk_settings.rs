@@ -0,0 +1,426 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+
+use actix_web::web::{Bytes, Data};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use futures::StreamExt;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::document_formats::PayloadType;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::settings::settings;
+use meilisearch_types::tasks::{KindWithContent, SettingsDelta};
+use once_cell::sync::Lazy;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::payload::Payload;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::SummarizedTaskView;
+
+/// The number of chunks of an import received but not yet written to disk.
+const IMPORT_CHUNKS_IN_FLIGHT: usize = 16;
+
+static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> =
+    Lazy::new(|| vec!["application/x-ndjson".to_string(), "text/csv".to_string()]);
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/synonyms/import").route(web::put().to(SeqHandler(import_synonyms))),
+    )
+    .service(web::resource("/synonyms/export").route(web::get().to(SeqHandler(export_synonyms))))
+    .service(
+        web::resource("/stop-words/import").route(web::put().to(SeqHandler(import_stop_words))),
+    )
+    .service(
+        web::resource("/stop-words/export").route(web::get().to(SeqHandler(export_stop_words))),
+    );
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Operation {
+    Add,
+    Remove,
+}
+
+/// A line of an NDJSON import of synonyms.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct SynonymsLine {
+    word: String,
+    #[serde(default)]
+    synonyms: Vec<String>,
+    operation: Option<Operation>,
+}
+
+/// A record of a CSV import of synonyms, there is one record per synonym.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SynonymsRecord {
+    word: String,
+    synonym: Option<String>,
+    operation: Option<Operation>,
+}
+
+/// A line of an NDJSON import or a record of a CSV import of stop words.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StopWordEntry {
+    word: String,
+    operation: Option<Operation>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Ndjson,
+    Csv,
+}
+
+impl ExportFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Csv => "text/csv",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ExportQuery {
+    #[serde(default = "default_export_format")]
+    format: ExportFormat,
+}
+
+fn default_export_format() -> ExportFormat {
+    ExportFormat::Ndjson
+}
+
+pub async fn import_synonyms(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?.into_inner();
+    let format = import_format(&req)?;
+    let delta = read_payload(body, format, move |payload| {
+        let mut delta = SettingsDelta::default();
+        match format {
+            PayloadType::Csv => {
+                for_each_entry(payload, format, |entry, record: SynonymsRecord| {
+                    add_synonyms(&mut delta, entry, record.word, record.synonym, record.operation)
+                })?;
+            }
+            _ => {
+                for_each_entry(payload, format, |entry, line: SynonymsLine| {
+                    add_synonyms(&mut delta, entry, line.word, line.synonyms, line.operation)
+                })?;
+            }
+        }
+        Ok(delta)
+    })
+    .await?;
+
+    analytics.publish(
+        "Synonyms Imported".to_string(),
+        json!({ "format": format.to_string() }),
+        Some(&req),
+    );
+
+    register_import(index_scheduler, index_uid, delta).await
+}
+
+pub async fn import_stop_words(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?.into_inner();
+    let format = import_format(&req)?;
+    let delta = read_payload(body, format, move |payload| {
+        let mut delta = SettingsDelta::default();
+        for_each_entry(payload, format, |entry, StopWordEntry { word, operation }| {
+            check_word(entry, &word)?;
+            match operation.unwrap_or(Operation::Add) {
+                Operation::Add => delta.add_stop_word(word),
+                Operation::Remove => delta.remove_stop_word(word),
+            }
+            Ok(())
+        })?;
+        Ok(delta)
+    })
+    .await?;
+
+    analytics.publish(
+        "Stop Words Imported".to_string(),
+        json!({ "format": format.to_string() }),
+        Some(&req),
+    );
+
+    register_import(index_scheduler, index_uid, delta).await
+}
+
+pub async fn export_synonyms(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: web::Query<ExportQuery>,
+) -> Result<HttpResponse, ResponseError> {
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let synonyms = settings(&index, &rtxn)?.synonyms.set().unwrap_or_default();
+
+    // The exported files can be imported as is.
+    let format = params.format;
+    let body = match format {
+        ExportFormat::Ndjson => {
+            let mut body = Vec::new();
+            for (word, synonyms) in synonyms {
+                let line = json!({ "word": word, "synonyms": synonyms });
+                serde_json::to_writer(&mut body, &line).map_err(MeilisearchHttpError::from)?;
+                body.push(b'\n');
+            }
+            body
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(["word", "synonym"]).map_err(csv_error)?;
+            for (word, synonyms) in synonyms {
+                for synonym in synonyms {
+                    writer.write_record([&word, &synonym]).map_err(csv_error)?;
+                }
+            }
+            writer.into_inner().map_err(csv_error)?
+        }
+    };
+
+    debug!("exporting the synonyms of {} as {:?}", index_uid, format);
+    Ok(HttpResponse::Ok().content_type(format.content_type()).body(body))
+}
+
+pub async fn export_stop_words(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: web::Query<ExportQuery>,
+) -> Result<HttpResponse, ResponseError> {
+    let index = index_scheduler.index(&index_uid)?;
+    let rtxn = index.read_txn()?;
+    let stop_words = settings(&index, &rtxn)?.stop_words.set().unwrap_or_default();
+
+    let format = params.format;
+    let body = match format {
+        ExportFormat::Ndjson => {
+            let mut body = Vec::new();
+            for word in stop_words {
+                let line = json!({ "word": word });
+                serde_json::to_writer(&mut body, &line).map_err(MeilisearchHttpError::from)?;
+                body.push(b'\n');
+            }
+            body
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(["word"]).map_err(csv_error)?;
+            for word in stop_words {
+                writer.write_record([&word]).map_err(csv_error)?;
+            }
+            writer.into_inner().map_err(csv_error)?
+        }
+    };
+
+    debug!("exporting the stop words of {} as {:?}", index_uid, format);
+    Ok(HttpResponse::Ok().content_type(format.content_type()).body(body))
+}
+
+fn csv_error(error: impl std::fmt::Display) -> ResponseError {
+    ResponseError::from_msg(error.to_string(), Code::Internal)
+}
+
+fn import_format(req: &HttpRequest) -> Result<PayloadType, MeilisearchHttpError> {
+    let mime_type = req.mime_type().ok().flatten();
+    match mime_type.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())) {
+        Some(("application", "x-ndjson")) => Ok(PayloadType::Ndjson),
+        Some(("text", "csv")) => Ok(PayloadType::Csv),
+        Some((type_, subtype)) => Err(MeilisearchHttpError::InvalidContentType(
+            format!("{}/{}", type_, subtype),
+            ACCEPTED_CONTENT_TYPE.clone(),
+        )),
+        None => Err(MeilisearchHttpError::MissingContentType(ACCEPTED_CONTENT_TYPE.clone())),
+    }
+}
+
+/// Writes the payload to a temporary file, without ever holding the whole payload in memory,
+/// then calls `f` with the content of the file. The file is written and read on a blocking
+/// thread fed with the chunks of the payload.
+async fn read_payload<T, F>(
+    mut body: Payload,
+    format: PayloadType,
+    f: F,
+) -> Result<T, MeilisearchHttpError>
+where
+    T: Send + 'static,
+    F: FnOnce(BufReader<File>) -> Result<T, MeilisearchHttpError> + Send + 'static,
+{
+    let (sender, mut receiver) = mpsc::channel(IMPORT_CHUNKS_IN_FLIGHT);
+    let import = tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
+        let mut file = tempfile::tempfile().map_err(index_scheduler::Error::IoError)?;
+        let mut is_empty = true;
+        while let Some(bytes) = receiver.blocking_recv() {
+            let bytes: Bytes = bytes?;
+            is_empty &= bytes.is_empty();
+            file.write_all(&bytes).map_err(index_scheduler::Error::IoError)?;
+        }
+        if is_empty {
+            return Err(MeilisearchHttpError::MissingPayload(format));
+        }
+        file.seek(SeekFrom::Start(0)).map_err(index_scheduler::Error::IoError)?;
+        f(BufReader::new(file))
+    });
+
+    while let Some(bytes) = body.next().await {
+        let is_err = bytes.is_err();
+        // The import stopped on an error, it is returned once the task is joined.
+        if sender.send(bytes).await.is_err() || is_err {
+            break;
+        }
+    }
+    drop(sender);
+
+    import.await?
+}
+
+/// Calls `f` on each entry of the file with the number of the entry, starting at one.
+fn for_each_entry<T, F>(
+    payload: impl Read,
+    format: PayloadType,
+    mut f: F,
+) -> Result<(), MeilisearchHttpError>
+where
+    T: DeserializeOwned,
+    F: FnMut(usize, T) -> Result<(), MeilisearchHttpError>,
+{
+    match format {
+        PayloadType::Csv => {
+            let mut reader = csv::Reader::from_reader(payload);
+            for (i, record) in reader.deserialize().enumerate() {
+                let record = record.map_err(|e| {
+                    MeilisearchHttpError::InvalidSettingsImport(i + 1, e.to_string())
+                })?;
+                f(i + 1, record)?;
+            }
+        }
+        _ => {
+            let lines = serde_json::Deserializer::from_reader(payload).into_iter();
+            for (i, line) in lines.enumerate() {
+                let line = line.map_err(|e| {
+                    MeilisearchHttpError::InvalidSettingsImport(i + 1, e.to_string())
+                })?;
+                f(i + 1, line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_word(entry: usize, word: &str) -> Result<(), MeilisearchHttpError> {
+    if word.trim().is_empty() {
+        return Err(MeilisearchHttpError::InvalidSettingsImport(
+            entry,
+            "the word can't be empty.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn add_synonyms(
+    delta: &mut SettingsDelta,
+    entry: usize,
+    word: String,
+    synonyms: impl IntoIterator<Item = String>,
+    operation: Option<Operation>,
+) -> Result<(), MeilisearchHttpError> {
+    check_word(entry, &word)?;
+    let synonyms: Vec<_> = synonyms.into_iter().collect();
+    if synonyms.iter().any(|synonym| synonym.trim().is_empty()) {
+        return Err(MeilisearchHttpError::InvalidSettingsImport(
+            entry,
+            "a synonym can't be empty.".to_string(),
+        ));
+    }
+
+    match operation.unwrap_or(Operation::Add) {
+        Operation::Add if synonyms.is_empty() => {
+            return Err(MeilisearchHttpError::InvalidSettingsImport(
+                entry,
+                format!("at least one synonym must be added to the word `{}`.", word),
+            ))
+        }
+        Operation::Add => {
+            for synonym in synonyms {
+                delta.add_synonym(word.clone(), synonym);
+            }
+        }
+        // Without synonyms, all the synonyms of the word are removed.
+        Operation::Remove if synonyms.is_empty() => delta.remove_synonym(word, None),
+        Operation::Remove => {
+            for synonym in synonyms {
+                delta.remove_synonym(word.clone(), Some(synonym));
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn register_import(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: String,
+    delta: SettingsDelta,
+) -> Result<HttpResponse, ResponseError> {
+    let added_entries = delta.added_entries();
+    let removed_entries = delta.removed_entries();
+
+    // The delta is stored in an update file, like the documents of a document addition.
+    let scheduler = index_scheduler.clone();
+    let uuid = tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
+        let (uuid, mut update_file) = scheduler.create_update_file()?;
+        let mut writer = BufWriter::new(update_file.as_file_mut());
+        serde_json::to_writer(&mut writer, &delta)?;
+        writer.flush().map_err(index_scheduler::Error::IoError)?;
+        drop(writer);
+        update_file.persist()?;
+        Ok(uuid)
+    })
+    .await??;
+
+    let task = KindWithContent::SettingsImport {
+        index_uid,
+        content_file: uuid,
+        added_entries,
+        removed_entries,
+    };
+    let scheduler = index_scheduler.clone();
+    let task = match tokio::task::spawn_blocking(move || scheduler.register(task)).await? {
+        Ok(task) => task,
+        Err(e) => {
+            index_scheduler.delete_update_file(uuid)?;
+            return Err(e.into());
+        }
+    };
+
+    let task: SummarizedTaskView = task.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
@@ -1,25 +1,32 @@
+use std::collections::{BTreeMap, HashSet};
 use std::io::{Cursor, ErrorKind};
 
 use actix_web::http::header::CONTENT_TYPE;
-use actix_web::web::Data;
-use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::web::{Bytes, Data};
+use actix_web::{guard, web, HttpMessage, HttpRequest, HttpResponse};
 use bstr::ByteSlice;
-use futures::StreamExt;
+use futures::{stream, StreamExt};
 use index_scheduler::IndexScheduler;
 use log::debug;
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_auth::AuthFilter;
+use meilisearch_types::document_formats::{
+    read_csv, read_json, read_msgpack, NdjsonStreamReader, PayloadType,
+};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::star_or::StarOr;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::tasks::{Details, DocumentEdition, KindWithContent};
 use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
+use roaring::RoaringBitmap;
 use serde::Deserialize;
 use serde_cs::vec::CS;
-use serde_json::Value;
+use serde_json::{json, Map, Number, Value};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::analytics::{Analytics, DocumentDeletionKind};
 use crate::error::MeilisearchHttpError;
@@ -27,10 +34,18 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::extractors::payload::Payload;
 use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::fuse_filters;
 use crate::routes::{fold_star_or, PaginationView, SummarizedTaskView};
+use crate::search::parse_filter;
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "application/msgpack".to_string(),
+        "application/x-msgpack".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -63,8 +78,21 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::put().to(SeqHandler(update_documents)))
             .route(web::delete().to(SeqHandler(clear_all_documents))),
     )
-    // this route needs to be before the /documents/{document_id} to match properly
+    // these routes need to be before the /documents/{document_id} to match properly
     .service(web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents))))
+    // The other methods fall through to the document named `edit`.
+    .service(
+        web::resource("/edit")
+            .guard(guard::Post())
+            .route(web::post().to(SeqHandler(edit_documents))),
+    )
+    // The other methods fall through to the document named `fetch`, which can only be read
+    // through the documents listing.
+    .service(
+        web::resource("/fetch")
+            .guard(guard::Get())
+            .route(web::get().to(SeqHandler(fetch_documents))),
+    )
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
@@ -86,8 +114,14 @@ pub async fn get_document(
     let GetDocument { fields } = params.into_inner();
     let attributes_to_retrieve = fields.and_then(fold_star_or);
 
+    let rules_filter = search_rules_filter(index_scheduler.filters(), &path.index_uid);
     let index = index_scheduler.index(&path.index_uid)?;
-    let document = retrieve_document(&index, &path.document_id, attributes_to_retrieve)?;
+    let document = retrieve_document(
+        &index,
+        &path.document_id,
+        rules_filter.as_ref(),
+        attributes_to_retrieve,
+    )?;
     debug!("returns: {:?}", document);
     Ok(HttpResponse::Ok().json(document))
 }
@@ -127,8 +161,10 @@ pub async fn get_all_documents(
     let BrowseQuery { limit, offset, fields } = params.into_inner();
     let attributes_to_retrieve = fields.and_then(fold_star_or);
 
+    let rules_filter = search_rules_filter(index_scheduler.filters(), &index_uid);
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset, limit, attributes_to_retrieve)?;
+    let (total, documents) =
+        retrieve_documents(&index, rules_filter.as_ref(), offset, limit, attributes_to_retrieve)?;
 
     let ret = PaginationView::new(offset, limit, total as usize, documents);
 
@@ -136,10 +172,55 @@ pub async fn get_all_documents(
     Ok(HttpResponse::Ok().json(ret))
 }
 
+/// The number of documents sent in each chunk of a documents fetch.
+const FETCH_CHUNK_SIZE: usize = 1000;
+/// The number of chunks read in advance while the previous ones are sent.
+const FETCH_BUFFERED_CHUNKS: usize = 4;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FetchDocumentsQuery {
+    filter: Option<String>,
+    fields: Option<CS<StarOr<String>>>,
+}
+
+/// Streams all the documents matching the filter as NDJSON. Unlike the paginated listing, all
+/// the documents are read from the same snapshot of the index, however long the download takes.
+///
+/// This route shadows `GET /indexes/{uid}/documents/fetch`, a document whose id is `fetch` can
+/// only be read through the paginated listing.
+pub async fn fetch_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: web::Query<FetchDocumentsQuery>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", params);
+    let FetchDocumentsQuery { filter, fields } = params.into_inner();
+    let attributes_to_retrieve = fields.and_then(fold_star_or);
+    let filter = fuse_filters(
+        filter.map(Value::String),
+        search_rules_filter(index_scheduler.filters(), &index_uid),
+    );
+
+    let index = index_scheduler.index(&index_uid)?;
+    let (sender, mut receiver) = mpsc::channel(FETCH_BUFFERED_CHUNKS);
+    tokio::task::spawn_blocking(move || {
+        send_documents(&index, filter.as_ref(), attributes_to_retrieve, sender)
+    });
+
+    // The errors preventing the fetch, like an invalid filter, are sent before any document.
+    let first_chunk = receiver.recv().await.transpose()?;
+    let chunks = stream::iter(first_chunk.map(Ok)).chain(ReceiverStream::new(receiver));
+
+    Ok(HttpResponse::Ok().content_type("application/x-ndjson").streaming(chunks))
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct UpdateDocumentsQuery {
     pub primary_key: Option<String>,
+    /// The character separating the fields of a CSV payload, a comma by default.
+    pub csv_delimiter: Option<String>,
 }
 
 pub async fn add_documents(
@@ -161,6 +242,7 @@ pub async fn add_documents(
         index_scheduler,
         index_uid.into_inner(),
         params.primary_key,
+        params.csv_delimiter,
         body,
         IndexDocumentsMethod::ReplaceDocuments,
         allow_index_creation,
@@ -183,12 +265,14 @@ pub async fn update_documents(
 
     analytics.update_documents(&params, index_scheduler.index(&index_uid).is_err(), &req);
 
+    let UpdateDocumentsQuery { primary_key, csv_delimiter } = params.into_inner();
     let allow_index_creation = index_scheduler.filters().allow_index_creation;
     let task = document_addition(
         extract_mime_type(&req)?,
         index_scheduler,
         index_uid,
-        params.into_inner().primary_key,
+        primary_key,
+        csv_delimiter,
         body,
         IndexDocumentsMethod::UpdateDocuments,
         allow_index_creation,
@@ -198,11 +282,26 @@ pub async fn update_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+/// The number of chunks of a JSON Lines payload received but not yet converted.
+const NDJSON_CHUNKS_IN_FLIGHT: usize = 16;
+
+/// Returns the byte separating the fields of a CSV payload.
+fn parse_csv_delimiter(delimiter: &str) -> Result<u8, MeilisearchHttpError> {
+    match delimiter.as_bytes() {
+        [b'"' | b'\n' | b'\r'] => {
+            Err(MeilisearchHttpError::InvalidCsvDelimiter(delimiter.to_string()))
+        }
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(MeilisearchHttpError::InvalidCsvDelimiter(delimiter.to_string())),
+    }
+}
+
 async fn document_addition(
     mime_type: Option<Mime>,
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
     index_uid: String,
     primary_key: Option<String>,
+    csv_delimiter: Option<String>,
     mut body: Payload,
     method: IndexDocumentsMethod,
     allow_index_creation: bool,
@@ -211,6 +310,7 @@ async fn document_addition(
         Some(("application", "json")) => PayloadType::Json,
         Some(("application", "x-ndjson")) => PayloadType::Ndjson,
         Some(("text", "csv")) => PayloadType::Csv,
+        Some(("application", "msgpack" | "x-msgpack")) => PayloadType::MessagePack,
         Some((type_, subtype)) => {
             return Err(MeilisearchHttpError::InvalidContentType(
                 format!("{}/{}", type_, subtype),
@@ -222,40 +322,83 @@ async fn document_addition(
         }
     };
 
+    let csv_delimiter = match (csv_delimiter, &format) {
+        (None, _) => b',',
+        (Some(delimiter), PayloadType::Csv) => parse_csv_delimiter(&delimiter)?,
+        (Some(_), _) => {
+            let mime_type = mime_type.map_or_else(String::new, |m| m.essence_str().to_string());
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(mime_type));
+        }
+    };
+
     // is your indexUid valid?
     let index_uid = IndexUid::try_from(index_uid)?.into_inner();
 
     let (uuid, mut update_file) = index_scheduler.create_update_file()?;
 
-    // TODO: this can be slow, maybe we should spawn a thread? But the payload isn't Send+Sync :weary:
-    // push the entire stream into a `Vec`.
-    // If someone sends us a never ending stream we're going to block the thread.
-    // TODO: Maybe we should write it to a file to reduce the RAM consumption
-    // and then reread it to convert it to obkv?
-    let mut buffer = Vec::new();
-    while let Some(bytes) = body.next().await {
-        buffer.extend_from_slice(&bytes?);
-    }
-    if buffer.is_empty() {
-        return Err(MeilisearchHttpError::MissingPayload(format));
-    }
-    let reader = Cursor::new(buffer);
+    let documents_count = if let PayloadType::Ndjson = format {
+        // The JSON Lines are converted as they are received, so we never hold the entire
+        // payload in memory and a malformed line only rejects its own document. The conversion
+        // writes to the update file and thus runs on a blocking thread fed with the chunks.
+        let (sender, mut receiver) = mpsc::channel(NDJSON_CHUNKS_IN_FLIGHT);
+        let conversion = tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
+            let mut reader = NdjsonStreamReader::new(update_file.as_file_mut());
+            let mut is_empty = true;
+            while let Some(bytes) = receiver.blocking_recv() {
+                // A payload error must be returned before the update file is persisted.
+                let bytes: Bytes = bytes?;
+                is_empty &= bytes.is_empty();
+                reader.push_chunk(&bytes)?;
+            }
+            if is_empty {
+                return Err(MeilisearchHttpError::MissingPayload(PayloadType::Ndjson));
+            }
+            let (documents_count, rejected_documents, rejected_count) = reader.finish()?;
+            update_file.persist()?;
+            Ok((documents_count as u64, rejected_documents, rejected_count as u64))
+        });
+
+        while let Some(bytes) = body.next().await {
+            let is_err = bytes.is_err();
+            // The conversion stopped on an error, it is returned once the task is joined.
+            if sender.send(bytes).await.is_err() || is_err {
+                break;
+            }
+        }
+        drop(sender);
+
+        conversion.await
+    } else {
+        // TODO: this can be slow, maybe we should spawn a thread? But the payload isn't Send+Sync :weary:
+        // push the entire stream into a `Vec`.
+        // If someone sends us a never ending stream we're going to block the thread.
+        // TODO: Maybe we should write it to a file to reduce the RAM consumption
+        // and then reread it to convert it to obkv?
+        let mut buffer = Vec::new();
+        while let Some(bytes) = body.next().await {
+            buffer.extend_from_slice(&bytes?);
+        }
+        if buffer.is_empty() {
+            return Err(MeilisearchHttpError::MissingPayload(format));
+        }
+        let reader = Cursor::new(buffer);
 
-    let documents_count =
         tokio::task::spawn_blocking(move || -> Result<_, MeilisearchHttpError> {
             let documents_count = match format {
                 PayloadType::Json => read_json(reader, update_file.as_file_mut())?,
-                PayloadType::Csv => read_csv(reader, update_file.as_file_mut())?,
-                PayloadType::Ndjson => read_ndjson(reader, update_file.as_file_mut())?,
+                PayloadType::Csv => read_csv(reader, update_file.as_file_mut(), csv_delimiter)?,
+                PayloadType::MessagePack => read_msgpack(reader, update_file.as_file_mut())?,
+                PayloadType::Ndjson => unreachable!(),
             };
             // we NEED to persist the file here because we moved the `udpate_file` in another task.
             update_file.persist()?;
-            Ok(documents_count)
+            Ok((documents_count as u64, Vec::new(), 0))
         })
-        .await;
+        .await
+    };
 
-    let documents_count = match documents_count {
-        Ok(Ok(documents_count)) => documents_count as u64,
+    let (documents_count, rejected_documents, rejected_documents_count) = match documents_count {
+        Ok(Ok(documents_count)) => documents_count,
         // in this case the file has not possibly be persisted.
         Ok(Err(e)) => return Err(e),
         Err(e) => {
@@ -283,8 +426,19 @@ async fn document_addition(
         index_uid,
     };
 
+    let details = Details::DocumentAdditionOrUpdate {
+        received_documents: documents_count,
+        indexed_documents: None,
+        rejected_documents,
+        rejected_documents_count,
+    };
+
     let scheduler = index_scheduler.clone();
-    let task = match tokio::task::spawn_blocking(move || scheduler.register(task)).await? {
+    let task = match tokio::task::spawn_blocking(move || {
+        scheduler.register_with_details(task, Some(details))
+    })
+    .await?
+    {
         Ok(task) => task,
         Err(e) => {
             index_scheduler.delete_update_file(uuid)?;
@@ -321,6 +475,78 @@ pub async fn delete_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DocumentEditionQuery {
+    filter: Value,
+    #[serde(default)]
+    set: Map<String, Value>,
+    #[serde(default)]
+    unset: Vec<String>,
+    #[serde(default)]
+    increment: BTreeMap<String, Number>,
+}
+
+pub async fn edit_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    path: web::Path<String>,
+    body: web::Json<DocumentEditionQuery>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+
+    // is your indexUid valid?
+    let index_uid = IndexUid::try_from(path.into_inner())?.into_inner();
+
+    let DocumentEditionQuery { filter, set, unset, increment } = body.into_inner();
+    let edition = DocumentEdition { set, unset, increment };
+
+    analytics.publish(
+        "Documents Edited".to_string(),
+        json!({
+            "set": edition.set.len(),
+            "unset": edition.unset.len(),
+            "increment": edition.increment.len(),
+        }),
+        Some(&req),
+    );
+
+    if parse_filter(&filter)?.is_none() {
+        return Err(MeilisearchHttpError::MissingDocumentEditionFilter.into());
+    }
+    if edition.is_empty() {
+        return Err(MeilisearchHttpError::EmptyDocumentEdition.into());
+    }
+    let mut fields = HashSet::new();
+    if let Some(field) = edition.fields().find(|field| !fields.insert(*field)) {
+        return Err(MeilisearchHttpError::DocumentEditionConflictingField(field.to_string()).into());
+    }
+
+    let task = KindWithContent::DocumentEdition {
+        index_uid,
+        filter_expr: filter_expression(&filter, " AND "),
+        edition,
+    };
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+/// Joins the filter given as an array, like in a search query, into a single expression.
+fn filter_expression(filter: &Value, separator: &str) -> String {
+    match filter {
+        Value::Array(filters) => filters
+            .iter()
+            .map(|filter| format!("({})", filter_expression(filter, " OR ")))
+            .collect::<Vec<_>>()
+            .join(separator),
+        filter => filter.as_str().unwrap_or_default().to_string(),
+    }
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     path: web::Path<String>,
@@ -337,47 +563,135 @@ pub async fn clear_all_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
-fn all_documents<'a>(
+/// Returns the filter of the API key or tenant token of the request on the index, the documents
+/// it doesn't match can't be read with them.
+fn search_rules_filter(filters: &AuthFilter, index_uid: &str) -> Option<Value> {
+    filters.search_rules.get_index_search_rules(index_uid).and_then(|rules| rules.filter)
+}
+
+/// Sends the documents matching the filter to the channel by chunks of NDJSON, until they are
+/// all sent or the receiver is dropped.
+fn send_documents(
+    index: &Index,
+    filter: Option<&Value>,
+    attributes_to_retrieve: Option<Vec<String>>,
+    sender: mpsc::Sender<Result<Bytes, ResponseError>>,
+) {
+    let rtxn = match index.read_txn() {
+        Ok(rtxn) => rtxn,
+        Err(e) => {
+            let _ = sender.blocking_send(Err(e.into()));
+            return;
+        }
+    };
+
+    let chunks = filtered_documents_ids(index, &rtxn, filter)
+        .and_then(|ids| documents_chunks(index, &rtxn, ids, attributes_to_retrieve));
+    match chunks {
+        Ok(chunks) => {
+            for chunk in chunks {
+                let is_err = chunk.is_err();
+                // The receiver is dropped when the client closes the connection.
+                if sender.blocking_send(chunk).is_err() || is_err {
+                    break;
+                }
+            }
+        }
+        Err(e) => {
+            let _ = sender.blocking_send(Err(e));
+        }
+    }
+}
+
+/// Returns the ids of the documents matching the filter, or of all the documents without one.
+fn filtered_documents_ids(
     index: &Index,
+    rtxn: &RoTxn,
+    filter: Option<&Value>,
+) -> Result<RoaringBitmap, ResponseError> {
+    match filter.map(parse_filter).transpose()?.flatten() {
+        Some(filter) => Ok(filter.evaluate(rtxn, index)?),
+        None => Ok(index.documents_ids(rtxn)?),
+    }
+}
+
+/// Iterates over the documents with the given ids, by chunks of `FETCH_CHUNK_SIZE` documents
+/// serialized as NDJSON. The ids already returned are removed from the bitmap, which acts as
+/// a cursor over the remaining documents.
+fn documents_chunks<'a>(
+    index: &'a Index,
     rtxn: &'a RoTxn,
-) -> Result<impl Iterator<Item = Result<Document, ResponseError>> + 'a, ResponseError> {
+    mut ids: RoaringBitmap,
+    attributes_to_retrieve: Option<Vec<String>>,
+) -> Result<impl Iterator<Item = Result<Bytes, ResponseError>> + 'a, ResponseError> {
     let fields_ids_map = index.fields_ids_map(rtxn)?;
     let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
 
-    Ok(index.all_documents(rtxn)?.map(move |ret| {
-        ret.map_err(ResponseError::from).and_then(|(_key, document)| -> Result<_, ResponseError> {
-            Ok(milli::obkv_to_json(&all_fields, &fields_ids_map, document)?)
-        })
+    Ok(std::iter::from_fn(move || {
+        let chunk: Vec<_> = ids.iter().take(FETCH_CHUNK_SIZE).collect();
+        let last = *chunk.last()?;
+        ids.remove_range(..=last);
+
+        let documents = match index.documents(rtxn, chunk) {
+            Ok(documents) => documents,
+            Err(e) => return Some(Err(e.into())),
+        };
+        let mut bytes = Vec::new();
+        for (_id, document) in documents {
+            let document = match milli::obkv_to_json(&all_fields, &fields_ids_map, document) {
+                Ok(document) => document,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let document = match &attributes_to_retrieve {
+                Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
+                    &document,
+                    attributes_to_retrieve.iter().map(|s| s.as_ref()),
+                ),
+                None => document,
+            };
+            // A document is always serializable.
+            serde_json::to_writer(&mut bytes, &document).unwrap();
+            bytes.push(b'\n');
+        }
+        Some(Ok(Bytes::from(bytes)))
     }))
 }
 
 fn retrieve_documents<S: AsRef<str>>(
     index: &Index,
+    rules_filter: Option<&Value>,
     offset: usize,
     limit: usize,
     attributes_to_retrieve: Option<Vec<S>>,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
 
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+
+    let ids = filtered_documents_ids(index, &rtxn, rules_filter)?;
+    let page = ids.iter().skip(offset).take(limit);
+
     let mut documents = Vec::new();
-    for document in all_documents(index, &rtxn)?.skip(offset).take(limit) {
+    for (_id, document) in index.documents(&rtxn, page)? {
+        let document = milli::obkv_to_json(&all_fields, &fields_ids_map, document)?;
         let document = match &attributes_to_retrieve {
             Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
-                &document?,
+                &document,
                 attributes_to_retrieve.iter().map(|s| s.as_ref()),
             ),
-            None => document?,
+            None => document,
         };
         documents.push(document);
     }
 
-    let number_of_documents = index.number_of_documents(&rtxn)?;
-    Ok((number_of_documents, documents))
+    Ok((ids.len(), documents))
 }
 
 fn retrieve_document<S: AsRef<str>>(
     index: &Index,
     doc_id: &str,
+    rules_filter: Option<&Value>,
     attributes_to_retrieve: Option<Vec<S>>,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
@@ -390,6 +704,13 @@ fn retrieve_document<S: AsRef<str>>(
         .get(doc_id.as_bytes())
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))?;
 
+    // The documents not matched by the filter of the API key or tenant token are hidden.
+    if rules_filter.is_some()
+        && !filtered_documents_ids(index, &txn, rules_filter)?.contains(internal_id)
+    {
+        return Err(MeilisearchHttpError::DocumentNotFound(doc_id.to_string()).into());
+    }
+
     let document = index
         .documents(&txn, std::iter::once(internal_id))?
         .into_iter()
@@ -0,0 +1,42 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::error::ResponseError;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::fuse_filters;
+use crate::search::{perform_facet_search, FacetSearchQuery};
+use crate::search_pool::SearchPool;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(search))));
+}
+
+pub async fn search(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: web::Json<FacetSearchQuery>,
+    search_pool: web::Data<SearchPool>,
+) -> Result<HttpResponse, ResponseError> {
+    let deadline = search_pool.deadline();
+    let mut query = params.into_inner();
+    debug!("facet search called with params: {:?}", query);
+
+    // Tenant token and API key search_rules.
+    if let Some(search_rules) =
+        index_scheduler.filters().search_rules.get_index_search_rules(&index_uid)
+    {
+        query.filter = fuse_filters(query.filter.take(), search_rules.filter);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let search_result = search_pool
+        .run(deadline, move |deadline| perform_facet_search(&index, query, deadline))
+        .await?;
+
+    debug!("returns: {:?}", search_result);
+    Ok(HttpResponse::Ok().json(search_result))
+}
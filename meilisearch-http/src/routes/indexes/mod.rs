@@ -1,24 +1,28 @@
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
-use index_scheduler::IndexScheduler;
+use index_scheduler::{IndexDiskUsage, IndexScheduler};
 use log::debug;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::Setting;
 use meilisearch_types::milli::{self, FieldDistribution, Index};
 use meilisearch_types::tasks::KindWithContent;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use time::OffsetDateTime;
 
-use super::{Pagination, SummarizedTaskView};
+use super::{Pagination, StatsQuery, SummarizedTaskView};
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
 
+pub mod bulk_settings;
 pub mod documents;
+pub mod facet_search;
 pub mod search;
 pub mod settings;
+pub mod similar;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -37,7 +41,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
-            .service(web::scope("/settings").configure(settings::configure)),
+            .service(web::scope("/facet-search").configure(facet_search::configure))
+            .service(web::scope("/similar").configure(similar::configure))
+            .service(
+                web::scope("/settings")
+                    .configure(settings::configure)
+                    .configure(bulk_settings::configure),
+            ),
     );
 }
 
@@ -87,6 +97,7 @@ pub async fn list_indexes(
 pub struct IndexCreateRequest {
     uid: String,
     primary_key: Option<String>,
+    max_size: Option<u64>,
 }
 
 pub async fn create_index(
@@ -95,18 +106,19 @@ pub async fn create_index(
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    let IndexCreateRequest { primary_key, uid } = body.into_inner();
+    let IndexCreateRequest { primary_key, uid, max_size } = body.into_inner();
     let uid = IndexUid::try_from(uid)?.into_inner();
 
     let allow_index_creation = index_scheduler.filters().search_rules.is_index_authorized(&uid);
     if allow_index_creation {
         analytics.publish(
             "Index Created".to_string(),
-            json!({ "primary_key": primary_key }),
+            json!({ "primary_key": primary_key, "max_size": max_size }),
             Some(&req),
         );
 
-        let task = KindWithContent::IndexCreation { index_uid: uid, primary_key };
+        let max_size = max_size.map_or(Setting::NotSet, Setting::Set);
+        let task = KindWithContent::IndexCreation { index_uid: uid, primary_key, max_size };
         let task: SummarizedTaskView =
             tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
 
@@ -122,6 +134,8 @@ pub async fn create_index(
 pub struct UpdateIndexRequest {
     uid: Option<String>,
     primary_key: Option<String>,
+    #[serde(default)]
+    max_size: Setting<u64>,
 }
 
 pub async fn get_index(
@@ -147,13 +161,14 @@ pub async fn update_index(
     let body = body.into_inner();
     analytics.publish(
         "Index Updated".to_string(),
-        json!({ "primary_key": body.primary_key}),
+        json!({ "primary_key": body.primary_key, "max_size": body.max_size.set() }),
         Some(&req),
     );
 
     let task = KindWithContent::IndexUpdate {
         index_uid: path.into_inner(),
         primary_key: body.primary_key,
+        max_size: body.max_size,
     };
 
     let task: SummarizedTaskView =
@@ -177,12 +192,17 @@ pub async fn delete_index(
 pub async fn get_index_stats(
     index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
+    params: web::Query<StatsQuery>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     analytics.publish("Stats Seen".to_string(), json!({ "per_index_uid": true }), Some(&req));
+    let with_fragmentation = params.fragmentation;
 
-    let stats = IndexStats::new((*index_scheduler).clone(), index_uid.into_inner())?;
+    let stats = tokio::task::spawn_blocking(move || {
+        IndexStats::new((*index_scheduler).clone(), index_uid.into_inner(), with_fragmentation)
+    })
+    .await??;
 
     debug!("returns: {:?}", stats);
     Ok(HttpResponse::Ok().json(stats))
@@ -194,12 +214,32 @@ pub struct IndexStats {
     pub number_of_documents: u64,
     pub is_indexing: bool,
     pub field_distribution: FieldDistribution,
+    pub disk_usage: DiskUsageView,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageView {
+    pub on_disk_size: u64,
+    pub map_size: u64,
+    pub max_size: Option<u64>,
+    pub map_usage: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fragmentation: Option<f64>,
+}
+
+impl From<IndexDiskUsage> for DiskUsageView {
+    fn from(usage: IndexDiskUsage) -> Self {
+        let IndexDiskUsage { on_disk_size, map_size, max_size, map_usage, fragmentation } = usage;
+        DiskUsageView { on_disk_size, map_size, max_size, map_usage, fragmentation }
+    }
 }
 
 impl IndexStats {
     pub fn new(
         index_scheduler: Data<IndexScheduler>,
         index_uid: String,
+        with_fragmentation: bool,
     ) -> Result<Self, ResponseError> {
         // we check if there is currently a task processing associated with this index.
         let is_processing = index_scheduler.is_index_processing(&index_uid)?;
@@ -209,6 +249,7 @@ impl IndexStats {
             number_of_documents: index.number_of_documents(&rtxn)?,
             is_indexing: is_processing,
             field_distribution: index.field_distribution(&rtxn)?,
+            disk_usage: index_scheduler.index_disk_usage(&index_uid, with_fragmentation)?.into(),
         })
     }
 }
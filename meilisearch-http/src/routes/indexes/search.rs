@@ -17,6 +17,7 @@ use crate::search::{
     DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
     DEFAULT_SEARCH_OFFSET,
 };
+use crate::search_pool::SearchPool;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -89,8 +90,13 @@ impl From<SearchQueryGet> for SearchQuery {
 }
 
 /// Incorporate search rules in search query
-fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
-    query.filter = match (query.filter.take(), rules.filter) {
+pub fn add_search_rules(query: &mut SearchQuery, rules: IndexSearchRules) {
+    query.filter = fuse_filters(query.filter.take(), rules.filter);
+}
+
+/// Return a filter matching the documents matched by both filters.
+pub fn fuse_filters(filter: Option<Value>, rules_filter: Option<Value>) -> Option<Value> {
+    match (filter, rules_filter) {
         (None, rules_filter) => rules_filter,
         (filter, None) => filter,
         (Some(filter), Some(rules_filter)) => {
@@ -139,11 +145,13 @@ pub async fn search_with_url_query(
     params: web::Query<SearchQueryGet>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
+    search_pool: web::Data<SearchPool>,
 ) -> Result<HttpResponse, ResponseError> {
+    let deadline = search_pool.deadline();
     debug!("called with params: {:?}", params);
     let mut query: SearchQuery = params.into_inner().into();
 
-    // Tenant token search_rules.
+    // Tenant token and API key search_rules.
     if let Some(search_rules) =
         index_scheduler.filters().search_rules.get_index_search_rules(&index_uid)
     {
@@ -153,7 +161,8 @@ pub async fn search_with_url_query(
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
-    let search_result = tokio::task::spawn_blocking(move || perform_search(&index, query)).await?;
+    let search_result =
+        search_pool.run(deadline, move |deadline| perform_search(&index, query, deadline)).await;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
     }
@@ -171,11 +180,13 @@ pub async fn search_with_post(
     params: web::Json<SearchQuery>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
+    search_pool: web::Data<SearchPool>,
 ) -> Result<HttpResponse, ResponseError> {
+    let deadline = search_pool.deadline();
     let mut query = params.into_inner();
     debug!("search called with params: {:?}", query);
 
-    // Tenant token search_rules.
+    // Tenant token and API key search_rules.
     if let Some(search_rules) =
         index_scheduler.filters().search_rules.get_index_search_rules(&index_uid)
     {
@@ -185,7 +196,8 @@ pub async fn search_with_post(
     let mut aggregate = SearchAggregator::from_query(&query, &req);
 
     let index = index_scheduler.index(&index_uid)?;
-    let search_result = tokio::task::spawn_blocking(move || perform_search(&index, query)).await?;
+    let search_result =
+        search_pool.run(deadline, move |deadline| perform_search(&index, query, deadline)).await;
     if let Ok(ref search_result) = search_result {
         aggregate.succeed(search_result);
     }
@@ -0,0 +1,43 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::error::ResponseError;
+
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{perform_similar, SimilarQuery};
+use crate::search_pool::SearchPool;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(similar))));
+}
+
+pub async fn similar(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: web::Json<SimilarQuery>,
+    search_pool: web::Data<SearchPool>,
+) -> Result<HttpResponse, ResponseError> {
+    let deadline = search_pool.deadline();
+    let query = params.into_inner();
+    debug!("similar called with params: {:?}", query);
+
+    // Tenant token and API key search_rules.
+    let search_rules_filter = index_scheduler
+        .filters()
+        .search_rules
+        .get_index_search_rules(&index_uid)
+        .and_then(|search_rules| search_rules.filter);
+
+    let index = index_scheduler.index(&index_uid)?;
+    let similar_result = search_pool
+        .run(deadline, move |deadline| {
+            perform_similar(&index, query, search_rules_filter, deadline)
+        })
+        .await?;
+
+    debug!("returns: {:?}", similar_result);
+    Ok(HttpResponse::Ok().json(similar_result))
+}
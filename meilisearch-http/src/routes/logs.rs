@@ -0,0 +1,55 @@
+use std::convert::Infallible;
+
+use actix_web::http::header::ContentEncoding;
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse};
+use futures::StreamExt;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::error::ResponseError;
+use serde::Deserialize;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::logs::{parse_stream_level, LOG_STREAMS};
+use crate::option::LogFormat;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/stream").route(web::post().to(stream_logs)));
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LogStreamQuery {
+    level: String,
+    #[serde(default = "default_log_stream_format")]
+    format: LogFormat,
+}
+
+fn default_log_stream_format() -> LogFormat {
+    LogFormat::Json
+}
+
+pub async fn stream_logs(
+    _index_scheduler: GuardedData<ActionPolicy<{ actions::LOGS_GET }>, Data<IndexScheduler>>,
+    params: web::Json<LogStreamQuery>,
+) -> Result<HttpResponse, ResponseError> {
+    let LogStreamQuery { level, format } = params.into_inner();
+    let level =
+        parse_stream_level(&level).ok_or(MeilisearchHttpError::InvalidLogStreamLevel(level))?;
+
+    debug!("opening a {} log stream at the {} level", format, level);
+    let lines = ReceiverStream::new(LOG_STREAMS.subscribe(level, format)).map(Ok::<_, Infallible>);
+    let content_type = match format {
+        LogFormat::Human => "text/plain; charset=utf-8",
+        LogFormat::Json => "application/x-ndjson",
+    };
+
+    // The lines must be sent as soon as they are logged, they are never compressed.
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentEncoding::Identity)
+        .streaming(lines))
+}
@@ -18,20 +18,28 @@ use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 
 mod api_key;
+mod batches;
 mod dump;
 pub mod indexes;
+mod logs;
+mod multi_search;
 mod swap_indexes;
 pub mod tasks;
+mod webhooks;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(web::scope("/tasks").configure(tasks::configure))
+        .service(web::scope("/batches").configure(batches::configure))
         .service(web::resource("/health").route(web::get().to(get_health)))
         .service(web::scope("/keys").configure(api_key::configure))
+        .service(web::scope("/logs").configure(logs::configure))
         .service(web::scope("/dumps").configure(dump::configure))
         .service(web::resource("/stats").route(web::get().to(get_stats)))
         .service(web::resource("/version").route(web::get().to(get_version)))
         .service(web::scope("/indexes").configure(indexes::configure))
-        .service(web::scope("/swap-indexes").configure(swap_indexes::configure));
+        .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
+        .service(web::scope("/multi-search").configure(multi_search::configure))
+        .service(web::scope("/webhooks").configure(webhooks::configure));
 }
 
 /// Extracts the raw values from the `StarOr` types and
@@ -249,15 +257,33 @@ pub struct Stats {
     pub indexes: BTreeMap<String, IndexStats>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct StatsQuery {
+    /// Whether the fragmentation of the indexes is computed, which requires a compacted copy of
+    /// each of them to be written on disk.
+    #[serde(default)]
+    fragmentation: bool,
+}
+
 async fn get_stats(
     index_scheduler: GuardedData<ActionPolicy<{ actions::STATS_GET }>, Data<IndexScheduler>>,
+    params: web::Query<StatsQuery>,
     req: HttpRequest,
     analytics: web::Data<dyn Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
-    analytics.publish("Stats Seen".to_string(), json!({ "per_index_uid": false }), Some(&req));
-    let search_rules = &index_scheduler.filters().search_rules;
-
-    let stats = create_all_stats((*index_scheduler).clone(), search_rules)?;
+    analytics.publish(
+        "Stats Seen".to_string(),
+        json!({ "per_index_uid": false, "fragmentation": params.fragmentation }),
+        Some(&req),
+    );
+    let search_rules = index_scheduler.filters().search_rules.clone();
+    let with_fragmentation = params.fragmentation;
+
+    let stats = tokio::task::spawn_blocking(move || {
+        create_all_stats((*index_scheduler).clone(), &search_rules, with_fragmentation)
+    })
+    .await??;
 
     debug!("returns: {:?}", stats);
     Ok(HttpResponse::Ok().json(stats))
@@ -266,6 +292,7 @@ async fn get_stats(
 pub fn create_all_stats(
     index_scheduler: Data<IndexScheduler>,
     search_rules: &meilisearch_auth::SearchRules,
+    with_fragmentation: bool,
 ) -> Result<Stats, ResponseError> {
     let mut last_task: Option<OffsetDateTime> = None;
     let mut indexes = BTreeMap::new();
@@ -287,6 +314,7 @@ pub fn create_all_stats(
             number_of_documents: index.number_of_documents(&rtxn)?,
             is_indexing: processing_index.map_or(false, |index_name| name == index_name),
             field_distribution: index.field_distribution(&rtxn)?,
+            disk_usage: index_scheduler.index_disk_usage(&name, with_fragmentation)?.into(),
         };
 
         let updated_at = index.updated_at(&rtxn)?;
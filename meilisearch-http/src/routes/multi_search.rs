@@ -0,0 +1,106 @@
+use std::time::Instant;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use futures::future::join_all;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::error::ResponseError;
+use serde::{Deserialize, Serialize};
+
+use crate::analytics::{Analytics, SearchAggregator};
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::add_search_rules;
+use crate::search::{
+    perform_search, SearchQuery, SearchQueryWithIndex, SearchResult, SearchResultWithIndex,
+};
+use crate::search_pool::SearchPool;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(multi_search_with_post))));
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SearchQueries {
+    queries: Vec<SearchQueryWithIndex>,
+}
+
+#[derive(Serialize, Debug)]
+struct SearchResults {
+    results: Vec<SearchResultWithIndex>,
+}
+
+pub async fn multi_search_with_post(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    params: web::Json<SearchQueries>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+    search_pool: web::Data<SearchPool>,
+) -> Result<HttpResponse, ResponseError> {
+    // The time budget is shared by all the queries of the request.
+    let deadline = search_pool.deadline();
+    let queries = params.into_inner().queries;
+    debug!("multi-search called with params: {:?}", queries);
+
+    // All the queries are executed concurrently on the search pool.
+    let searches = queries.into_iter().map(|query| {
+        let (index_uid, query) = query.into_index_query();
+        let mut aggregate = SearchAggregator::from_query(&query, &req);
+        let search =
+            search_on_index(&index_scheduler, &search_pool, deadline, index_uid.clone(), query);
+        async move {
+            let search_result = search.await;
+            if let Ok(ref search_result) = search_result {
+                aggregate.succeed(search_result);
+            }
+            (index_uid, search_result, aggregate)
+        }
+    });
+
+    let mut results = Vec::new();
+    for (index_uid, search_result, aggregate) in join_all(searches).await {
+        analytics.post_search(aggregate);
+        results.push(match search_result {
+            Ok(result) => SearchResultWithIndex::Success { index_uid, result },
+            Err(error) => SearchResultWithIndex::Failure { index_uid, error },
+        });
+    }
+
+    let multi_search_result = SearchResults { results };
+
+    debug!("returns: {:?}", multi_search_result);
+    Ok(HttpResponse::Ok().json(multi_search_result))
+}
+
+/// Execute a single query of a multi-search.
+///
+/// Everything that can go wrong, from the authorization to the search itself,
+/// is returned as an error so that it only affects this query.
+async fn search_on_index(
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_pool: &SearchPool,
+    deadline: Option<Instant>,
+    index_uid: String,
+    mut query: SearchQuery,
+) -> Result<SearchResult, ResponseError> {
+    // The authentication was done for any index, we must ensure
+    // that the key or tenant token can reach this specific index.
+    let search_rules = &index_scheduler.filters().search_rules;
+    if !search_rules.is_index_authorized(&index_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    // Tenant token and API key search_rules.
+    if let Some(search_rules) = search_rules.get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let search_result =
+        search_pool.run(deadline, move |deadline| perform_search(&index, query, deadline)).await?;
+
+    Ok(search_result)
+}
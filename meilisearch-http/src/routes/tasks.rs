@@ -3,8 +3,11 @@ use std::str::FromStr;
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use index_scheduler::{IndexScheduler, Query, TaskId};
+use meilisearch_types::batches::BatchId;
+use meilisearch_types::document_formats::RejectedDocument;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::Setting;
 use meilisearch_types::settings::{Settings, Unchecked};
 use meilisearch_types::star_or::StarOr;
 use meilisearch_types::tasks::{
@@ -46,6 +49,8 @@ pub struct TaskView {
     pub kind: Kind,
     pub canceled_by: Option<TaskId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_uid: Option<BatchId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<DetailsView>,
     pub error: Option<ResponseError>,
     #[serde(serialize_with = "serialize_duration", default)]
@@ -66,6 +71,7 @@ impl TaskView {
             status: task.status,
             kind: task.kind.as_kind(),
             canceled_by: task.canceled_by,
+            batch_uid: task.batch_uid,
             details: task.details.clone().map(DetailsView::from),
             error: task.error.clone(),
             duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
@@ -84,12 +90,20 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_documents: Option<Vec<RejectedDocument>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejected_documents_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub provided_ids: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_tasks: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_tasks: Option<Option<u64>>,
@@ -100,6 +114,12 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dump_uid: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_indexes: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_entries: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_entries: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(flatten)]
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -109,19 +129,35 @@ pub struct DetailsView {
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                rejected_documents,
+                rejected_documents_count,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                rejected_documents: Some(rejected_documents).filter(|r| !r.is_empty()),
+                rejected_documents_count: Some(rejected_documents_count).filter(|c| *c > 0),
+                ..DetailsView::default()
+            },
             Details::SettingsUpdate { settings } => {
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
             }
-            Details::IndexInfo { primary_key } => {
-                DetailsView { primary_key: Some(primary_key), ..DetailsView::default() }
-            }
+            Details::SettingsImport { added_entries, removed_entries } => DetailsView {
+                added_entries: Some(added_entries),
+                removed_entries: Some(removed_entries),
+                ..DetailsView::default()
+            },
+            Details::IndexInfo { primary_key, max_size } => DetailsView {
+                primary_key: Some(primary_key),
+                max_size: match max_size {
+                    Setting::Set(max_size) => Some(Some(max_size)),
+                    Setting::Reset => Some(None),
+                    Setting::NotSet => None,
+                },
+                ..DetailsView::default()
+            },
             Details::DocumentDeletion {
                 provided_ids: received_document_ids,
                 deleted_documents,
@@ -133,6 +169,11 @@ impl From<Details> for DetailsView {
             Details::ClearAll { deleted_documents } => {
                 DetailsView { deleted_documents: Some(deleted_documents), ..DetailsView::default() }
             }
+            Details::DocumentEdition { edited_documents, original_filter } => DetailsView {
+                edited_documents: Some(edited_documents),
+                original_filter: Some(original_filter),
+                ..DetailsView::default()
+            },
             Details::TaskCancelation { matched_tasks, canceled_tasks, original_filter } => {
                 DetailsView {
                     matched_tasks: Some(matched_tasks),
@@ -155,6 +196,9 @@ impl From<Details> for DetailsView {
             Details::IndexSwap { swaps } => {
                 DetailsView { swaps: Some(swaps), ..Default::default() }
             }
+            Details::DumpImport { imported_indexes } => {
+                DetailsView { imported_indexes: Some(imported_indexes), ..DetailsView::default() }
+            }
         }
     }
 }
@@ -933,7 +977,7 @@ mod tests {
                 .unwrap()
                 .validate()
                 .unwrap_err();
-            snapshot!(format!("{err}"), @"Task type `createIndex` is invalid. Available task types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`");
+            snapshot!(format!("{err}"), @"Task type `createIndex` is invalid. Available task types are `documentAdditionOrUpdate`, `documentDeletion`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`, `documentEdition`, `dumpImport`, `settingsImport`");
         }
     }
     #[test]
@@ -0,0 +1,91 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use log::debug;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::milli::update::Setting;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::analytics::Analytics;
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::webhook::{parse_webhook_url, WebhookConfig, Webhooks};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(get_webhooks))
+            .route(web::patch().to(update_webhooks)),
+    );
+}
+
+/// The webhooks the finished tasks are sent to. The secret signing the requests is never
+/// returned, only whether there is one.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhooksView {
+    urls: Vec<String>,
+    has_secret: bool,
+}
+
+impl From<WebhookConfig> for WebhooksView {
+    fn from(config: WebhookConfig) -> Self {
+        WebhooksView {
+            urls: config.urls.into_iter().map(String::from).collect(),
+            has_secret: config.secret.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct UpdateWebhooks {
+    urls: Option<Vec<String>>,
+    #[serde(default)]
+    secret: Setting<String>,
+}
+
+pub async fn get_webhooks(
+    webhooks: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<Webhooks>>,
+) -> Result<HttpResponse, ResponseError> {
+    let view = WebhooksView::from(webhooks.config());
+
+    debug!("returns: {:?}", view);
+    Ok(HttpResponse::Ok().json(view))
+}
+
+pub async fn update_webhooks(
+    webhooks: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<Webhooks>>,
+    body: web::Json<UpdateWebhooks>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let UpdateWebhooks { urls, secret } = body.into_inner();
+
+    let mut config = webhooks.config();
+    if let Some(urls) = urls {
+        config.urls = urls
+            .into_iter()
+            .map(|url| parse_webhook_url(&url).ok_or(MeilisearchHttpError::InvalidWebhookUrl(url)))
+            .collect::<Result<_, _>>()?;
+    }
+    match secret {
+        Setting::Set(secret) => config.secret = Some(secret),
+        Setting::Reset => config.secret = None,
+        Setting::NotSet => (),
+    }
+
+    analytics.publish(
+        "Webhooks Updated".to_string(),
+        json!({ "urls": config.urls.len(), "secret": config.secret.is_some() }),
+        Some(&req),
+    );
+
+    webhooks.set_config(config.clone());
+    let view = WebhooksView::from(config);
+
+    debug!("returns: {:?}", view);
+    Ok(HttpResponse::Ok().json(view))
+}
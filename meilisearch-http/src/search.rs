@@ -1,21 +1,23 @@
-use std::cmp::min;
-use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::cmp::{min, Ordering};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::str::FromStr;
 use std::time::Instant;
 
 use either::Either;
+use meilisearch_types::error::ResponseError;
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::tokenizer::{Tokenizer, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, DocumentId, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds,
+    MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::error::MeilisearchHttpError;
+use crate::search_pool::Deadline;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
@@ -26,6 +28,10 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 
+/// The maximum number of words of a document, the most distinctive ones, used to find the
+/// documents similar to it.
+const MAX_SIMILAR_WORDS: usize = 25;
+
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct SearchQuery {
@@ -63,6 +69,88 @@ impl SearchQuery {
     }
 }
 
+/// A `SearchQuery` of a multi-search, along with the index it must be executed on.
+// This struct can't be a flattened `SearchQuery` as `deny_unknown_fields` is not
+// supported by serde when flattening a struct.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SearchQueryWithIndex {
+    pub index_uid: String,
+    pub q: Option<String>,
+    #[serde(default = "DEFAULT_SEARCH_OFFSET")]
+    pub offset: usize,
+    #[serde(default = "DEFAULT_SEARCH_LIMIT")]
+    pub limit: usize,
+    pub page: Option<usize>,
+    pub hits_per_page: Option<usize>,
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+    pub attributes_to_crop: Option<Vec<String>>,
+    #[serde(default = "DEFAULT_CROP_LENGTH")]
+    pub crop_length: usize,
+    pub attributes_to_highlight: Option<HashSet<String>>,
+    // Default to false
+    #[serde(default = "Default::default")]
+    pub show_matches_position: bool,
+    pub filter: Option<Value>,
+    pub sort: Option<Vec<String>>,
+    pub facets: Option<Vec<String>>,
+    #[serde(default = "DEFAULT_HIGHLIGHT_PRE_TAG")]
+    pub highlight_pre_tag: String,
+    #[serde(default = "DEFAULT_HIGHLIGHT_POST_TAG")]
+    pub highlight_post_tag: String,
+    #[serde(default = "DEFAULT_CROP_MARKER")]
+    pub crop_marker: String,
+    #[serde(default)]
+    pub matching_strategy: MatchingStrategy,
+}
+
+impl SearchQueryWithIndex {
+    pub fn into_index_query(self) -> (String, SearchQuery) {
+        let SearchQueryWithIndex {
+            index_uid,
+            q,
+            offset,
+            limit,
+            page,
+            hits_per_page,
+            attributes_to_retrieve,
+            attributes_to_crop,
+            crop_length,
+            attributes_to_highlight,
+            show_matches_position,
+            filter,
+            sort,
+            facets,
+            highlight_pre_tag,
+            highlight_post_tag,
+            crop_marker,
+            matching_strategy,
+        } = self;
+        (
+            index_uid,
+            SearchQuery {
+                q,
+                offset,
+                limit,
+                page,
+                hits_per_page,
+                attributes_to_retrieve,
+                attributes_to_crop,
+                crop_length,
+                attributes_to_highlight,
+                show_matches_position,
+                filter,
+                sort,
+                facets,
+                highlight_pre_tag,
+                highlight_post_tag,
+                crop_marker,
+                matching_strategy,
+            },
+        )
+    }
+}
+
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum MatchingStrategy {
@@ -107,6 +195,74 @@ pub struct SearchResult {
     pub hits_info: HitsInfo,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_distribution: Option<BTreeMap<String, BTreeMap<String, u64>>>,
+    /// Whether the time budget of the search was exceeded, in which case the hits are returned
+    /// without being formatted and the facet distribution isn't computed.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+}
+
+/// The outcome of one of the queries of a multi-search.
+///
+/// A failing query doesn't make the whole multi-search fail, its error is
+/// returned in place of its results instead.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum SearchResultWithIndex {
+    #[serde(rename_all = "camelCase")]
+    Success {
+        index_uid: String,
+        #[serde(flatten)]
+        result: SearchResult,
+    },
+    #[serde(rename_all = "camelCase")]
+    Failure { index_uid: String, error: ResponseError },
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FacetSearchQuery {
+    pub facet_name: String,
+    pub facet_query: Option<String>,
+    pub q: Option<String>,
+    pub filter: Option<Value>,
+    #[serde(default)]
+    pub matching_strategy: MatchingStrategy,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetSearchResult {
+    pub facet_hits: Vec<FacetValueHit>,
+    pub facet_query: Option<String>,
+    pub processing_time_ms: u128,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FacetValueHit {
+    pub value: String,
+    pub count: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SimilarQuery {
+    pub id: Value,
+    #[serde(default = "DEFAULT_SEARCH_OFFSET")]
+    pub offset: usize,
+    #[serde(default = "DEFAULT_SEARCH_LIMIT")]
+    pub limit: usize,
+    pub filter: Option<Value>,
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimilarResult {
+    pub hits: Vec<Document>,
+    pub id: String,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
@@ -121,6 +277,7 @@ pub enum HitsInfo {
 pub fn perform_search(
     index: &Index,
     query: SearchQuery,
+    deadline: &Deadline,
 ) -> Result<SearchResult, MeilisearchHttpError> {
     let before_search = Instant::now();
     let rtxn = index.read_txn()?;
@@ -178,6 +335,7 @@ pub fn perform_search(
     }
 
     let milli::SearchResult { documents_ids, matching_words, candidates, .. } = search.execute()?;
+    deadline.hits_found();
 
     let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
 
@@ -240,6 +398,7 @@ pub fn perform_search(
     formatter_builder.highlight_suffix(query.highlight_post_tag);
 
     let mut documents = Vec::new();
+    let mut degraded = false;
 
     let documents_iter = index.documents(&rtxn, documents_ids)?;
 
@@ -254,14 +413,20 @@ pub fn perform_search(
         let mut document =
             permissive_json_pointer::select_values(&displayed_document, attributes_to_retrieve);
 
-        let (matches_position, formatted) = format_fields(
-            &displayed_document,
-            &fields_ids_map,
-            &formatter_builder,
-            &formatted_options,
-            query.show_matches_position,
-            &displayed_ids,
-        )?;
+        // Once the time budget is exceeded, the remaining hits are not formatted anymore.
+        degraded = degraded || deadline.is_exceeded();
+        let (matches_position, formatted) = if degraded {
+            (None, Document::new())
+        } else {
+            format_fields(
+                &displayed_document,
+                &fields_ids_map,
+                &formatter_builder,
+                &formatted_options,
+                query.show_matches_position,
+                &displayed_ids,
+            )?
+        };
 
         if let Some(sort) = query.sort.as_ref() {
             insert_geo_distance(sort, &mut document);
@@ -290,7 +455,7 @@ pub fn perform_search(
     };
 
     let facet_distribution = match query.facets {
-        Some(ref fields) => {
+        Some(ref fields) if !deadline.is_exceeded() => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
 
             let max_values_by_facet = index
@@ -306,6 +471,10 @@ pub fn perform_search(
 
             Some(distribution)
         }
+        Some(_) => {
+            degraded = true;
+            None
+        }
         None => None,
     };
 
@@ -315,10 +484,261 @@ pub fn perform_search(
         query: query.q.clone().unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
+        degraded,
     };
     Ok(result)
 }
 
+/// Return the values of the facet matching the facet query, along with the number of documents
+/// matching the search containing them, the most frequent values first.
+///
+/// The engine doesn't expose a prefix index of the facet values yet, so the values are read from
+/// the facet distribution of the matching documents and filtered here: the cost of a facet search
+/// grows with the number of distinct values of the facet in these documents. Only the
+/// `maxValuesPerFacet` most frequent matching values are ever sorted.
+pub fn perform_facet_search(
+    index: &Index,
+    query: FacetSearchQuery,
+    deadline: &Deadline,
+) -> Result<FacetSearchResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let filterable_fields = index.filterable_fields(&rtxn)?;
+    if !filterable_fields.contains(&query.facet_name) {
+        return Err(MeilisearchHttpError::InvalidFacetSearchFacetName(
+            query.facet_name,
+            filterable_fields.into_iter().collect(),
+        ));
+    }
+
+    // Only the documents matching the search are considered.
+    let candidates = if query.q.is_some() || query.filter.is_some() {
+        let mut search = index.search(&rtxn);
+        if let Some(ref q) = query.q {
+            search.query(q);
+        }
+        if let Some(ref filter) = query.filter {
+            if let Some(facets) = parse_filter(filter)? {
+                search.filter(facets);
+            }
+        }
+        search.terms_matching_strategy(query.matching_strategy.into());
+        search.exhaustive_number_hits(true);
+        search.limit(0);
+        search.execute()?.candidates
+    } else {
+        index.documents_ids(&rtxn)?
+    };
+    deadline.check()?;
+
+    let mut facet_distribution = index.facets_distribution(&rtxn);
+    facet_distribution.facets([query.facet_name.as_str()]);
+    facet_distribution.max_values_per_facet(usize::MAX);
+    let mut distribution = facet_distribution.candidates(candidates).execute()?;
+    deadline.check()?;
+
+    let max_values_by_facet = index
+        .max_values_per_facet(&rtxn)
+        .map_err(milli::Error::from)?
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let facet_query = query.facet_query.as_deref().map(str::to_lowercase);
+    let mut facet_hits: Vec<_> = distribution
+        .remove(&query.facet_name)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(value, _)| match facet_query {
+            Some(ref facet_query) => facet_value_matches(value, facet_query),
+            None => true,
+        })
+        .map(|(value, count)| FacetValueHit { value, count })
+        .collect();
+    let by_frequency = |a: &FacetValueHit, b: &FacetValueHit| {
+        b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value))
+    };
+    if max_values_by_facet == 0 {
+        facet_hits.clear();
+    } else if facet_hits.len() > max_values_by_facet {
+        facet_hits.select_nth_unstable_by(max_values_by_facet - 1, by_frequency);
+        facet_hits.truncate(max_values_by_facet);
+    }
+    facet_hits.sort_unstable_by(by_frequency);
+
+    Ok(FacetSearchResult {
+        facet_hits,
+        facet_query: query.facet_query,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
+/// A facet value matches a query if the value, or one of its words, starts with the query.
+/// The query must be lowercased.
+fn facet_value_matches(value: &str, facet_query: &str) -> bool {
+    let value = value.to_lowercase();
+    value.starts_with(facet_query) || value.split_whitespace().any(|w| w.starts_with(facet_query))
+}
+
+/// Return the documents sharing the most distinctive words of the document with the given id,
+/// the most similar first.
+///
+/// The words of the searchable attributes of the document are weighted by their frequency in
+/// the document and their rarity in the index, and every other document is scored by the sum of
+/// the weights of the words it shares with it. The documents hidden by the search rules can
+/// neither be returned nor be the source document.
+pub fn perform_similar(
+    index: &Index,
+    query: SimilarQuery,
+    search_rules_filter: Option<Value>,
+    deadline: &Deadline,
+) -> Result<SimilarResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let id = match query.id {
+        Value::String(id) => id,
+        Value::Number(id) if id.is_u64() || id.is_i64() => id.to_string(),
+        id => return Err(MeilisearchHttpError::InvalidSimilarDocumentId(id)),
+    };
+    let source_id = index
+        .external_documents_ids(&rtxn)?
+        .get(id.as_bytes())
+        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(id.clone()))?;
+
+    let mut candidates = match search_rules_filter.as_ref().map(parse_filter).transpose()?.flatten()
+    {
+        Some(filter) => filter.evaluate(&rtxn, index)?,
+        None => index.documents_ids(&rtxn)?,
+    };
+    if !candidates.remove(source_id) {
+        return Err(MeilisearchHttpError::DocumentNotFound(id));
+    }
+    if let Some(filter) = query.filter.as_ref().map(parse_filter).transpose()?.flatten() {
+        candidates &= filter.evaluate(&rtxn, index)?;
+    }
+    deadline.check()?;
+
+    let stop_words = index.stop_words(&rtxn)?;
+    let mut tokenizer = TokenizerBuilder::new();
+    if let Some(ref stop_words) = stop_words {
+        tokenizer.stop_words(stop_words);
+    }
+    let tokenizer = tokenizer.build();
+
+    let searchable_ids = index.searchable_fields_ids(&rtxn)?;
+    let (_, source) = index
+        .documents(&rtxn, std::iter::once(source_id))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(id.clone()))?;
+    let mut frequencies = HashMap::new();
+    for (fid, value) in source.iter() {
+        if searchable_ids.as_ref().map_or(true, |ids| ids.contains(&fid)) {
+            let value = serde_json::from_slice(value)?;
+            count_words(&value, &tokenizer, &mut frequencies);
+        }
+    }
+
+    let number_of_documents = index.number_of_documents(&rtxn)? as f64;
+    let mut words = Vec::new();
+    for (word, frequency) in frequencies {
+        deadline.check()?;
+        if let Some(docids) = index.word_docids.get(&rtxn, &word)? {
+            let docids = docids & &candidates;
+            if !docids.is_empty() {
+                let rarity = (1.0 + number_of_documents / (docids.len() as f64 + 1.0)).ln();
+                words.push((frequency as f64 * rarity, docids));
+            }
+        }
+    }
+    words.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+    words.truncate(MAX_SIMILAR_WORDS);
+
+    let mut scores: HashMap<DocumentId, f64> = HashMap::new();
+    for (weight, docids) in &words {
+        deadline.check()?;
+        for docid in docids {
+            *scores.entry(docid).or_default() += weight;
+        }
+    }
+    let mut ranked: Vec<_> = scores.into_iter().collect();
+    ranked.sort_by(|(a_id, a), (b_id, b)| {
+        b.partial_cmp(a).unwrap_or(Ordering::Equal).then_with(|| a_id.cmp(b_id))
+    });
+
+    let max_total_hits = index
+        .pagination_max_total_hits(&rtxn)
+        .map_err(milli::Error::from)?
+        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+    let offset = min(query.offset, max_total_hits);
+    let limit = min(query.limit, max_total_hits.saturating_sub(offset));
+    let documents_ids = ranked.iter().skip(offset).take(limit).map(|(docid, _)| *docid);
+
+    let fields_ids_map = index.fields_ids_map(&rtxn)?;
+    let displayed_ids = index
+        .displayed_fields_ids(&rtxn)?
+        .map(|fields| fields.into_iter().collect::<BTreeSet<_>>())
+        .unwrap_or_else(|| fields_ids_map.iter().map(|(id, _)| id).collect());
+    let attributes_to_retrieve: Vec<_> = match query.attributes_to_retrieve {
+        Some(ref attributes) if !attributes.contains("*") => attributes
+            .iter()
+            .filter(|attr| fields_ids_map.id(attr).map_or(false, |id| displayed_ids.contains(&id)))
+            .map(String::as_str)
+            .collect(),
+        _ => displayed_ids
+            .iter()
+            .map(|&fid| fields_ids_map.name(fid).expect("Missing field name"))
+            .collect(),
+    };
+
+    let mut hits = Vec::new();
+    for (_id, obkv) in index.documents(&rtxn, documents_ids)? {
+        deadline.check()?;
+        let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
+        let document = permissive_json_pointer::select_values(
+            &displayed_document,
+            attributes_to_retrieve.iter().copied(),
+        );
+        hits.push(document);
+    }
+
+    Ok(SimilarResult {
+        hits,
+        id,
+        processing_time_ms: before_search.elapsed().as_millis(),
+        hits_info: HitsInfo::OffsetLimit {
+            limit,
+            offset,
+            estimated_total_hits: min(ranked.len(), max_total_hits),
+        },
+    })
+}
+
+/// Count the occurrences of the words of the strings and numbers of a value.
+fn count_words<A: AsRef<[u8]>>(
+    value: &Value,
+    tokenizer: &Tokenizer<'_, A>,
+    frequencies: &mut HashMap<String, usize>,
+) {
+    match value {
+        Value::String(text) => {
+            for token in tokenizer.tokenize(text).filter(|token| token.is_word()) {
+                *frequencies.entry(token.lemma().to_string()).or_default() += 1;
+            }
+        }
+        Value::Number(number) => {
+            *frequencies.entry(number.to_string()).or_default() += 1;
+        }
+        Value::Array(values) => {
+            values.iter().for_each(|value| count_words(value, tokenizer, frequencies))
+        }
+        Value::Object(object) => {
+            object.values().for_each(|value| count_words(value, tokenizer, frequencies))
+        }
+        Value::Null | Value::Bool(_) => (),
+    }
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
@@ -591,7 +1011,7 @@ fn format_value<'a, A: AsRef<[u8]>>(
     }
 }
 
-fn parse_filter(facets: &Value) -> Result<Option<Filter>, MeilisearchHttpError> {
+pub(crate) fn parse_filter(facets: &Value) -> Result<Option<Filter>, MeilisearchHttpError> {
     match facets {
         Value::String(expr) => {
             let condition = Filter::from_str(expr)?;
@@ -638,6 +1058,59 @@ fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpEr
 mod test {
     use super::*;
 
+    #[test]
+    fn search_exceeding_its_deadline_is_degraded() {
+        use std::io::Cursor;
+
+        use meilisearch_types::document_formats::read_json;
+        use meilisearch_types::heed::EnvOpenOptions;
+        use milli::documents::DocumentsBatchReader;
+        use milli::update::{IndexDocuments, IndexDocumentsConfig, IndexerConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut options = EnvOpenOptions::new();
+        options.map_size(10 * 1024 * 1024);
+        let index = Index::new(options, dir.path()).unwrap();
+
+        let documents = json!([{ "id": 1, "title": "Shazam!" }, { "id": 2, "title": "Glass" }]);
+        let mut payload = Cursor::new(Vec::new());
+        read_json(documents.to_string().as_bytes(), &mut payload).unwrap();
+        let reader = DocumentsBatchReader::from_reader(Cursor::new(payload.into_inner())).unwrap();
+        let mut wtxn = index.write_txn().unwrap();
+        let indexer_config = IndexerConfig::default();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &indexer_config,
+            IndexDocumentsConfig::default(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (builder, user_result) = builder.add_documents(reader).unwrap();
+        user_result.unwrap();
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let query = json!({ "q": "glass", "attributesToHighlight": ["title"], "facets": ["*"] });
+        let query: SearchQuery = serde_json::from_value(query).unwrap();
+
+        let result = perform_search(&index, query.clone(), &Deadline::default()).unwrap();
+        assert!(!result.degraded);
+        assert!(result.facet_distribution.is_some());
+        assert!(result.hits[0].formatted.contains_key("title"));
+
+        // The deadline is exceeded once the engine found the hits.
+        let deadline = Deadline::new(Some(Instant::now()), 0);
+        let result = perform_search(&index, query, &deadline).unwrap();
+        assert!(result.degraded);
+        assert_eq!(result.hits.len(), 1);
+        assert_eq!(result.hits[0].document.get("title"), Some(&json!("Glass")));
+        assert!(result.hits[0].formatted.is_empty());
+        assert!(result.facet_distribution.is_none());
+        assert_eq!(serde_json::to_value(&result).unwrap()["degraded"], json!(true));
+    }
+
     #[test]
     fn test_insert_geo_distance() {
         let value: Document = serde_json::from_str(
@@ -698,4 +1171,13 @@ mod test {
         insert_geo_distance(sorters, &mut document);
         assert_eq!(document.get("_geoDistance"), None);
     }
+
+    #[test]
+    fn test_facet_value_matches() {
+        assert!(facet_value_matches("Science Fiction", "sci"));
+        assert!(facet_value_matches("Science Fiction", "fic"));
+        assert!(facet_value_matches("Science Fiction", "science f"));
+        assert!(!facet_value_matches("Science Fiction", "tion"));
+        assert!(!facet_value_matches("Romance", "sci"));
+    }
 }
@@ -0,0 +1,135 @@
+//! The searches are processed on a dedicated pool, within an optional time budget.
+//!
+//! The engine can't be interrupted while it looks for the hits of a search. When it doesn't find
+//! them within the time budget, the request fails without waiting for it. Every other step of a
+//! search checks the time budget and is skipped, or stops early, once it is exceeded. There is no
+//! memory budget yet, the memory used by a search is bounded by `pagination.maxTotalHits`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::error;
+use tokio::sync::oneshot;
+
+use crate::error::MeilisearchHttpError;
+use crate::Opt;
+
+/// The thread pool the searches are processed on, shared by all the HTTP workers so that a slow
+/// search can only ever occupy one of its threads.
+#[derive(Clone)]
+pub struct SearchPool {
+    pool: Arc<rayon::ThreadPool>,
+    time_budget: Option<Duration>,
+    /// Whether the duration of the searches is recorded in the `--experimental-enable-metrics`
+    /// histogram.
+    record_durations: bool,
+}
+
+impl SearchPool {
+    pub fn new(opt: &Opt) -> anyhow::Result<SearchPool> {
+        let opts = &opt.search_options;
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .thread_name(|index| format!("search-thread:{index}"))
+            // A panicking search must not abort the whole process.
+            .panic_handler(|_| error!("A search thread panicked."));
+        if let Some(search_threads) = opts.search_threads {
+            builder = builder.num_threads(search_threads.get());
+        }
+
+        Ok(SearchPool {
+            pool: Arc::new(builder.build()?),
+            time_budget: opts.search_time_budget_ms.map(Duration::from_millis),
+            record_durations: opt.experimental_enable_metrics,
+        })
+    }
+
+    /// Returns the instant at which a search request received now exceeds its time budget.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.time_budget.map(|budget| Instant::now() + budget)
+    }
+
+    /// Runs the search on the pool as soon as one of its threads is available, or fails if
+    /// the deadline is exceeded before that, or before the engine found the hits of the search.
+    pub async fn run<T, F>(
+        &self,
+        deadline: Option<Instant>,
+        search: F,
+    ) -> Result<T, MeilisearchHttpError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Deadline) -> Result<T, MeilisearchHttpError> + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let time_budget_ms = self.time_budget.map_or(0, |budget| budget.as_millis() as u64);
+        let record_durations = self.record_durations;
+        let search_deadline = Deadline::new(deadline, time_budget_ms);
+        let hits_found = search_deadline.hits_found.clone();
+        self.pool.spawn(move || {
+            let result = if search_deadline.is_exceeded() {
+                Err(MeilisearchHttpError::SearchTimeout(search_deadline.time_budget_ms))
+            } else {
+                let before_search = Instant::now();
+                let result = search(&search_deadline);
+                if record_durations {
+                    crate::metrics::MEILISEARCH_SEARCH_DURATION_SECONDS
+                        .observe(before_search.elapsed().as_secs_f64());
+                }
+                result
+            };
+            // The request may have been canceled in the meantime.
+            let _ = sender.send(result);
+        });
+
+        let mut receiver = receiver;
+        if let Some(deadline) = deadline {
+            let deadline = tokio::time::Instant::from_std(deadline);
+            match tokio::time::timeout_at(deadline, &mut receiver).await {
+                Ok(result) => return result.unwrap_or(Err(MeilisearchHttpError::SearchPanicked)),
+                // Once its hits are found, the search quickly returns them degraded.
+                Err(_) if hits_found.load(Ordering::Relaxed) => (),
+                Err(_) => {
+                    return Err(MeilisearchHttpError::SearchTimeExceeded(time_budget_ms));
+                }
+            }
+        }
+
+        receiver.await.unwrap_or(Err(MeilisearchHttpError::SearchPanicked))
+    }
+}
+
+/// The deadline of a search processed on the pool.
+#[derive(Debug, Clone, Default)]
+pub struct Deadline {
+    instant: Option<Instant>,
+    time_budget_ms: u64,
+    /// Set once the engine found the hits of the search, the request then waits for them even
+    /// though the deadline is exceeded.
+    hits_found: Arc<AtomicBool>,
+}
+
+impl Deadline {
+    pub fn new(instant: Option<Instant>, time_budget_ms: u64) -> Deadline {
+        Deadline { instant, time_budget_ms, hits_found: Arc::default() }
+    }
+
+    /// Returns `true` if the deadline of the search is exceeded.
+    pub fn is_exceeded(&self) -> bool {
+        self.instant.map_or(false, |instant| Instant::now() >= instant)
+    }
+
+    /// Fails if the deadline is exceeded, for the searches that can't return degraded results.
+    pub fn check(&self) -> Result<(), MeilisearchHttpError> {
+        if self.is_exceeded() {
+            Err(MeilisearchHttpError::SearchTimeExceeded(self.time_budget_ms))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Marks the hits of the search as found, the remaining steps of the search must then be
+    /// skipped as soon as the deadline is exceeded.
+    pub fn hits_found(&self) {
+        self.hits_found.store(true, Ordering::Relaxed);
+    }
+}
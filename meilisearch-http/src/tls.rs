@@ -0,0 +1,320 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use actix_tls::accept::rustls::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+use log::{error, info};
+use meilisearch_auth::AuthController;
+use parking_lot::{Mutex, RwLock};
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::{CertifiedKey, SigningKey};
+use rustls::SignatureScheme;
+use uuid::Uuid;
+
+use crate::option::{load_certs, load_ocsp, load_private_key};
+
+/// The interval at which the files of the TLS configuration are checked for modifications.
+const RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The certificate served to the clients. It is replaced when its files are modified or when
+/// the process receives a `SIGHUP`, the open connections keep the certificate they started with.
+pub struct ReloadableCertificate {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    ocsp_path: Option<PathBuf>,
+    certified_key: RwLock<Arc<CertifiedKey>>,
+    modified: Mutex<Vec<Option<SystemTime>>>,
+}
+
+impl ReloadableCertificate {
+    pub fn load(
+        cert_path: PathBuf,
+        key_path: PathBuf,
+        ocsp_path: Option<PathBuf>,
+    ) -> anyhow::Result<ReloadableCertificate> {
+        let modified = modification_times([&cert_path, &key_path].into_iter().chain(&ocsp_path));
+        let certified_key = read_certified_key(&cert_path, &key_path, &ocsp_path)?;
+        Ok(ReloadableCertificate {
+            cert_path,
+            key_path,
+            ocsp_path,
+            certified_key: RwLock::new(Arc::new(certified_key)),
+            modified: Mutex::new(modified),
+        })
+    }
+
+    /// Reloads the certificate, either because its files were modified since they were last
+    /// read or because it is forced to.
+    fn reload(&self, force: bool) -> anyhow::Result<bool> {
+        let paths = [&self.cert_path, &self.key_path].into_iter().chain(&self.ocsp_path);
+        let modified = modification_times(paths);
+        if !force && *self.modified.lock() == modified {
+            return Ok(false);
+        }
+
+        // The previous certificate is kept when the new one is invalid, which can happen when
+        // the files are read while a renewal is writing them. It is retried on the next check.
+        let certified_key = read_certified_key(&self.cert_path, &self.key_path, &self.ocsp_path)?;
+        *self.certified_key.write() = Arc::new(certified_key);
+        *self.modified.lock() = modified;
+        Ok(true)
+    }
+}
+
+impl ResolvesServerCert for ReloadableCertificate {
+    fn resolve(&self, _client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        Some(self.certified_key.read().clone())
+    }
+}
+
+fn read_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+    ocsp_path: &Option<PathBuf>,
+) -> anyhow::Result<CertifiedKey> {
+    let certs = load_certs(cert_path.to_path_buf())?;
+    let privkey = load_private_key(key_path.to_path_buf())?;
+    let ocsp = load_ocsp(ocsp_path)?;
+    let key = rustls::sign::any_supported_type(&privkey)
+        .map_err(|_| anyhow::anyhow!("bad certificates/private key"))?;
+    let end_entity = certs.first().ok_or_else(|| anyhow::anyhow!("no certificate found"))?;
+    check_key_matches_certificate(&*key, end_entity)?;
+
+    let mut certified_key = CertifiedKey::new(certs, key);
+    if !ocsp.is_empty() {
+        certified_key.ocsp = Some(ocsp);
+    }
+    Ok(certified_key)
+}
+
+/// Checks that the private key is the one of the certificate by verifying a signature of the key
+/// with the public key of the certificate, so that a certificate renewed without its key is
+/// refused instead of breaking all the handshakes.
+fn check_key_matches_certificate(
+    key: &dyn SigningKey,
+    certificate: &rustls::Certificate,
+) -> anyhow::Result<()> {
+    let schemes = [
+        SignatureScheme::ECDSA_NISTP256_SHA256,
+        SignatureScheme::ECDSA_NISTP384_SHA384,
+        SignatureScheme::ED25519,
+        SignatureScheme::RSA_PKCS1_SHA256,
+    ];
+    let signer = key
+        .choose_scheme(&schemes)
+        .ok_or_else(|| anyhow::anyhow!("unsupported private key algorithm"))?;
+    let algorithm = match signer.scheme() {
+        SignatureScheme::ECDSA_NISTP256_SHA256 => &webpki::ECDSA_P256_SHA256,
+        SignatureScheme::ECDSA_NISTP384_SHA384 => &webpki::ECDSA_P384_SHA384,
+        SignatureScheme::ED25519 => &webpki::ED25519,
+        _ => &webpki::RSA_PKCS1_2048_8192_SHA256,
+    };
+
+    let message = b"meilisearch certificate check";
+    let signature = signer
+        .sign(message)
+        .map_err(|e| anyhow::anyhow!("cannot sign with the private key: {}", e))?;
+    let certificate = webpki::EndEntityCert::try_from(certificate.0.as_slice())
+        .map_err(|e| anyhow::anyhow!("invalid certificate: {}", e))?;
+    certificate
+        .verify_signature(algorithm, message, &signature)
+        .map_err(|_| anyhow::anyhow!("the private key doesn't match the certificate"))
+}
+
+fn modification_times<'a>(paths: impl Iterator<Item = &'a PathBuf>) -> Vec<Option<SystemTime>> {
+    paths.map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok()).collect()
+}
+
+/// The API keys the clients are authenticated with when they present a certificate of which the
+/// common name is mapped to the uid of a key, read from a JSON object and reloaded with the
+/// certificate.
+#[derive(Clone, Default)]
+pub struct ClientKeys {
+    path: Option<PathBuf>,
+    keys: Arc<RwLock<HashMap<String, Uuid>>>,
+    modified: Arc<Mutex<Option<SystemTime>>>,
+}
+
+impl ClientKeys {
+    pub fn load(path: Option<PathBuf>) -> anyhow::Result<ClientKeys> {
+        let client_keys = ClientKeys { path, ..Default::default() };
+        client_keys.reload(true)?;
+        Ok(client_keys)
+    }
+
+    fn reload(&self, force: bool) -> anyhow::Result<bool> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(false),
+        };
+        let modified = fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        if !force && *self.modified.lock() == modified {
+            return Ok(false);
+        }
+
+        let keys = fs::read(path)
+            .map_err(|e| anyhow::anyhow!("cannot read the client keys file: {}", e))?;
+        let keys = serde_json::from_slice(&keys)
+            .map_err(|e| anyhow::anyhow!("invalid client keys file: {}", e))?;
+        *self.keys.write() = keys;
+        *self.modified.lock() = modified;
+        Ok(true)
+    }
+
+    fn key_uid(&self, common_name: &str) -> Option<Uuid> {
+        self.keys.read().get(common_name).copied()
+    }
+}
+
+/// The common name of the certificate presented by the client, stored in the data of its
+/// connection.
+#[derive(Clone)]
+pub struct ClientIdentity {
+    common_name: String,
+    client_keys: ClientKeys,
+}
+
+impl ClientIdentity {
+    /// Returns the API key mapped to the certificate of the client, if any.
+    pub fn api_key(&self, auth: &AuthController) -> Option<String> {
+        let uid = self.client_keys.key_uid(&self.common_name)?;
+        auth.generate_key(uid)
+    }
+}
+
+/// Stores the identity of the client in the data of its connection when it presented a
+/// certificate, to be used as the `on_connect` callback of the HTTP server.
+pub fn store_client_identity(
+    connection: &dyn Any,
+    data: &mut Extensions,
+    client_keys: &ClientKeys,
+) {
+    let stream = match connection.downcast_ref::<TlsStream<TcpStream>>() {
+        Some(stream) => stream,
+        None => return,
+    };
+    let (_, session) = stream.get_ref();
+    let certificate = match session.peer_certificates().and_then(|certs| certs.first()) {
+        Some(certificate) => certificate,
+        None => return,
+    };
+
+    if let Ok((_, certificate)) = x509_parser::parse_x509_certificate(&certificate.0) {
+        let common_name = certificate.subject().iter_common_name().next();
+        if let Some(common_name) = common_name.and_then(|cn| cn.as_str().ok()) {
+            data.insert(ClientIdentity {
+                common_name: common_name.to_string(),
+                client_keys: client_keys.clone(),
+            });
+        }
+    }
+}
+
+/// Reloads the certificate and the client keys when their files are modified or when the
+/// process receives a `SIGHUP`.
+pub fn watch_reloads(
+    certificate: Arc<ReloadableCertificate>,
+    client_keys: ClientKeys,
+) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = signal(SignalKind::hangup())?;
+        let certificate = certificate.clone();
+        let client_keys = client_keys.clone();
+        actix_web::rt::spawn(async move {
+            while hangup.recv().await.is_some() {
+                info!("Received SIGHUP, reloading the TLS configuration.");
+                reload(&certificate, &client_keys, true);
+            }
+        });
+    }
+
+    actix_web::rt::spawn(async move {
+        let mut interval = tokio::time::interval(RELOAD_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            reload(&certificate, &client_keys, false);
+        }
+    });
+
+    Ok(())
+}
+
+fn reload(certificate: &ReloadableCertificate, client_keys: &ClientKeys, force: bool) {
+    match certificate.reload(force) {
+        Ok(true) => info!("Reloaded the TLS certificate."),
+        Ok(false) => (),
+        Err(e) => error!("Could not reload the TLS certificate: {}", e),
+    }
+    match client_keys.reload(force) {
+        Ok(true) => info!("Reloaded the client keys."),
+        Ok(false) => (),
+        Err(e) => error!("Could not reload the client keys: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::*;
+
+    fn tls_asset(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/assets/tls").join(name)
+    }
+
+    #[test]
+    fn certificate_is_reloaded_when_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        fs::copy(tls_asset("first.pem"), &cert_path).unwrap();
+        fs::copy(tls_asset("first.key"), &key_path).unwrap();
+
+        let certificate =
+            ReloadableCertificate::load(cert_path.clone(), key_path.clone(), None).unwrap();
+        let first = certificate.certified_key.read().clone();
+        assert!(!certificate.reload(false).unwrap());
+
+        // A certificate renewed without its key is refused and the previous one is still served.
+        fs::copy(tls_asset("second.pem"), &cert_path).unwrap();
+        assert!(certificate.reload(true).is_err());
+        assert_eq!(certificate.certified_key.read().cert[0].0, first.cert[0].0);
+
+        fs::copy(tls_asset("second.key"), &key_path).unwrap();
+        assert!(certificate.reload(true).unwrap());
+        let second = certificate.certified_key.read().clone();
+        assert_ne!(second.cert[0].0, first.cert[0].0);
+        assert_eq!(second.cert[0].0, load_certs(tls_asset("second.pem")).unwrap()[0].0);
+    }
+
+    #[test]
+    fn client_keys_are_reloaded_when_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("client-keys.json");
+        let uid = Uuid::new_v4();
+        fs::write(&path, format!(r#"{{ "client-a": "{uid}" }}"#)).unwrap();
+
+        let client_keys = ClientKeys::load(Some(path.clone())).unwrap();
+        assert_eq!(client_keys.key_uid("client-a"), Some(uid));
+        assert_eq!(client_keys.key_uid("client-b"), None);
+        assert!(!client_keys.reload(false).unwrap());
+
+        // An invalid file keeps the previous keys.
+        fs::File::create(&path).unwrap().write_all(b"{").unwrap();
+        assert!(client_keys.reload(true).is_err());
+        assert_eq!(client_keys.key_uid("client-a"), Some(uid));
+
+        fs::write(&path, format!(r#"{{ "client-b": "{uid}" }}"#)).unwrap();
+        assert!(client_keys.reload(true).unwrap());
+        assert_eq!(client_keys.key_uid("client-a"), None);
+        assert_eq!(client_keys.key_uid("client-b"), Some(uid));
+    }
+}
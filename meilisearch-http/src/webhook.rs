@@ -0,0 +1,304 @@
+//! Send the tasks that succeeded or failed to the URLs registered with `--task-webhook-url`,
+//! or later on with the `/webhooks` route.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender, TrySendError};
+use hmac::{Hmac, Mac};
+use log::{error, warn};
+use meilisearch_types::tasks::{Status, Task};
+use parking_lot::RwLock;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Client, Url};
+use sha2::Sha256;
+
+use crate::metrics::MEILISEARCH_WEBHOOK_DROPPED_NOTIFICATIONS_TOTAL;
+use crate::routes::tasks::TaskView;
+use crate::Opt;
+
+/// The header containing the signature of the payload, only sent if a secret is defined.
+pub const SIGNATURE_HEADER: &str = "X-Meilisearch-Signature";
+
+/// How many times we try to deliver a task to a webhook before giving up.
+const MAX_ATTEMPTS: u32 = 5;
+/// The delay before the first retry, it is doubled after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// The time we are willing to wait for a webhook to answer.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// How many finished tasks can wait to be delivered, the next ones are dropped while a slow
+/// webhook keeps the queue full.
+const MAX_PENDING_NOTIFICATIONS: usize = 1000;
+
+/// The webhooks the finished tasks are sent to, which can be changed while the instance runs.
+///
+/// The changes are not persisted, the webhooks of the command line are used on restart.
+#[derive(Clone, Default)]
+pub struct Webhooks {
+    config: Arc<RwLock<WebhookConfig>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WebhookConfig {
+    pub urls: Vec<Url>,
+    pub secret: Option<String>,
+}
+
+impl Webhooks {
+    /// Returns the webhooks registered with `--task-webhook-url`.
+    pub fn new(opt: &Opt) -> anyhow::Result<Webhooks> {
+        let urls = opt
+            .task_webhook_url
+            .iter()
+            .map(|url| {
+                parse_webhook_url(url).with_context(|| format!("invalid task webhook url `{url}`"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let config = WebhookConfig { urls, secret: opt.task_webhook_secret.clone() };
+        Ok(Webhooks { config: Arc::new(RwLock::new(config)) })
+    }
+
+    pub fn config(&self) -> WebhookConfig {
+        self.config.read().clone()
+    }
+
+    /// Replaces the webhooks, the tasks already being delivered are sent to the previous ones.
+    pub fn set_config(&self, config: WebhookConfig) {
+        *self.config.write() = config;
+    }
+}
+
+/// Parses the URL of a webhook, only the HTTP and HTTPS schemes are accepted.
+pub fn parse_webhook_url(url: &str) -> Option<Url> {
+    Url::parse(url).ok().filter(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// Spawn the thread in charge of calling the webhooks and return the channel the
+/// index scheduler must send the finished tasks to.
+///
+/// The finished tasks are moved right away to a queue of at most [`MAX_PENDING_NOTIFICATIONS`]
+/// tasks, so that the index scheduler is never slowed down by the webhooks. The tasks that don't
+/// fit in the queue are dropped and counted in the metrics.
+pub fn spawn_webhook_sender(webhooks: Webhooks) -> anyhow::Result<Sender<Task>> {
+    let client = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+
+    let (sender, receiver) = unbounded();
+    let (queue_sender, queue_receiver) = bounded(MAX_PENDING_NOTIFICATIONS);
+    std::thread::Builder::new()
+        .name(String::from("task-webhooks-queue"))
+        .spawn(move || enqueue(receiver, queue_sender))?;
+    std::thread::Builder::new().name(String::from("task-webhooks")).spawn(move || {
+        runtime.block_on(run(queue_receiver, client, webhooks));
+    })?;
+
+    Ok(sender)
+}
+
+/// Move the finished tasks to the delivery queue, dropping them when it is full.
+fn enqueue(receiver: Receiver<Task>, queue: Sender<Task>) {
+    for task in receiver {
+        if !is_notified(&task) {
+            continue;
+        }
+        match queue.try_send(task) {
+            Ok(()) => (),
+            Err(TrySendError::Full(task)) => {
+                MEILISEARCH_WEBHOOK_DROPPED_NOTIFICATIONS_TOTAL.inc();
+                warn!("The webhooks are too slow, the task {} will not be notified.", task.uid);
+            }
+            Err(TrySendError::Disconnected(_)) => break,
+        }
+    }
+}
+
+/// Returns `true` if the task must be sent to the webhooks, only the tasks that succeeded or
+/// failed are, the canceled tasks are not.
+fn is_notified(task: &Task) -> bool {
+    matches!(task.status, Status::Succeeded | Status::Failed)
+}
+
+async fn run(receiver: Receiver<Task>, client: Client, webhooks: Webhooks) {
+    // The tasks are delivered one after the other to keep them ordered by uid.
+    while let Ok(task) = receiver.recv() {
+        let WebhookConfig { urls, secret } = webhooks.config();
+        if urls.is_empty() {
+            continue;
+        }
+
+        let body = match serde_json::to_vec(&TaskView::from_task(&task)) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("Could not serialize the task {} for the webhooks: {e}", task.uid);
+                continue;
+            }
+        };
+        let signature = secret.as_deref().map(|secret| sign(secret, &body));
+
+        for url in &urls {
+            deliver(&client, url, &body, signature.as_deref(), task.uid).await;
+        }
+    }
+}
+
+/// Post the body to the webhook, retrying with an exponential backoff
+/// on network errors and unsuccessful responses.
+async fn deliver(client: &Client, url: &Url, body: &[u8], signature: Option<&str>, uid: u32) {
+    let mut backoff = INITIAL_BACKOFF;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request =
+            client.post(url.clone()).header(CONTENT_TYPE, "application/json").body(body.to_vec());
+        if let Some(signature) = signature {
+            request = request.header(SIGNATURE_HEADER, signature);
+        }
+
+        let error = match request.send().await {
+            Ok(response) if response.status().is_success() => return,
+            Ok(response) => format!("the webhook answered with the status {}", response.status()),
+            Err(e) => e.to_string(),
+        };
+
+        if attempt == MAX_ATTEMPTS {
+            error!(
+                "Could not notify `{url}` of the task {uid} after {MAX_ATTEMPTS} attempts: {error}"
+            );
+        } else {
+            warn!("Could not notify `{url}` of the task {uid}, retrying in {backoff:?}: {error}");
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+/// Compute the signature of a payload, formatted as `sha256=<hex-encoded HMAC-SHA256>`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    // HMAC accepts keys of any size, this call can't fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    use super::*;
+
+    /// Reads an HTTP request and returns its head, the lines of which are lowercased, and body.
+    fn read_request(stream: &mut TcpStream) -> (Vec<String>, Vec<u8>) {
+        let mut reader = BufReader::new(stream);
+        let mut head = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            head.push(line);
+        }
+        let length = head
+            .iter()
+            .find_map(|line| line.strip_prefix("content-length: "))
+            .map_or(0, |length| length.parse().unwrap());
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        (head, body)
+    }
+
+    #[actix_rt::test]
+    async fn delivery_is_retried_until_the_webhook_succeeds() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/hook", listener.local_addr().unwrap())).unwrap();
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                requests.push(read_request(&mut stream));
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        let client = Client::builder().timeout(REQUEST_TIMEOUT).build().unwrap();
+        let body = br#"{"uid":0}"#;
+        let signature = sign("key", body);
+        deliver(&client, &url, body, Some(&signature), 0).await;
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        for (head, received_body) in requests {
+            assert_eq!(head[0], "post /hook http/1.1");
+            assert!(head.contains(&"content-type: application/json".to_string()), "{head:?}");
+            let signature_line = format!("{}: {signature}", SIGNATURE_HEADER.to_lowercase());
+            assert!(head.contains(&signature_line), "{head:?}");
+            assert_eq!(received_body, body);
+        }
+    }
+
+    #[test]
+    fn notifications_are_dropped_when_the_queue_is_full() {
+        let task = |uid| Task {
+            uid,
+            enqueued_at: time::OffsetDateTime::now_utc(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            canceled_by: None,
+            batch_uid: None,
+            details: None,
+            status: Status::Succeeded,
+            kind: meilisearch_types::tasks::KindWithContent::SnapshotCreation,
+        };
+        let (sender, receiver) = unbounded();
+        let (queue_sender, queue_receiver) = bounded(1);
+        for uid in 0..3 {
+            sender.send(task(uid)).unwrap();
+        }
+        drop(sender);
+
+        let dropped = MEILISEARCH_WEBHOOK_DROPPED_NOTIFICATIONS_TOTAL.get();
+        enqueue(receiver, queue_sender);
+
+        let queued: Vec<_> = queue_receiver.iter().map(|task| task.uid).collect();
+        assert_eq!(queued, vec![0]);
+        assert_eq!(MEILISEARCH_WEBHOOK_DROPPED_NOTIFICATIONS_TOTAL.get() - dropped, 2);
+    }
+
+    #[test]
+    fn only_the_succeeded_and_failed_tasks_are_notified() {
+        let mut task = Task {
+            uid: 0,
+            enqueued_at: time::OffsetDateTime::now_utc(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            canceled_by: None,
+            batch_uid: None,
+            details: None,
+            status: Status::Succeeded,
+            kind: meilisearch_types::tasks::KindWithContent::SnapshotCreation,
+        };
+        assert!(is_notified(&task));
+        task.status = Status::Failed;
+        assert!(is_notified(&task));
+        task.status = Status::Canceled;
+        assert!(!is_notified(&task));
+    }
+
+    #[test]
+    fn signature_is_a_hex_encoded_hmac_sha256() {
+        // Computed with `echo -n "The quick brown fox jumps over the lazy dog" | openssl dgst -sha256 -hmac "key"`.
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+}
@@ -153,6 +153,31 @@ async fn add_valid_api_key_no_description() {
     assert_json_include!(actual: response, expected: expected_response);
 }
 
+#[actix_rt::test]
+async fn add_valid_api_key_index_pattern_and_filter() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "indexes": ["products-*", "movies"],
+        "actions": ["search"],
+        "filter": ["tenant = 1", ["color = blue", "color = red"]],
+        "expiresAt": "2050-11-13T00:00:00"
+    });
+
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+
+    let expected_response = json!({
+        "actions": ["search"],
+        "indexes": ["products-*", "movies"],
+        "filter": ["tenant = 1", ["color = blue", "color = red"]],
+        "expiresAt": "2050-11-13T00:00:00Z"
+    });
+
+    assert_json_include!(actual: response, expected: expected_response);
+}
+
 #[actix_rt::test]
 async fn add_valid_api_key_null_description() {
     let mut server = Server::new_auth().await;
@@ -377,7 +402,7 @@ async fn error_add_api_key_invalid_index_uids() {
     let (response, code) = server.add_api_key(content).await;
 
     let expected_response = json!({
-        "message": r#"`invalid index # / \name with spaces` is not a valid index uid. Index uid can be an integer or a string containing only alphanumeric characters, hyphens (-) and underscores (_)."#,
+        "message": r#"`invalid index # / \name with spaces` is not a valid index uid pattern. Index uid patterns can be an integer or a string containing only alphanumeric characters, hyphens (-), underscores (_), and optionally end with a star (*)."#,
         "code": "invalid_api_key_indexes",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_api_key_indexes"
@@ -387,6 +412,53 @@ async fn error_add_api_key_invalid_index_uids() {
     assert_eq!(code, 400);
 }
 
+#[actix_rt::test]
+async fn error_add_api_key_invalid_index_uid_pattern() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "indexes": ["prod*ucts"],
+        "actions": ["search"],
+        "expiresAt": "2050-11-13T00:00:00"
+    });
+    let (response, code) = server.add_api_key(content).await;
+
+    let expected_response = json!({
+        "message": r#"`prod*ucts` is not a valid index uid pattern. Index uid patterns can be an integer or a string containing only alphanumeric characters, hyphens (-), underscores (_), and optionally end with a star (*)."#,
+        "code": "invalid_api_key_indexes",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_api_key_indexes"
+    });
+
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 400);
+}
+
+#[actix_rt::test]
+async fn error_add_api_key_invalid_parameters_filter() {
+    let mut server = Server::new_auth().await;
+    server.use_api_key("MASTER_KEY");
+
+    let content = json!({
+        "indexes": ["products"],
+        "actions": ["search"],
+        "filter": {"tenant": 1},
+        "expiresAt": "2050-11-13T00:00:00"
+    });
+    let (response, code) = server.add_api_key(content).await;
+
+    let expected_response = json!({
+        "message": r#"`filter` field value `{"tenant":1}` is invalid. It should be a string, an array of strings, or an array of arrays of strings representing a search filter, or specified as a null value."#,
+        "code": "invalid_api_key_filter",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_api_key_filter"
+    });
+
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 400);
+}
+
 #[actix_rt::test]
 async fn error_add_api_key_invalid_parameters_actions() {
     let mut server = Server::new_auth().await;
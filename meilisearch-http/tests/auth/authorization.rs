@@ -6,21 +6,28 @@ use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 use time::{Duration, OffsetDateTime};
 
-use crate::common::Server;
+use crate::common::{GetAllDocumentsOptions, Server};
 
 pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'static str>>> =
     Lazy::new(|| {
-        let mut authorizations = hashmap! {
+        hashmap! {
             ("POST",    "/indexes/products/search") =>                         hashset!{"search", "*"},
             ("GET",     "/indexes/products/search") =>                         hashset!{"search", "*"},
+            ("POST",    "/indexes/products/facet-search") =>                   hashset!{"search", "*"},
+            ("POST",    "/indexes/products/similar") =>                        hashset!{"search", "*"},
+            ("POST",    "/multi-search") =>                                    hashset!{"search", "*"},
             ("POST",    "/indexes/products/documents") =>                      hashset!{"documents.add", "documents.*", "*"},
+            ("POST",    "/indexes/products/documents/edit") =>                 hashset!{"documents.add", "documents.*", "*"},
             ("GET",     "/indexes/products/documents") =>                      hashset!{"documents.get", "documents.*", "*"},
+            ("GET",     "/indexes/products/documents/fetch") =>                hashset!{"documents.get", "documents.*", "*"},
             ("GET",     "/indexes/products/documents/0") =>                    hashset!{"documents.get", "documents.*", "*"},
             ("DELETE",  "/indexes/products/documents/0") =>                    hashset!{"documents.delete", "documents.*", "*"},
             ("GET",     "/tasks") =>                                           hashset!{"tasks.get", "tasks.*", "*"},
             ("DELETE",  "/tasks") =>                                           hashset!{"tasks.delete", "tasks.*", "*"},
             ("GET",     "/tasks?indexUid=products") =>                         hashset!{"tasks.get", "tasks.*", "*"},
             ("GET",     "/tasks/0") =>                                         hashset!{"tasks.get", "tasks.*", "*"},
+            ("GET",     "/batches") =>                                         hashset!{"tasks.get", "tasks.*", "*"},
+            ("GET",     "/batches/0") =>                                       hashset!{"tasks.get", "tasks.*", "*"},
             ("PATCH",   "/indexes/products/") =>                               hashset!{"indexes.update", "indexes.*", "*"},
             ("GET",     "/indexes/products/") =>                               hashset!{"indexes.get", "indexes.*", "*"},
             ("DELETE",  "/indexes/products/") =>                               hashset!{"indexes.delete", "indexes.*", "*"},
@@ -36,6 +43,8 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("GET",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/stop-words/export") =>     hashset!{"settings.get", "settings.*", "*"},
+            ("GET",     "/indexes/products/settings/synonyms/export") =>       hashset!{"settings.get", "settings.*", "*"},
             ("DELETE",  "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings/typo-tolerance") =>        hashset!{"settings.update", "settings.*", "*"},
@@ -47,22 +56,24 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PUT",     "/indexes/products/settings/sortable-attributes") =>   hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/stop-words") =>            hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/stop-words/import") =>     hashset!{"settings.update", "settings.*", "*"},
+            ("PUT",     "/indexes/products/settings/synonyms/import") =>       hashset!{"settings.update", "settings.*", "*"},
             ("GET",     "/indexes/products/stats") =>                          hashset!{"stats.get", "stats.*", "*"},
             ("GET",     "/stats") =>                                           hashset!{"stats.get", "stats.*", "*"},
             ("POST",    "/dumps") =>                                           hashset!{"dumps.create", "dumps.*", "*"},
+            ("POST",    "/dumps/import") =>                                    hashset!{"dumps.import", "dumps.*", "*"},
+            ("GET",     "/dumps/20221101-100000000/download") =>               hashset!{"dumps.get", "dumps.*", "*"},
             ("GET",     "/version") =>                                         hashset!{"version", "*"},
             ("PATCH",   "/keys/mykey/") =>                                     hashset!{"keys.update", "*"},
             ("GET",     "/keys/mykey/") =>                                     hashset!{"keys.get", "*"},
             ("DELETE",  "/keys/mykey/") =>                                     hashset!{"keys.delete", "*"},
             ("POST",    "/keys") =>                                            hashset!{"keys.create", "*"},
             ("GET",     "/keys") =>                                            hashset!{"keys.get", "*"},
-        };
-
-        if cfg!(feature = "metrics") {
-            authorizations.insert(("GET", "/metrics"), hashset! {"metrics.get", "metrics.*", "*"});
+            ("GET",     "/metrics") =>                                         hashset!{"metrics.get", "metrics.*", "*"},
+            ("POST",    "/logs/stream") =>                                     hashset!{"logs.get", "*"},
+            ("GET",     "/webhooks") =>                                        hashset!{"webhooks.get", "*"},
+            ("PATCH",   "/webhooks") =>                                        hashset!{"webhooks.update", "*"},
         }
-
-        authorizations
     });
 
 pub static ALL_ACTIONS: Lazy<HashSet<&'static str>> = Lazy::new(|| {
@@ -373,6 +384,150 @@ async fn list_authorized_indexes_restricted_index() {
     assert!(!response.iter().any(|index| index["uid"] == "test"));
 }
 
+#[actix_rt::test]
+async fn list_authorized_indexes_index_pattern() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    for uid in ["products-fr", "products-en", "test"] {
+        let index = server.index(uid);
+        let (response, code) = index.create(Some("id")).await;
+        assert_eq!(202, code, "{:?}", &response);
+        let task_id = response["taskUid"].as_u64().unwrap();
+        index.wait_task(task_id).await;
+    }
+
+    // create key with access on the `products-*` indexes only.
+    let content = json!({
+        "indexes": ["products-*"],
+        "actions": ["indexes.get"],
+        "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+
+    // use created key.
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let (response, code) = server.list_indexes(None, None).await;
+    assert_eq!(200, code, "{:?}", &response);
+
+    let response = response["results"].as_array().unwrap();
+    // key should have access on the `products-*` indexes.
+    assert!(response.iter().any(|index| index["uid"] == "products-fr"));
+    assert!(response.iter().any(|index| index["uid"] == "products-en"));
+
+    // key should not have access on `test` index.
+    assert!(!response.iter().any(|index| index["uid"] == "test"));
+
+    let (_, code) = server.index("products-fr").get().await;
+    assert_eq!(200, code);
+    let (_, code) = server.index("test").get().await;
+    assert_eq!(403, code);
+}
+
+#[actix_rt::test]
+async fn search_with_key_filter() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    let index = server.index("products");
+    let documents = json!([
+        { "id": 1, "tenant": 1, "color": "blue" },
+        { "id": 2, "tenant": 1, "color": "red" },
+        { "id": 3, "tenant": 2, "color": "blue" },
+    ]);
+    index.update_settings(json!({ "filterableAttributes": ["tenant", "color"] })).await;
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(202, code, "{:?}", &response);
+    let task_id = response["taskUid"].as_u64().unwrap();
+    index.wait_task(task_id).await;
+
+    // create key that can only see the documents of the first tenant.
+    let content = json!({
+        "indexes": ["products"],
+        "actions": ["search"],
+        "filter": "tenant = 1",
+        "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+
+    // use created key.
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let index = server.index("products");
+    index
+        .search(json!({}), |response, code| {
+            assert_eq!(200, code, "{:?}", &response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{:?}", &response);
+        })
+        .await;
+
+    // the filter of the key is added to the filter of the query.
+    index
+        .search(json!({ "filter": "color = blue" }), |response, code| {
+            assert_eq!(200, code, "{:?}", &response);
+            assert_eq!(response["hits"], json!([{ "id": 1, "tenant": 1, "color": "blue" }]));
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn get_documents_with_key_filter() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    let index = server.index("products");
+    let documents = json!([
+        { "id": 1, "tenant": 1, "color": "blue" },
+        { "id": 2, "tenant": 1, "color": "red" },
+        { "id": 3, "tenant": 2, "color": "blue" },
+    ]);
+    index.update_settings(json!({ "filterableAttributes": ["tenant", "color"] })).await;
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(202, code, "{:?}", &response);
+    let task_id = response["taskUid"].as_u64().unwrap();
+    index.wait_task(task_id).await;
+
+    // create key that can only read the documents of the first tenant.
+    let content = json!({
+        "indexes": ["products"],
+        "actions": ["documents.get"],
+        "filter": "tenant = 1",
+        "expiresAt": (OffsetDateTime::now_utc() + Duration::hours(1)).format(&Rfc3339).unwrap(),
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+
+    // use created key.
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let index = server.index("products");
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(200, code, "{:?}", &response);
+    assert_eq!(response["total"], json!(2), "{:?}", &response);
+    let ids: Vec<_> =
+        response["results"].as_array().unwrap().iter().map(|doc| &doc["id"]).collect();
+    assert_eq!(ids, vec![&json!(1), &json!(2)]);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(200, code, "{:?}", &response);
+    let (response, code) = index.get_document(3, None).await;
+    assert_eq!(404, code, "{:?}", &response);
+    assert_eq!(response["code"], json!("document_not_found"));
+
+    // the filter of the key is added to the filter of the fetch.
+    let (body, code) = index.fetch_documents(Some("color = blue"), None).await;
+    assert_eq!(200, code);
+    let fetched: Vec<Value> =
+        serde_json::Deserializer::from_slice(&body).into_iter().map(Result::unwrap).collect();
+    assert_eq!(fetched, vec![json!({ "id": 1, "tenant": 1, "color": "blue" })]);
+}
+
 #[actix_rt::test]
 async fn list_authorized_indexes_no_index_restriction() {
     let mut server = Server::new_auth().await;
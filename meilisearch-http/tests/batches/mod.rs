@@ -0,0 +1,85 @@
+use serde_json::json;
+
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn error_get_unexisting_batch() {
+    let server = Server::new().await;
+    let (response, code) = server.get_batch(1).await;
+
+    let expected_response = json!({
+        "message": "Batch `1` not found.",
+        "code": "batch_not_found",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#batch_not_found"
+    });
+
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 404);
+
+    let (response, code) = server.service.get("/batches/pied").await;
+
+    let expected_response = json!({
+        "message": "Batch uid `pied` is invalid. It should only contain numeric characters.",
+        "code": "invalid_batch_uid",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_batch_uid"
+    });
+
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 400);
+}
+
+#[actix_rt::test]
+async fn processed_tasks_are_batched() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "content": "doggos" }]), None).await;
+    index.wait_task(0).await;
+
+    let (task, code) = index.get_task(0).await;
+    assert_eq!(code, 200, "{}", task);
+    let batch_uid = task["batchUid"].as_u64().unwrap();
+
+    let (batch, code) = server.get_batch(batch_uid).await;
+    assert_eq!(code, 200, "{}", batch);
+    assert_eq!(batch["uid"], batch_uid);
+    assert_eq!(batch["taskUids"], json!([0]));
+    assert_eq!(batch["indexUids"], json!(["test"]));
+    assert!(batch["duration"].is_string());
+    assert!(batch["startedAt"].is_string());
+    assert!(batch["finishedAt"].is_string());
+
+    let (response, code) = server.batches().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"], json!([batch]));
+    assert_eq!(response["from"], batch_uid);
+    assert_eq!(response["next"], json!(null));
+}
+
+#[actix_rt::test]
+async fn list_batches_pagination() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.update_settings(json!({ "filterableAttributes": ["doggo"] })).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server.batches_filter(json!({ "limit": 1 })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"].as_array().unwrap().len(), 1);
+    assert_eq!(response["results"][0]["taskUids"], json!([1]));
+    assert_eq!(response["limit"], 1);
+    let next = response["next"].clone();
+    assert!(next.is_u64(), "{}", response);
+
+    let (response, code) = server.batches_filter(json!({ "limit": 1, "from": next })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"][0]["taskUids"], json!([0]));
+    assert_eq!(response["next"], json!(null));
+
+    let (response, code) = server.batches_filter(json!({ "lol": 1 })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "bad_request");
+}
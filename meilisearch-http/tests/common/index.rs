@@ -3,6 +3,7 @@ use std::panic::{catch_unwind, resume_unwind, UnwindSafe};
 use std::time::Duration;
 
 use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
 use serde_json::{json, Value};
 use tokio::time::sleep;
 use urlencoding::encode as urlencode;
@@ -150,6 +151,23 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn fetch_documents(
+        &self,
+        filter: Option<&str>,
+        fields: Option<&str>,
+    ) -> (Bytes, StatusCode) {
+        let mut url = format!("/indexes/{}/documents/fetch?", urlencode(self.uid.as_ref()));
+        if let Some(filter) = filter {
+            let _ = write!(url, "filter={}&", urlencode(filter));
+        }
+        if let Some(fields) = fields {
+            let _ = write!(url, "fields={}&", fields);
+        }
+
+        let req = actix_web::test::TestRequest::get().uri(&url);
+        self.service.request_raw(req).await
+    }
+
     pub async fn delete_document(&self, id: u64) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/documents/{}", urlencode(self.uid.as_ref()), id);
         self.service.delete(url).await
@@ -165,6 +183,11 @@ impl Index<'_> {
         self.service.post_encoded(url, serde_json::to_value(&ids).unwrap(), self.encoder).await
     }
 
+    pub async fn edit_documents(&self, edition: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/edit", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, edition, self.encoder).await
+    }
+
     pub async fn settings(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.get(url).await
@@ -175,6 +198,32 @@ impl Index<'_> {
         self.service.patch_encoded(url, settings, self.encoder).await
     }
 
+    /// Imports a file of synonyms or stop words, `setting` being `synonyms` or `stop-words`.
+    pub async fn import_setting(
+        &self,
+        setting: &str,
+        content_type: &str,
+        body: impl Into<String>,
+    ) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/{}/import", urlencode(self.uid.as_ref()), setting);
+        let req = actix_web::test::TestRequest::put()
+            .uri(&url)
+            .insert_header(("content-type", content_type))
+            .set_payload(body.into());
+        self.service.request(req).await
+    }
+
+    pub async fn export_setting(&self, setting: &str, format: &str) -> (Bytes, StatusCode) {
+        let url = format!(
+            "/indexes/{}/settings/{}/export?format={}",
+            urlencode(self.uid.as_ref()),
+            setting,
+            format
+        );
+        let req = actix_web::test::TestRequest::get().uri(&url);
+        self.service.request_raw(req).await
+    }
+
     pub async fn delete_settings(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/settings", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
@@ -216,6 +265,16 @@ impl Index<'_> {
         self.service.get(url).await
     }
 
+    pub async fn facet_search(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/facet-search", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
+    pub async fn similar(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/similar", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn update_distinct_attribute(&self, value: Value) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
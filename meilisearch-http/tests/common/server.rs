@@ -6,9 +6,14 @@ use std::time::Duration;
 use actix_http::body::MessageBody;
 use actix_web::dev::ServiceResponse;
 use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
 use byte_unit::{Byte, ByteUnit};
 use clap::Parser;
 use meilisearch_http::option::{IndexerOpts, MaxMemory, Opt};
+use meilisearch_http::rate_limit::RateLimiter;
+use meilisearch_http::replica::ReadOnlyReplica;
+use meilisearch_http::search_pool::SearchPool;
+use meilisearch_http::webhook::Webhooks;
 use meilisearch_http::{analytics, create_app, setup_meilisearch};
 use once_cell::sync::Lazy;
 use serde_json::{json, Value};
@@ -39,8 +44,22 @@ impl Server {
 
         let options = default_settings(dir.path());
 
-        let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let webhooks = Webhooks::new(&options).unwrap();
+        let (index_scheduler, auth) = setup_meilisearch(&options, &webhooks).unwrap();
+        let rate_limiter = RateLimiter::new(&options.rate_limit_options);
+        let replica =
+            ReadOnlyReplica::new(&options, index_scheduler.clone(), auth.clone()).unwrap();
+        let search_pool = SearchPool::new(&options).unwrap();
+        let service = Service {
+            index_scheduler,
+            auth,
+            options,
+            rate_limiter,
+            replica,
+            search_pool,
+            webhooks,
+            api_key: None,
+        };
 
         Server { service, _dir: Some(dir) }
     }
@@ -54,8 +73,22 @@ impl Server {
 
         options.master_key = Some("MASTER_KEY".to_string());
 
-        let (index_scheduler, auth) = setup_meilisearch(&options).unwrap();
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let webhooks = Webhooks::new(&options).unwrap();
+        let (index_scheduler, auth) = setup_meilisearch(&options, &webhooks).unwrap();
+        let rate_limiter = RateLimiter::new(&options.rate_limit_options);
+        let replica =
+            ReadOnlyReplica::new(&options, index_scheduler.clone(), auth.clone()).unwrap();
+        let search_pool = SearchPool::new(&options).unwrap();
+        let service = Service {
+            index_scheduler,
+            auth,
+            options,
+            rate_limiter,
+            replica,
+            search_pool,
+            webhooks,
+            api_key: None,
+        };
 
         Server { service, _dir: Some(dir) }
     }
@@ -67,8 +100,21 @@ impl Server {
     }
 
     pub async fn new_with_options(options: Opt) -> Result<Self, anyhow::Error> {
-        let (index_scheduler, auth) = setup_meilisearch(&options)?;
-        let service = Service { index_scheduler, auth, options, api_key: None };
+        let webhooks = Webhooks::new(&options)?;
+        let (index_scheduler, auth) = setup_meilisearch(&options, &webhooks)?;
+        let rate_limiter = RateLimiter::new(&options.rate_limit_options);
+        let replica = ReadOnlyReplica::new(&options, index_scheduler.clone(), auth.clone())?;
+        let search_pool = SearchPool::new(&options)?;
+        let service = Service {
+            index_scheduler,
+            auth,
+            options,
+            rate_limiter,
+            replica,
+            search_pool,
+            webhooks,
+            api_key: None,
+        };
 
         Ok(Server { service, _dir: None })
     }
@@ -81,10 +127,12 @@ impl Server {
         Error = actix_web::Error,
     > {
         actix_web::test::init_service(create_app(
-            self.service.index_scheduler.clone().into(),
-            self.service.auth.clone(),
             self.service.options.clone(),
             analytics::MockAnalytics::new(&self.service.options),
+            self.service.rate_limiter.clone(),
+            self.service.replica.clone(),
+            self.service.search_pool.clone(),
+            self.service.webhooks.clone(),
             true,
         ))
         .await
@@ -128,6 +176,11 @@ impl Server {
         self.service.get("/stats").await
     }
 
+    pub async fn metrics(&self) -> (Bytes, StatusCode) {
+        let req = actix_web::test::TestRequest::get().uri("/metrics");
+        self.service.request_raw(req).await
+    }
+
     pub async fn tasks(&self) -> (Value, StatusCode) {
         self.service.get("/tasks").await
     }
@@ -136,6 +189,19 @@ impl Server {
         self.service.get(format!("/tasks?{}", yaup::to_string(&filter).unwrap())).await
     }
 
+    pub async fn batches(&self) -> (Value, StatusCode) {
+        self.service.get("/batches").await
+    }
+
+    pub async fn batches_filter(&self, filter: Value) -> (Value, StatusCode) {
+        self.service.get(format!("/batches?{}", yaup::to_string(&filter).unwrap())).await
+    }
+
+    pub async fn get_batch(&self, batch_uid: u64) -> (Value, StatusCode) {
+        let url = format!("/batches/{}", batch_uid);
+        self.service.get(url).await
+    }
+
     pub async fn get_dump_status(&self, uid: &str) -> (Value, StatusCode) {
         self.service.get(format!("/dumps/{}/status", uid)).await
     }
@@ -144,10 +210,27 @@ impl Server {
         self.service.post("/dumps", json!(null)).await
     }
 
+    pub async fn download_dump(&self, uid: &str) -> (Bytes, StatusCode) {
+        let req = actix_web::test::TestRequest::get().uri(&format!("/dumps/{}/download", uid));
+        self.service.request_raw(req).await
+    }
+
+    pub async fn import_dump(&self, dump: impl Into<Bytes>) -> (Value, StatusCode) {
+        let req = actix_web::test::TestRequest::post()
+            .uri("/dumps/import")
+            .insert_header(("content-type", "application/octet-stream"))
+            .set_payload(dump.into());
+        self.service.request(req).await
+    }
+
     pub async fn index_swap(&self, value: Value) -> (Value, StatusCode) {
         self.service.post("/swap-indexes", value).await
     }
 
+    pub async fn multi_search(&self, queries: Value) -> (Value, StatusCode) {
+        self.service.post("/multi-search", queries).await
+    }
+
     pub async fn cancel_tasks(&self, value: Value) -> (Value, StatusCode) {
         self.service
             .post(format!("/tasks/cancel?{}", yaup::to_string(&value).unwrap()), json!(null))
@@ -197,8 +280,7 @@ pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
             max_indexing_memory: MaxMemory::unlimited(),
             ..Parser::parse_from(None as Option<&str>)
         },
-        #[cfg(feature = "metrics")]
-        enable_metrics_route: true,
+        experimental_enable_metrics: true,
         ..Parser::parse_from(None as Option<&str>)
     }
 }
@@ -4,8 +4,13 @@ use actix_web::http::header::ContentType;
 use actix_web::http::StatusCode;
 use actix_web::test;
 use actix_web::test::TestRequest;
+use actix_web::web::Bytes;
 use index_scheduler::IndexScheduler;
 use meilisearch_auth::AuthController;
+use meilisearch_http::rate_limit::RateLimiter;
+use meilisearch_http::replica::ReadOnlyReplica;
+use meilisearch_http::search_pool::SearchPool;
+use meilisearch_http::webhook::Webhooks;
 use meilisearch_http::{analytics, create_app, Opt};
 use serde_json::Value;
 
@@ -15,6 +20,10 @@ pub struct Service {
     pub index_scheduler: Arc<IndexScheduler>,
     pub auth: AuthController,
     pub options: Opt,
+    pub rate_limiter: RateLimiter,
+    pub replica: ReadOnlyReplica,
+    pub search_pool: SearchPool,
+    pub webhooks: Webhooks,
     pub api_key: Option<String>,
 }
 
@@ -87,12 +96,21 @@ impl Service {
         self.request(req).await
     }
 
-    pub async fn request(&self, mut req: test::TestRequest) -> (Value, StatusCode) {
+    pub async fn request(&self, req: test::TestRequest) -> (Value, StatusCode) {
+        let (body, status_code) = self.request_raw(req).await;
+        let response = serde_json::from_slice(&body).unwrap_or_default();
+        (response, status_code)
+    }
+
+    /// Send a request and return the body as is, for the routes that don't answer in JSON.
+    pub async fn request_raw(&self, mut req: test::TestRequest) -> (Bytes, StatusCode) {
         let app = test::init_service(create_app(
-            self.index_scheduler.clone().into(),
-            self.auth.clone(),
             self.options.clone(),
             analytics::MockAnalytics::new(&self.options),
+            self.rate_limiter.clone(),
+            self.replica.clone(),
+            self.search_pool.clone(),
+            self.webhooks.clone(),
             true,
         ))
         .await;
@@ -105,8 +123,7 @@ impl Service {
         let status_code = res.status();
 
         let body = test::read_body(res).await;
-        let response = serde_json::from_slice(&body).unwrap_or_default();
-        (response, status_code)
+        (body, status_code)
     }
 
     fn encode(&self, req: TestRequest, body: Value, encoder: Encoder) -> TestRequest {
@@ -1,10 +1,12 @@
 use actix_web::test;
+use byte_unit::{Byte, ByteUnit};
+use meilisearch_http::Opt;
 use serde_json::{json, Value};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
 use crate::common::encoder::Encoder;
-use crate::common::{GetAllDocumentsOptions, Server};
+use crate::common::{default_settings, GetAllDocumentsOptions, Server};
 
 /// This is the basic usage of our API and every other tests uses the content-type application/json
 #[actix_rt::test]
@@ -188,7 +190,7 @@ async fn error_add_documents_test_bad_content_types() {
     assert_eq!(
         response["message"],
         json!(
-            r#"The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`"#
+            r#"The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/msgpack`, `application/x-msgpack`"#
         )
     );
     assert_eq!(response["code"], "invalid_content_type");
@@ -209,7 +211,7 @@ async fn error_add_documents_test_bad_content_types() {
     assert_eq!(
         response["message"],
         json!(
-            r#"The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`"#
+            r#"The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/msgpack`, `application/x-msgpack`"#
         )
     );
     assert_eq!(response["code"], "invalid_content_type");
@@ -243,7 +245,7 @@ async fn error_add_documents_test_no_content_type() {
     assert_eq!(
         response["message"],
         json!(
-            r#"A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`"#
+            r#"A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/msgpack`, `application/x-msgpack`"#
         )
     );
     assert_eq!(response["code"], "missing_content_type");
@@ -263,7 +265,7 @@ async fn error_add_documents_test_no_content_type() {
     assert_eq!(
         response["message"],
         json!(
-            r#"A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`"#
+            r#"A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/msgpack`, `application/x-msgpack`"#
         )
     );
     assert_eq!(response["code"], "missing_content_type");
@@ -321,6 +323,133 @@ async fn error_add_malformed_csv_documents() {
     assert_eq!(response["link"], json!("https://docs.meilisearch.com/errors#malformed_payload"));
 }
 
+#[actix_rt::test]
+async fn add_csv_documents_with_delimiter_and_typed_headers() {
+    let document = "id;name;price:number;tags:string[];available:boolean;sizes:number[]\n\
+                    1;Bouvier Bernois;12.5;dog, fluffy;true;1,2.5\n\
+                    2;Leonberg;;;;";
+
+    let server = Server::new().await;
+    let index = server.index("dog");
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents?csvDelimiter=%3B")
+        .set_payload(document.to_string())
+        .insert_header(("content-type", "text/csv"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    assert_eq!(status_code, 202, "{}", response);
+
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({
+            "id": "1",
+            "name": "Bouvier Bernois",
+            "price": 12.5,
+            "tags": ["dog", "fluffy"],
+            "available": true,
+            "sizes": [1, 2.5],
+        })
+    );
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({
+            "id": "2",
+            "name": "Leonberg",
+            "price": null,
+            "tags": null,
+            "available": null,
+            "sizes": null,
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn error_add_csv_documents_with_invalid_delimiter() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    for delimiter in ["%3B%3B", "%22", "%0A", ""] {
+        let req = test::TestRequest::post()
+            .uri(&format!("/indexes/dog/documents?csvDelimiter={}", delimiter))
+            .set_payload("id;content\n1;hello")
+            .insert_header(("content-type", "text/csv"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let status_code = res.status();
+        let body = test::read_body(res).await;
+        let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+        assert_eq!(status_code, 400, "{}", response);
+        assert_eq!(response["code"], json!("invalid_document_csv_delimiter"));
+        assert_eq!(response["type"], json!("invalid_request"));
+        assert_eq!(
+            response["link"],
+            json!("https://docs.meilisearch.com/errors#invalid_document_csv_delimiter")
+        );
+    }
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents?csvDelimiter=%3B")
+        .set_payload(r#"[{ "id": 1 }]"#)
+        .insert_header(("content-type", "application/json"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    assert_eq!(status_code, 400, "{}", response);
+    assert_eq!(
+        response["message"],
+        json!(
+            r#"The Content-Type `application/json` does not support the use of a CSV delimiter. The CSV delimiter can only be used with the Content-Type `text/csv`."#
+        )
+    );
+    assert_eq!(response["code"], json!("invalid_document_csv_delimiter"));
+}
+
+#[actix_rt::test]
+async fn error_add_csv_documents_with_invalid_typed_value() {
+    let document = "id,price:number\n1,12\n2,cheap";
+
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .set_payload(document.to_string())
+        .insert_header(("content-type", "text/csv"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    assert_eq!(status_code, 400, "{}", response);
+    assert_eq!(
+        response["message"],
+        json!(
+            r#"The value `cheap` of the `price` column on line 3 of the `csv` payload can't be parsed as `number`."#
+        )
+    );
+    assert_eq!(response["code"], json!("invalid_document_csv_value"));
+    assert_eq!(response["type"], json!("invalid_request"));
+    assert_eq!(
+        response["link"],
+        json!("https://docs.meilisearch.com/errors#invalid_document_csv_value")
+    );
+}
+
 #[actix_rt::test]
 async fn error_add_malformed_json_documents() {
     let document = r#"[{"id": 1}, {id: 2}]"#;
@@ -417,7 +546,7 @@ async fn error_add_malformed_json_documents() {
 
 #[actix_rt::test]
 async fn error_add_malformed_ndjson_documents() {
-    let document = "{\"id\": 1}\n{id: 2}";
+    let document = "{id: 1}\n{id: 2}";
 
     let server = Server::new().await;
     let app = server.init_web_app().await;
@@ -436,7 +565,7 @@ async fn error_add_malformed_ndjson_documents() {
     assert_eq!(
         response["message"],
         json!(
-            r#"The `ndjson` payload provided is malformed. `Couldn't serialize document value: key must be a string at line 2 column 2`."#
+            r#"The `ndjson` payload provided is malformed. `Couldn't serialize document value: key must be a string at line 1 column 2`."#
         )
     );
     assert_eq!(response["code"], json!("malformed_payload"));
@@ -456,8 +585,150 @@ async fn error_add_malformed_ndjson_documents() {
     assert_eq!(status_code, 400);
     assert_eq!(
         response["message"],
-        json!("The `ndjson` payload provided is malformed. `Couldn't serialize document value: key must be a string at line 2 column 2`.")
+        json!("The `ndjson` payload provided is malformed. `Couldn't serialize document value: key must be a string at line 1 column 2`.")
+    );
+    assert_eq!(response["code"], json!("malformed_payload"));
+    assert_eq!(response["type"], json!("invalid_request"));
+    assert_eq!(response["link"], json!("https://docs.meilisearch.com/errors#malformed_payload"));
+}
+
+/// The malformed lines of a ndjson payload must only reject their own document.
+#[actix_rt::test]
+async fn add_ndjson_documents_with_malformed_lines() {
+    let document = "{\"id\": 1}\n{id: 2}\n\n{\"id\": 3}\n[4]\n{\"id\": 5}";
+
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .set_payload(document.to_string())
+        .insert_header(("content-type", "application/x-ndjson"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    assert_eq!(status_code, 202);
+
+    let index = server.index("dog");
+    index.wait_task(0).await;
+
+    let (response, code) = index.get_task(0).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["receivedDocuments"], 3);
+    assert_eq!(response["details"]["indexedDocuments"], 3);
+
+    let rejected = &response["details"]["rejectedDocuments"];
+    assert_eq!(rejected.as_array().unwrap().len(), 2);
+    assert_eq!(rejected[0]["line"], 2);
+    assert_eq!(rejected[0]["code"], "malformed_payload");
+    assert_eq!(
+        rejected[0]["message"],
+        json!("The `ndjson` payload provided is malformed. `Couldn't serialize document value: key must be a string at line 1 column 2`.")
     );
+    assert_eq!(rejected[1]["line"], 5);
+    assert_eq!(rejected[1]["code"], "malformed_payload");
+    assert_eq!(response["details"]["rejectedDocumentsCount"], 2);
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"].as_array().unwrap().len(), 3);
+}
+
+/// Only the first malformed lines of a ndjson payload are reported in the details of its task,
+/// but all of them are counted.
+#[actix_rt::test]
+async fn add_ndjson_documents_with_many_malformed_lines() {
+    let mut document = String::from("{\"id\": 1}\n");
+    for _ in 0..500 {
+        document.push_str("{id: 2}\n");
+    }
+
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .set_payload(document)
+        .insert_header(("content-type", "application/x-ndjson"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 202);
+
+    let index = server.index("dog");
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["receivedDocuments"], 1);
+    let rejected = response["details"]["rejectedDocuments"].as_array().unwrap();
+    assert_eq!(rejected.len(), 100);
+    assert_eq!(rejected[0]["line"], 2);
+    assert_eq!(rejected[99]["line"], 101);
+    assert_eq!(response["details"]["rejectedDocumentsCount"], 500);
+}
+
+#[actix_rt::test]
+async fn add_documents_test_msgpack_content_types() {
+    let document = json!([
+        {
+            "id": 1,
+            "content": "Bouvier Bernois",
+        },
+        {
+            "id": 2,
+            "content": "Pyrenean Mastiff",
+        }
+    ]);
+    let document = rmp_serde::to_vec_named(&document).unwrap();
+
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    for content_type in ["application/msgpack", "application/x-msgpack"] {
+        let req = test::TestRequest::post()
+            .uri("/indexes/dog/documents")
+            .set_payload(document.clone())
+            .insert_header(("content-type", content_type))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let status_code = res.status();
+        assert_eq!(status_code, 202);
+    }
+
+    let index = server.index("dog");
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_task(1).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["status"], "succeeded");
+    assert_eq!(response["details"]["receivedDocuments"], 2);
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 2, "content": "Pyrenean Mastiff" }));
+}
+
+#[actix_rt::test]
+async fn error_add_malformed_msgpack_documents() {
+    // 0xc1 is a marker that is never used by MessagePack.
+    let document = vec![0x91, 0xc1];
+
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/dog/documents")
+        .set_payload(document)
+        .insert_header(("content-type", "application/msgpack"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+    assert_eq!(status_code, 400);
+    assert!(response["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("The `msgpack` payload provided is malformed: "));
     assert_eq!(response["code"], json!("malformed_payload"));
     assert_eq!(response["type"], json!("invalid_request"));
     assert_eq!(response["link"], json!("https://docs.meilisearch.com/errors#malformed_payload"));
@@ -880,6 +1151,109 @@ async fn error_add_documents_payload_size() {
     assert_eq!(code, 413);
 }
 
+#[actix_rt::test]
+async fn error_add_documents_truncated_payload() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let document = json!([{ "id": 1, "content": "foo" }]).to_string();
+    let encoder = Encoder::Gzip;
+    let encoded: actix_web::web::Bytes = encoder.encode(document).into();
+    // the end of the gzip stream and its checksum are missing.
+    let truncated = encoded.slice(..encoded.len() / 2);
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/test/documents")
+        .set_payload(truncated)
+        .insert_header(("content-type", "application/json"))
+        .insert_header(encoder.header().unwrap())
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+
+    assert_eq!(status_code, 400, "{}", response);
+    assert_eq!(response["code"], json!("malformed_payload"));
+    assert!(response["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("The provided payload could not be decompressed from `gzip`: "));
+}
+
+#[actix_rt::test]
+async fn error_add_documents_truncated_plain_payload() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let document = json!([{ "id": 1, "content": "foo" }]).to_string();
+    // the payload is cut in the middle of the document.
+    let truncated = document[..document.len() / 2].to_string();
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/test/documents")
+        .set_payload(truncated)
+        .insert_header(("content-type", "application/json"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+
+    assert_eq!(status_code, 400, "{}", response);
+    assert_eq!(response["code"], json!("malformed_payload"));
+    assert!(response["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("The `json` payload provided is malformed"));
+}
+
+#[actix_rt::test]
+async fn error_add_documents_unsupported_content_encoding() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/test/documents")
+        .set_payload(json!([{ "id": 1, "content": "foo" }]).to_string())
+        .insert_header(("content-type", "application/json"))
+        .insert_header(("content-encoding", "compress"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    let status_code = res.status();
+    let body = test::read_body(res).await;
+    let response: Value = serde_json::from_slice(&body).unwrap_or_default();
+
+    assert_eq!(status_code, 415);
+    assert_eq!(
+        response,
+        json!({
+            "message": "The Content-Encoding `compress` is not supported. Accepted values for the Content-Encoding header are: `gzip`, `deflate`, `br`, `zstd`, `identity`",
+            "code": "unsupported_media_type",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#unsupported_media_type"
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn error_add_documents_payload_size_is_checked_after_decompression() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        http_payload_size_limit: Byte::from_unit(10.0, ByteUnit::KiB).unwrap(),
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index_with_encoder("test", Encoder::Zstd);
+
+    // very repetitive documents are much smaller once compressed.
+    let documents: Vec<_> = (0..1000).map(|id| json!({ "id": id, "content": "foo" })).collect();
+    let (response, code) = index.add_documents(json!(documents), None).await;
+
+    assert_eq!(code, 413, "{}", response);
+    assert_eq!(response["code"], json!("payload_too_large"));
+}
+
 #[actix_rt::test]
 async fn error_primary_key_inference() {
     let server = Server::new().await;
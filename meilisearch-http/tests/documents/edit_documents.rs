@@ -0,0 +1,166 @@
+use serde_json::json;
+
+use crate::common::{GetAllDocumentsOptions, Server};
+
+#[actix_rt::test]
+async fn edit_documents_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .add_documents(
+            json!([
+                { "id": 1, "genre": "horror", "price": 10, "discount": 0.5, "label": "sale" },
+                { "id": 2, "genre": "comedy", "price": 5 },
+                { "id": 3, "genre": "horror" },
+            ]),
+            None,
+        )
+        .await;
+    index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_documents(json!({
+            "filter": ["genre = horror"],
+            "set": { "status": "archived" },
+            "unset": ["label"],
+            "increment": { "price": 2, "discount": 0.25 },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+    assert_eq!(response["type"], "documentEdition");
+
+    let task = index.wait_task(2).await;
+    assert_eq!(task["status"], "succeeded", "{}", task);
+    assert_eq!(
+        task["details"],
+        json!({ "editedDocuments": 2, "originalFilter": "(genre = horror)" })
+    );
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["results"],
+        json!([
+            { "id": 1, "genre": "horror", "price": 12, "discount": 0.75, "status": "archived" },
+            { "id": 2, "genre": "comedy", "price": 5 },
+            { "id": 3, "genre": "horror", "status": "archived", "price": 2, "discount": 0.25 },
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn edit_documents_fails_on_the_primary_key_or_a_non_numeric_increment() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(json!([{ "id": 1, "genre": "horror" }]), None).await;
+    index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    index.wait_task(1).await;
+
+    index.edit_documents(json!({ "filter": "genre = horror", "unset": ["id"] })).await;
+    let task = index.wait_task(2).await;
+    assert_eq!(task["status"], "failed", "{}", task);
+    assert_eq!(
+        task["error"]["message"],
+        "The primary key `id` of the documents can't be modified by a document edition."
+    );
+    assert_eq!(task["error"]["code"], "invalid_document_edition");
+
+    index.edit_documents(json!({ "filter": "genre = horror", "increment": { "genre": 1 } })).await;
+    let task = index.wait_task(3).await;
+    assert_eq!(task["status"], "failed", "{}", task);
+    assert_eq!(
+        task["error"]["message"],
+        "Cannot increment the field `genre` of the document `1`. The field must be a number and its new value must fit in a 64-bit number."
+    );
+    assert_eq!(task["details"]["editedDocuments"], 0);
+
+    index.edit_documents(json!({ "filter": "unknown = 1", "set": { "genre": "comedy" } })).await;
+    let task = index.wait_task(4).await;
+    assert_eq!(task["status"], "failed", "{}", task);
+    assert_eq!(task["error"]["code"], "invalid_filter");
+}
+
+#[actix_rt::test]
+async fn error_edit_documents_invalid_edition() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.edit_documents(json!({ "filter": "genre = horror" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "A document edition must set, unset or increment at least one field.",
+            "code": "invalid_document_edition",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_document_edition"
+        })
+    );
+
+    let (response, code) = index.edit_documents(json!({ "filter": "", "unset": ["genre"] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response["message"],
+        "A document edition must have a filter selecting the documents to edit."
+    );
+
+    let (response, code) = index
+        .edit_documents(json!({
+            "filter": "genre = horror",
+            "set": { "price": 1 },
+            "increment": { "price": 1 },
+        }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response["message"],
+        "The field `price` can't be modified by several operations of the same document edition."
+    );
+
+    let (response, code) = index
+        .edit_documents(json!({ "filter": "genre = horror", "increment": { "price": "1" } }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "bad_request");
+}
+
+#[actix_rt::test]
+async fn error_edit_documents_bad_index_uid() {
+    let server = Server::new().await;
+    let index = server.index("883  fj!");
+
+    let (response, code) =
+        index.edit_documents(json!({ "filter": "genre = horror", "unset": ["genre"] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_index_uid");
+}
+
+#[actix_rt::test]
+async fn documents_named_like_the_document_routes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (response, _) = index
+        .add_documents(json!([{ "id": "edit", "genre": "horror" }, { "id": "fetch" }]), None)
+        .await;
+    index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+
+    // Only the POST requests are routed to the document edition.
+    let (response, code) = index.service.get("/indexes/test/documents/edit").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "id": "edit", "genre": "horror" }));
+
+    // The GET requests are routed to the documents fetch, the document named `fetch` is still
+    // listed and can be deleted.
+    let (response, code) = index.fetch_documents(None, Some("id")).await;
+    assert_eq!(code, 200);
+    let response = String::from_utf8(response.to_vec()).unwrap();
+    assert_eq!(response, "{\"id\":\"edit\"}\n{\"id\":\"fetch\"}\n");
+
+    let (response, code) = index.service.delete("/indexes/test/documents/fetch").await;
+    assert_eq!(code, 202, "{}", response);
+    index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"], json!([{ "id": "edit", "genre": "horror" }]));
+}
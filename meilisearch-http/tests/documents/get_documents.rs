@@ -378,3 +378,51 @@ async fn get_documents_displayed_attributes_is_ignored() {
     assert_eq!(response.as_object().unwrap().keys().count(), 16);
     assert!(response.as_object().unwrap().get("gender").is_some());
 }
+
+#[actix_rt::test]
+async fn fetch_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let documents: Vec<_> = (0..2500)
+        .map(|id| json!({ "id": id, "parity": if id % 2 == 0 { "even" } else { "odd" } }))
+        .collect();
+    index.add_documents(json!(documents), None).await;
+    index.update_settings(json!({ "filterableAttributes": ["parity"] })).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.fetch_documents(None, None).await;
+    assert_eq!(code, 200);
+    let lines: Vec<Value> = serde_json::Deserializer::from_slice(&response)
+        .into_iter()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(lines.len(), 2500);
+    assert_eq!(lines[1234], json!({ "id": 1234, "parity": "even" }));
+
+    let (response, code) =
+        index.fetch_documents(Some("parity = odd AND id < 10"), Some("id")).await;
+    assert_eq!(code, 200);
+    let response = String::from_utf8(response.to_vec()).unwrap();
+    assert_eq!(response, "{\"id\":1}\n{\"id\":3}\n{\"id\":5}\n{\"id\":7}\n{\"id\":9}\n");
+
+    let (response, code) = index.fetch_documents(Some("parity = none"), None).await;
+    assert_eq!(code, 200);
+    assert!(response.is_empty());
+}
+
+#[actix_rt::test]
+async fn error_fetch_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_response, code) = index.fetch_documents(None, None).await;
+    assert_eq!(code, 404);
+
+    index.add_documents(json!([{ "id": 1, "parity": "odd" }]), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.fetch_documents(Some("parity = odd"), None).await;
+    assert_eq!(code, 400);
+    let response: Value = serde_json::from_slice(&response).unwrap();
+    assert_eq!(response["code"], "invalid_filter");
+}
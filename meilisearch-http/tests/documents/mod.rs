@@ -1,4 +1,5 @@
 mod add_documents;
 mod delete_documents;
+mod edit_documents;
 mod get_documents;
 mod update_documents;
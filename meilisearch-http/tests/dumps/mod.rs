@@ -817,3 +817,96 @@ async fn import_dump_v5() {
 
     assert_eq!(key["name"], "my key");
 }
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn download_dump_and_import_it() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task["taskUid"].as_u64().unwrap()).await;
+
+    let (task, code) = server.create_dump().await;
+    assert_eq!(code, 202, "{task}");
+    let task = server.wait_task(task["taskUid"].as_u64().unwrap()).await;
+    assert_eq!(task["status"], "succeeded", "{task}");
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap();
+
+    let (dump, code) = server.download_dump(dump_uid).await;
+    assert_eq!(code, 200);
+
+    // The downloaded dump must be usable to start a new instance.
+    let temp = tempfile::tempdir().unwrap();
+    let dump_path = temp.path().join("downloaded.dump");
+    std::fs::write(&dump_path, &dump).unwrap();
+    let options = Opt { import_dump: Some(dump_path), ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let (document, code) = server.index("test").get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(document, json!({ "id": 1, "title": "Carol" }));
+}
+
+#[actix_rt::test]
+#[cfg_attr(target_os = "windows", ignore)]
+async fn import_uploaded_dump() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task["taskUid"].as_u64().unwrap()).await;
+
+    let dump = std::fs::read(GetDump::TestV5.path()).unwrap();
+    let (task, code) = server.import_dump(dump).await;
+    assert_eq!(code, 202, "{task}");
+    assert_eq!(task["type"], "dumpImport");
+    let task = server.wait_task(task["taskUid"].as_u64().unwrap()).await;
+    assert_eq!(task["status"], "succeeded", "{task}");
+    assert_eq!(task["details"], json!({ "importedIndexes": 2 }));
+
+    // The index with the same uid is replaced by the one of the dump.
+    let (indexes, code) = server.list_indexes(None, None).await;
+    assert_eq!(code, 200, "{indexes}");
+    assert_eq!(indexes["results"].as_array().unwrap().len(), 2);
+    assert_eq!(indexes["results"][0]["uid"], json!("test"));
+    assert_eq!(indexes["results"][1]["uid"], json!("test2"));
+
+    let (stats, code) = index.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(stats["numberOfDocuments"], 10);
+}
+
+#[actix_rt::test]
+async fn import_invalid_dump() {
+    let server = Server::new().await;
+
+    let (response, code) = server.import_dump("not a dump").await;
+    assert_eq!(code, 400, "{response}");
+    assert_eq!(
+        response,
+        json!({
+            "message": "The file is not a valid dump. A dump must be a gzipped tarball containing a `metadata.json` file.",
+            "code": "invalid_dump",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_dump"
+        })
+    );
+
+    // Nothing is enqueued for an invalid dump.
+    let (tasks, code) = server.tasks().await;
+    assert_eq!(code, 200);
+    assert_eq!(tasks["results"].as_array().unwrap().len(), 0);
+}
+
+#[actix_rt::test]
+async fn download_unknown_dump() {
+    let server = Server::new().await;
+
+    let (_, code) = server.download_dump("20221101-100000000").await;
+    assert_eq!(code, 404);
+
+    // A dump uid must not allow to read anything outside of the dumps directory.
+    let (_, code) = server.download_dump("..%2Fdata.ms%2Fauth").await;
+    assert_eq!(code, 404);
+}
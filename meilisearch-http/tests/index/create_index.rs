@@ -198,3 +198,36 @@ async fn error_create_with_invalid_index_uid() {
     assert_eq!(response, expected_response);
     assert_eq!(code, 400);
 }
+
+#[actix_rt::test]
+async fn create_index_with_max_size() {
+    let server = Server::new().await;
+    let (response, code) =
+        server.service.post("/indexes", json!({ "uid": "test", "maxSize": 1_048_576 })).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let index = server.index("test");
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["maxSize"], 1_048_576);
+
+    // Every document holds words of its own, so that the index grows well beyond its limit.
+    let documents: Vec<_> = (0..2_000)
+        .map(|id| {
+            let words: Vec<_> = (0..50).map(|i| format!("word{id}x{i}")).collect();
+            json!({ "id": id, "text": words.join(" ") })
+        })
+        .collect();
+    let (response, code) = index.add_documents(json!(documents), Some("id")).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    let expected_error = json!({
+        "message": "Index `test` reached its maximum size of 1048576 bytes.",
+        "code": "index_size_limit_reached",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#index_size_limit_reached"
+    });
+    assert_eq!(response["error"], expected_error);
+}
@@ -44,6 +44,17 @@ async fn stats() {
     assert_eq!(response["fieldDistribution"]["id"], 2);
     assert_eq!(response["fieldDistribution"]["name"], 1);
     assert_eq!(response["fieldDistribution"]["age"], 1);
+
+    let disk_usage = &response["diskUsage"];
+    assert!(disk_usage["onDiskSize"].as_u64().unwrap() > 0, "{}", disk_usage);
+    assert!(disk_usage["mapSize"].as_u64().unwrap() > 0, "{}", disk_usage);
+    assert_eq!(disk_usage["maxSize"], json!(null));
+    assert!(disk_usage.get("fragmentation").is_none(), "{}", disk_usage);
+
+    let (response, code) = index.service.get("/indexes/test/stats?fragmentation=true").await;
+    assert_eq!(code, 200, "{}", response);
+    let fragmentation = response["diskUsage"]["fragmentation"].as_f64().unwrap();
+    assert!((0.0..=100.0).contains(&fragmentation), "{}", fragmentation);
 }
 
 #[actix_rt::test]
@@ -122,3 +122,34 @@ async fn error_update_unexisting_index() {
 
     assert_eq!(response["error"], expected_response);
 }
+
+#[actix_rt::test]
+async fn update_max_size() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (_, code) = index.create(None).await;
+    assert_eq!(code, 202);
+
+    let (response, code) =
+        server.service.patch("/indexes/test", json!({ "maxSize": 4_194_304 })).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["maxSize"], 4_194_304);
+
+    let (response, code) = index.add_documents(json!([{ "id": 1, "name": "Alexey" }]), None).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = server.service.patch("/indexes/test", json!({ "maxSize": null })).await;
+    assert_eq!(code, 202, "{}", response);
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"], json!({ "primaryKey": null, "maxSize": null }));
+
+    // The documents are still there once the index is reopened without a limit.
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["results"].as_array().unwrap().len(), 1);
+}
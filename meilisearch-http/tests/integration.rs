@@ -1,14 +1,19 @@
 mod auth;
+mod batches;
 mod common;
 mod dashboard;
 mod documents;
 mod dumps;
 mod index;
+mod logs;
+mod rate_limit;
+mod replica;
 mod search;
 mod settings;
 mod snapshot;
 mod stats;
 mod tasks;
+mod webhooks;
 
 // Tests are isolated by features in different modules to allow better readability, test
 // targetability, and improved incremental compilation times.
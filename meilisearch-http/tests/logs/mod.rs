@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use actix_web::body::MessageBody;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::test;
+use futures::future::poll_fn;
+use meilisearch_http::logs::setup_logs;
+use meilisearch_http::Opt;
+use serde_json::json;
+use tokio::time::timeout;
+use uuid::Uuid;
+
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn stream_logs() {
+    let server = Server::new().await;
+    // The streams are fed by the global subscriber, only set up by the first test calling this.
+    let options = Opt { log_level: String::from("off"), ..server.service.options.clone() };
+    let _ = setup_logs(&options);
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::post()
+        .uri("/logs/stream")
+        .set_json(json!({ "level": "debug", "format": "human" }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "text/plain; charset=utf-8");
+    let mut lines = Box::pin(res.into_body());
+
+    // The request is logged once its response is sent.
+    let res = test::call_service(&app, test::TestRequest::get().uri("/health").to_request()).await;
+    test::read_body(res).await;
+    let line = timeout(Duration::from_secs(5), poll_fn(|cx| lines.as_mut().poll_next(cx)))
+        .await
+        .expect("no log line was received")
+        .unwrap()
+        .unwrap();
+    let line = String::from_utf8(line.to_vec()).unwrap();
+    assert!(line.ends_with('\n'), "{}", line);
+
+    let req = test::TestRequest::post()
+        .uri("/logs/stream")
+        .set_json(json!({ "level": "WARN" }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get(CONTENT_TYPE).unwrap(), "application/x-ndjson");
+}
+
+#[actix_rt::test]
+async fn error_stream_logs_invalid_level() {
+    let server = Server::new().await;
+
+    let (response, code) = server.service.post("/logs/stream", json!({ "level": "verbose" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "The log level `verbose` is invalid. Available levels are `error`, `warn`, `info`, `debug` and `trace`.",
+            "code": "invalid_log_stream_level",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_log_stream_level"
+        })
+    );
+
+    let (response, code) = server.service.post("/logs/stream", json!({})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "bad_request");
+}
+
+#[actix_rt::test]
+async fn request_id_is_sent_back() {
+    let server = Server::new().await;
+    let app = server.init_web_app().await;
+
+    let req = test::TestRequest::get().uri("/health").insert_header(("X-Request-Id", "my-request"));
+    let res = test::call_service(&app, req.to_request()).await;
+    assert_eq!(res.headers().get("x-request-id").unwrap(), "my-request");
+
+    // An id is generated when the client doesn't provide one.
+    let res = test::call_service(&app, test::TestRequest::get().uri("/health").to_request()).await;
+    let request_id = res.headers().get("x-request-id").unwrap().to_str().unwrap();
+    assert!(Uuid::parse_str(request_id).is_ok(), "{}", request_id);
+}
@@ -0,0 +1,97 @@
+use std::num::NonZeroU32;
+
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::test;
+use meilisearch_http::option::RateLimitOpts;
+use meilisearch_http::Opt;
+use serde_json::{json, Value};
+
+use crate::common::{default_settings, Server};
+
+#[actix_rt::test]
+async fn search_over_the_rate_limit() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        rate_limit_options: RateLimitOpts {
+            search_rate_limit: NonZeroU32::new(1),
+            ..RateLimitOpts::default()
+        },
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let app = server.init_web_app().await;
+
+    let search = || test::TestRequest::post().uri("/indexes/test/search").set_json(json!({}));
+    let res = test::call_service(&app, search().to_request()).await;
+    assert_eq!(res.status(), 404);
+
+    let res = test::call_service(&app, search().to_request()).await;
+    assert_eq!(res.status(), 429);
+    assert_eq!(res.headers().get(RETRY_AFTER).unwrap(), "1");
+    let response: Value = test::read_body_json(res).await;
+    assert_eq!(
+        response,
+        json!({
+            "message": "Too many requests. The search routes are limited to 1 requests per second.",
+            "code": "too_many_requests",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#too_many_requests"
+        })
+    );
+
+    // The other groups of routes are not limited.
+    let req = test::TestRequest::get().uri("/indexes/test").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 404);
+    let req = test::TestRequest::get().uri("/health").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 200);
+}
+
+#[actix_rt::test]
+async fn rate_limit_is_per_api_key() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt {
+        master_key: Some(String::from("MASTER_KEY")),
+        rate_limit_options: RateLimitOpts {
+            admin_rate_limit: NonZeroU32::new(1),
+            ..RateLimitOpts::default()
+        },
+        ..default_settings(temp.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let app = server.init_web_app().await;
+
+    let peer_addr = "127.0.0.1:4242".parse().unwrap();
+    let tasks = |key: &str| {
+        test::TestRequest::get()
+            .uri("/tasks")
+            .peer_addr(peer_addr)
+            .insert_header(("Authorization", format!("Bearer {key}")))
+            .to_request()
+    };
+
+    let req = test::TestRequest::post()
+        .uri("/keys")
+        .peer_addr(peer_addr)
+        .insert_header(("Authorization", "Bearer MASTER_KEY"))
+        .set_json(json!({ "actions": ["tasks.get"], "indexes": ["*"], "expiresAt": null }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), 201);
+    let response: Value = test::read_body_json(res).await;
+    let key = response["key"].as_str().unwrap().to_string();
+
+    let res = test::call_service(&app, tasks("MASTER_KEY")).await;
+    assert_eq!(res.status(), 429);
+
+    // Another key has its own limit.
+    let res = test::call_service(&app, tasks(&key)).await;
+    assert_eq!(res.status(), 200);
+
+    // The invalid keys are limited by IP address, so random keys don't bypass the limit.
+    let res = test::call_service(&app, tasks("ANOTHER_KEY")).await;
+    assert_eq!(res.status(), 403);
+    let res = test::call_service(&app, tasks("YET_ANOTHER_KEY")).await;
+    assert_eq!(res.status(), 429);
+}
@@ -0,0 +1,140 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use meilisearch_http::Opt;
+use serde_json::json;
+use tempfile::TempDir;
+
+use crate::common::{default_settings, GetAllDocumentsOptions, Server};
+
+/// Creates a primary instance containing a `test` index and returns the path of its dump.
+async fn primary_dump(dir: &TempDir) -> String {
+    let primary = Server::new_with_options(default_settings(dir.path())).await.unwrap();
+    let index = primary.index("test");
+    index.add_documents(json!([{ "id": 1, "content": "doggos" }]), None).await;
+    index.wait_task(0).await;
+
+    create_dump(&primary, dir).await.display().to_string()
+}
+
+/// Creates a dump of the primary instance stored in `dir` and returns its path.
+async fn create_dump(primary: &Server, dir: &TempDir) -> PathBuf {
+    let (task, code) = primary.create_dump().await;
+    assert_eq!(code, 202, "{}", task);
+    let task = primary.index("test").wait_task(task["taskUid"].as_u64().unwrap()).await;
+    assert_eq!(task["status"], "succeeded", "{}", task);
+
+    let dump_uid = task["details"]["dumpUid"].as_str().unwrap();
+    dir.path().join("dump").join(format!("{dump_uid}.dump"))
+}
+
+#[actix_rt::test]
+async fn replica_serves_the_primary_content() {
+    let primary_dir = TempDir::new().unwrap();
+    let dump = primary_dump(&primary_dir).await;
+
+    let replica_dir = TempDir::new().unwrap();
+    let options = Opt { read_only_replica: Some(dump), ..default_settings(replica_dir.path()) };
+    let replica = Server::new_with_options(options).await.unwrap();
+    let index = replica.index("test");
+
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"], json!([{ "id": 1, "content": "doggos" }]));
+
+    let (response, code) = index.search_post(json!({ "q": "doggo" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 1, "content": "doggos" }]));
+
+    // The similar documents are searched with a POST, which isn't a write.
+    let (response, code) = index.similar(json!({ "id": 1 })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([]));
+}
+
+#[actix_rt::test]
+async fn replica_rejects_the_writes() {
+    let primary_dir = TempDir::new().unwrap();
+    let dump = primary_dump(&primary_dir).await;
+
+    let replica_dir = TempDir::new().unwrap();
+    let options = Opt { read_only_replica: Some(dump), ..default_settings(replica_dir.path()) };
+    let replica = Server::new_with_options(options).await.unwrap();
+    let index = replica.index("test");
+
+    let expected_response = json!({
+        "message": "This instance is a read-only replica. The requests modifying the database must be sent to the primary instance.",
+        "code": "read_only_replica",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#read_only_replica"
+    });
+
+    let (response, code) = index.add_documents(json!([{ "id": 2 }]), None).await;
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 403);
+
+    let (response, code) = index.delete().await;
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 403);
+
+    let (response, code) = replica.create_dump().await;
+    assert_eq!(response, expected_response);
+    assert_eq!(code, 403);
+}
+
+#[actix_rt::test]
+async fn replica_refuses_an_existing_database() {
+    let dir = TempDir::new().unwrap();
+    let primary = Server::new_with_options(default_settings(dir.path())).await.unwrap();
+    drop(primary);
+
+    let options = Opt {
+        read_only_replica: Some(String::from("missing.dump")),
+        ..default_settings(dir.path())
+    };
+    let error = Server::new_with_options(options).await.err().unwrap();
+    assert!(error.to_string().contains("is not a read-only replica"), "{}", error);
+}
+
+#[actix_rt::test]
+async fn replica_serves_the_refreshed_content() {
+    let primary_dir = TempDir::new().unwrap();
+    let primary = Server::new_with_options(default_settings(primary_dir.path())).await.unwrap();
+    let index = primary.index("test");
+    index.add_documents(json!([{ "id": 1, "content": "doggos" }]), None).await;
+    index.wait_task(0).await;
+
+    let source_dir = TempDir::new().unwrap();
+    let source = source_dir.path().join("primary.dump");
+    std::fs::copy(create_dump(&primary, &primary_dir).await, &source).unwrap();
+
+    let replica_dir = TempDir::new().unwrap();
+    let options = Opt {
+        read_only_replica: Some(source.display().to_string()),
+        read_only_replica_refresh_interval_sec: 1,
+        ..default_settings(replica_dir.path())
+    };
+    let replica = Server::new_with_options(options).await.unwrap();
+
+    index.add_documents(json!([{ "id": 2, "content": "catto" }]), None).await;
+    index.wait_task(2).await;
+    std::fs::copy(create_dump(&primary, &primary_dir).await, &source).unwrap();
+
+    let mut documents = json!(null);
+    for _ in 0..60 {
+        let (response, code) =
+            replica.index("test").get_all_documents(GetAllDocumentsOptions::default()).await;
+        assert_eq!(code, 200, "{}", response);
+        documents = response["results"].clone();
+        if documents.as_array().map_or(false, |documents| documents.len() == 2) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    assert_eq!(
+        documents,
+        json!([{ "id": 1, "content": "doggos" }, { "id": 2, "content": "catto" }])
+    );
+    // The first generation is removed once the second one is served.
+    assert!(!replica_dir.path().join("replica-0").exists());
+}
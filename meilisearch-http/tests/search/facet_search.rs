@@ -0,0 +1,92 @@
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+fn documents() -> Value {
+    json!([
+        { "id": 1, "title": "Dune", "genres": ["Science Fiction", "Adventure"], "year": 1965 },
+        { "id": 2, "title": "Hyperion", "genres": ["Science Fiction"], "year": 1989 },
+        { "id": 3, "title": "Emma", "genres": ["Romance"], "year": 1815 },
+        { "id": 4, "title": "Foundation", "genres": ["Science Fiction", "Space Opera"], "year": 1951 },
+        { "id": 5, "title": "Treasure Island", "genres": ["Adventure"], "year": 1883 },
+    ])
+}
+
+#[actix_rt::test]
+async fn facet_search_by_prefix() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["genres", "year"] })).await;
+    index.add_documents(documents(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({ "facetName": "genres", "facetQuery": "sci" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["facetHits"], json!([{ "value": "Science Fiction", "count": 3 }]));
+    assert_eq!(response["facetQuery"], json!("sci"));
+
+    // the values are sorted by count, the most frequent first.
+    let (response, code) = index.facet_search(json!({ "facetName": "genres" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetHits"],
+        json!([
+            { "value": "Science Fiction", "count": 3 },
+            { "value": "Adventure", "count": 2 },
+            { "value": "Romance", "count": 1 },
+            { "value": "Space Opera", "count": 1 },
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn facet_search_honors_the_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["genres", "year"] })).await;
+    index.add_documents(documents(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .facet_search(json!({ "facetName": "genres", "facetQuery": "a", "filter": "year < 1960" }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["facetHits"], json!([{ "value": "Adventure", "count": 1 }]));
+
+    let (response, code) =
+        index.facet_search(json!({ "facetName": "genres", "q": "foundation" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetHits"],
+        json!([
+            { "value": "Science Fiction", "count": 1 },
+            { "value": "Space Opera", "count": 1 },
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn facet_search_on_a_non_filterable_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["genres", "year"] })).await;
+    index.add_documents(documents(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({ "facetName": "title", "facetQuery": "du" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "Attribute `title` is not filterable. Available filterable attributes are: `genres, year`.",
+            "code": "invalid_facet_search_facet_name",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_facet_search_facet_name"
+        })
+    );
+}
@@ -2,8 +2,12 @@
 // should be tested in its own module to isolate tests and keep the tests readable.
 
 mod errors;
+mod facet_search;
 mod formatted;
+mod multi;
 mod pagination;
+mod similar;
+mod time_budget;
 
 use once_cell::sync::Lazy;
 use serde_json::{json, Value};
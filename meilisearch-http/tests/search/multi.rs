@@ -0,0 +1,95 @@
+use serde_json::json;
+
+use crate::common::Server;
+use crate::search::DOCUMENTS;
+
+#[actix_rt::test]
+async fn multi_search_empty_list() {
+    let server = Server::new().await;
+
+    let (response, code) = server.multi_search(json!({"queries": []})).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "results": [] }));
+}
+
+#[actix_rt::test]
+async fn multi_search_several_indexes() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let index = server.index("test2");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+            {"indexUid": "test", "q": "glass"},
+            {"indexUid": "test2", "q": "captain", "limit": 1},
+            {"indexUid": "test", "q": "zzzz"},
+        ]}))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+
+    assert_eq!(results[0]["indexUid"], "test");
+    assert_eq!(results[0]["query"], "glass");
+    assert_eq!(results[0]["hits"], json!([{"title": "Glass", "id": "450465"}]));
+
+    assert_eq!(results[1]["indexUid"], "test2");
+    assert_eq!(results[1]["query"], "captain");
+    assert_eq!(results[1]["limit"], 1);
+    assert_eq!(results[1]["hits"], json!([{"title": "Captain Marvel", "id": "299537"}]));
+
+    assert_eq!(results[2]["indexUid"], "test");
+    assert_eq!(results[2]["hits"], json!([]));
+}
+
+#[actix_rt::test]
+async fn multi_search_failing_query_does_not_fail_the_others() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+            {"indexUid": "test", "q": "glass"},
+            {"indexUid": "nope", "q": "glass"},
+            {"indexUid": "test", "q": "glass", "filter": "title & Glass"},
+        ]}))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+
+    assert_eq!(results[0]["indexUid"], "test");
+    assert_eq!(results[0]["hits"], json!([{"title": "Glass", "id": "450465"}]));
+
+    assert_eq!(results[1]["indexUid"], "nope");
+    assert_eq!(results[1]["error"]["code"], "index_not_found");
+    assert_eq!(results[1]["error"]["message"], "Index `nope` not found.");
+    assert!(results[1].get("hits").is_none());
+
+    assert_eq!(results[2]["indexUid"], "test");
+    assert_eq!(results[2]["error"]["code"], "invalid_filter");
+}
+
+#[actix_rt::test]
+async fn multi_search_bad_payload() {
+    let server = Server::new().await;
+
+    let (response, code) = server.multi_search(json!([{"indexUid": "test"}])).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "bad_request");
+
+    let (response, code) = server.multi_search(json!({"queries": [{"q": "glass"}]})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "bad_request");
+}
@@ -0,0 +1,97 @@
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+fn documents() -> Value {
+    json!([
+        { "id": 1, "title": "Dune", "overview": "A desert planet and its spice" },
+        { "id": 2, "title": "Children of Dune", "overview": "The desert planet after the spice war" },
+        { "id": 3, "title": "Emma", "overview": "A romance in the english countryside" },
+        { "id": 4, "title": "Foundation", "overview": "The fall of a galactic empire" },
+    ])
+}
+
+#[actix_rt::test]
+async fn similar_documents_are_ranked_by_shared_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(documents(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.similar(json!({ "id": 1, "attributesToRetrieve": ["id"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 2 }, { "id": 3 }, { "id": 4 }]));
+    assert_eq!(response["id"], json!("1"));
+    assert_eq!(response["estimatedTotalHits"], json!(3));
+
+    let (response, code) =
+        index.similar(json!({ "id": "1", "limit": 1, "attributesToRetrieve": ["title"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "title": "Children of Dune" }]));
+    assert_eq!(response["limit"], json!(1));
+    assert_eq!(response["estimatedTotalHits"], json!(3));
+}
+
+#[actix_rt::test]
+async fn similar_documents_are_filtered() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["id"] })).await;
+    index.add_documents(documents(), None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .similar(json!({ "id": 1, "filter": "id != 2", "attributesToRetrieve": ["id"] }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 3 }, { "id": 4 }]));
+}
+
+#[actix_rt::test]
+async fn similar_documents_are_bounded_by_the_max_total_hits() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.add_documents(documents(), None).await;
+    index.update_settings(json!({ "pagination": { "maxTotalHits": 2 } })).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.similar(json!({ "id": 1, "limit": 10, "attributesToRetrieve": ["id"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([{ "id": 2 }, { "id": 3 }]));
+    assert_eq!(response["limit"], json!(2));
+    assert_eq!(response["estimatedTotalHits"], json!(2));
+}
+
+#[actix_rt::test]
+async fn similar_errors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.similar(json!({ "id": 1 })).await;
+    assert_eq!(code, 404, "{}", response);
+    assert_eq!(response["code"], json!("index_not_found"));
+
+    index.add_documents(documents(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.similar(json!({ "id": 42 })).await;
+    assert_eq!(code, 404, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "Document `42` not found.",
+            "code": "document_not_found",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#document_not_found"
+        })
+    );
+
+    let (response, code) = index.similar(json!({ "id": true })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response["message"],
+        json!("The document id `true` is invalid. It must be a string or an integer.")
+    );
+    assert_eq!(response["code"], json!("invalid_document_id"));
+}
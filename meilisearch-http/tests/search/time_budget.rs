@@ -0,0 +1,60 @@
+use meilisearch_http::option::SearchOpts;
+use meilisearch_http::Opt;
+use serde_json::json;
+
+use super::DOCUMENTS;
+use crate::common::{default_settings, Server};
+
+async fn server_with_time_budget(temp: &tempfile::TempDir, time_budget_ms: u64) -> Server {
+    let options = Opt {
+        search_options: SearchOpts {
+            search_threads: None,
+            search_time_budget_ms: Some(time_budget_ms),
+        },
+        ..default_settings(temp.path())
+    };
+    Server::new_with_options(options).await.unwrap()
+}
+
+#[actix_rt::test]
+async fn search_exceeding_the_time_budget_before_starting() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = server_with_time_budget(&temp, 0).await;
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "glass" })).await;
+    assert_eq!(code, 503, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "The search could not start within its time budget of 0ms. Too many searches are being processed.",
+            "code": "search_timeout",
+            "type": "internal",
+            "link": "https://docs.meilisearch.com/errors#search_timeout"
+        })
+    );
+
+    let (response, code) =
+        server.multi_search(json!({ "queries": [{ "indexUid": "test", "q": "glass" }] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["results"][0]["error"]["code"], "search_timeout");
+}
+
+#[actix_rt::test]
+async fn search_within_the_time_budget() {
+    let temp = tempfile::tempdir().unwrap();
+    let server = server_with_time_budget(&temp, 60_000).await;
+    let index = server.index("test");
+    index.add_documents(DOCUMENTS.clone(), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index
+        .search_post(json!({ "q": "glass", "attributesToHighlight": ["title"], "facets": ["*"] }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+    assert!(response["hits"][0].get("_formatted").is_some(), "{}", response);
+    assert!(response.get("degraded").is_none(), "{}", response);
+}
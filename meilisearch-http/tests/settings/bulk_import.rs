@@ -0,0 +1,95 @@
+use serde_json::json;
+
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn import_and_export_synonyms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index
+        .update_settings(json!({ "synonyms": { "phone": ["cellphone"], "tv": ["television"] } }))
+        .await;
+    index.wait_task(0).await;
+
+    let lines = r#"
+        {"word": "phone", "synonyms": ["mobile"]}
+        {"word": "phone", "synonyms": ["cellphone"], "operation": "remove"}
+        {"word": "tv", "operation": "remove"}
+        {"word": "car", "synonyms": ["automobile"]}
+    "#;
+    let (response, code) = index.import_setting("synonyms", "application/x-ndjson", lines).await;
+    assert_eq!(code, 202, "{}", response);
+    assert_eq!(response["type"], "settingsImport");
+
+    let task = index.wait_task(1).await;
+    assert_eq!(task["status"], "succeeded", "{}", task);
+    assert_eq!(task["details"], json!({ "addedEntries": 2, "removedEntries": 2 }));
+
+    let (settings, _) = index.settings().await;
+    assert_eq!(settings["synonyms"], json!({ "car": ["automobile"], "phone": ["mobile"] }));
+
+    let (csv, code) = index.export_setting("synonyms", "csv").await;
+    assert_eq!(code, 200);
+    assert_eq!(csv, "word,synonym\ncar,automobile\nphone,mobile\n");
+
+    let (ndjson, code) = index.export_setting("synonyms", "ndjson").await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        ndjson,
+        "{\"word\":\"car\",\"synonyms\":[\"automobile\"]}\n{\"word\":\"phone\",\"synonyms\":[\"mobile\"]}\n"
+    );
+}
+
+#[actix_rt::test]
+async fn import_and_export_stop_words() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "stopWords": ["a", "an"] })).await;
+    index.wait_task(0).await;
+
+    let records = "word,operation\nthe,\nof,add\na,remove\n";
+    let (response, code) = index.import_setting("stop-words", "text/csv", records).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let task = index.wait_task(1).await;
+    assert_eq!(task["status"], "succeeded", "{}", task);
+    assert_eq!(task["details"], json!({ "addedEntries": 2, "removedEntries": 1 }));
+
+    let (settings, _) = index.settings().await;
+    assert_eq!(settings["stopWords"], json!(["an", "of", "the"]));
+
+    let (ndjson, code) = index.export_setting("stop-words", "ndjson").await;
+    assert_eq!(code, 200);
+    assert_eq!(ndjson, "{\"word\":\"an\"}\n{\"word\":\"of\"}\n{\"word\":\"the\"}\n");
+}
+
+#[actix_rt::test]
+async fn error_import_invalid_file() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.import_setting("synonyms", "application/x-ndjson", r#"{"word": ""}"#).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "The entry `1` of the imported file is invalid: the word can't be empty.",
+            "code": "malformed_payload",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#malformed_payload"
+        })
+    );
+
+    let (response, code) = index.import_setting("synonyms", "text/csv", "word\nphone\n").await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response["message"],
+        "The entry `1` of the imported file is invalid: at least one synonym must be added to the word `phone`."
+    );
+
+    let (response, code) =
+        index.import_setting("stop-words", "application/json", r#"["the"]"#).await;
+    assert_eq!(code, 415, "{}", response);
+    assert_eq!(response["code"], "invalid_content_type");
+}
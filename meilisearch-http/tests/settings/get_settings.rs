@@ -81,6 +81,19 @@ async fn error_update_settings_unknown_field() {
     assert_eq!(code, 400);
 }
 
+#[actix_rt::test]
+async fn error_update_settings_payload_size() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    // The JSON payloads are limited to 2 MiB, only the document payloads can be bigger.
+    let stop_words: Vec<_> = (0..300_000).map(|i| format!("word{i}")).collect();
+    let (response, code) = index.update_settings(json!({ "stopWords": stop_words })).await;
+
+    assert_eq!(code, 413, "{}", response);
+    assert_eq!(response["code"], json!("payload_too_large"));
+}
+
 #[actix_rt::test]
 async fn test_partial_update() {
     let server = Server::new().await;
@@ -1,2 +1,3 @@
+mod bulk_import;
 mod distinct;
 mod get_settings;
@@ -1,8 +1,9 @@
+use meilisearch_http::option::Opt;
 use serde_json::json;
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
-use crate::common::Server;
+use crate::common::{default_settings, Server};
 
 #[actix_rt::test]
 async fn get_settings_unexisting_index() {
@@ -73,4 +74,51 @@ async fn stats() {
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["id"], 2);
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["name"], 1);
     assert_eq!(response["indexes"]["test"]["fieldDistribution"]["age"], 1);
+
+    let disk_usage = &response["indexes"]["test"]["diskUsage"];
+    assert!(disk_usage["onDiskSize"].as_u64().unwrap() > 0, "{}", disk_usage);
+    assert!(disk_usage["mapSize"].as_u64().unwrap() > 0, "{}", disk_usage);
+    assert_eq!(disk_usage["maxSize"], json!(null));
+    assert!(disk_usage.get("fragmentation").is_none(), "{}", disk_usage);
+
+    let (response, code) = server.service.get("/stats?fragmentation=true").await;
+    assert_eq!(code, 200, "{}", response);
+    let fragmentation = response["indexes"]["test"]["diskUsage"]["fragmentation"].as_f64().unwrap();
+    assert!((0.0..=100.0).contains(&fragmentation), "{}", fragmentation);
+}
+
+#[actix_rt::test]
+async fn metrics() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (_, code) = index.add_documents(json!([{ "id": 1, "name": "Alexey" }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+    let (_, code) = index.search_post(json!({ "q": "alexey" })).await;
+    assert_eq!(code, 200);
+
+    let (response, code) = server.metrics().await;
+    assert_eq!(code, 200);
+    let response = String::from_utf8(response.to_vec()).unwrap();
+    for metric in [
+        "http_response_time_seconds",
+        "meilisearch_db_size_bytes",
+        "meilisearch_index_docs_count{index=\"test\"} 1",
+        "meilisearch_index_size_bytes{index=\"test\"}",
+        "meilisearch_index_map_size_bytes",
+        "meilisearch_task_queue_length{status=\"enqueued\"}",
+        "meilisearch_search_duration_seconds",
+    ] {
+        assert!(response.contains(metric), "`{metric}` is missing from:\n{response}");
+    }
+}
+
+#[actix_rt::test]
+async fn metrics_route_can_be_disabled() {
+    let temp = tempfile::tempdir().unwrap();
+    let options = Opt { experimental_enable_metrics: false, ..default_settings(temp.path()) };
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let (_, code) = server.metrics().await;
+    assert_eq!(code, 404);
 }
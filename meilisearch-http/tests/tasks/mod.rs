@@ -349,7 +349,7 @@ async fn test_summarized_document_addition_or_update() {
     index.wait_task(0).await;
     let (task, _) = index.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -357,6 +357,7 @@ async fn test_summarized_document_addition_or_update() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -373,7 +374,7 @@ async fn test_summarized_document_addition_or_update() {
     index.wait_task(1).await;
     let (task, _) = index.get_task(1).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 1,
@@ -381,6 +382,7 @@ async fn test_summarized_document_addition_or_update() {
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "receivedDocuments": 1,
         "indexedDocuments": 1
@@ -402,7 +404,7 @@ async fn test_summarized_delete_batch() {
     index.wait_task(0).await;
     let (task, _) = index.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -410,6 +412,7 @@ async fn test_summarized_delete_batch() {
       "status": "failed",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "providedIds": 3,
         "deletedDocuments": 0
@@ -432,7 +435,7 @@ async fn test_summarized_delete_batch() {
     index.wait_task(2).await;
     let (task, _) = index.get_task(2).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 2,
@@ -440,6 +443,7 @@ async fn test_summarized_delete_batch() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "providedIds": 1,
         "deletedDocuments": 0
@@ -461,7 +465,7 @@ async fn test_summarized_delete_document() {
     index.wait_task(0).await;
     let (task, _) = index.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -469,6 +473,7 @@ async fn test_summarized_delete_document() {
       "status": "failed",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "providedIds": 1,
         "deletedDocuments": 0
@@ -491,7 +496,7 @@ async fn test_summarized_delete_document() {
     index.wait_task(2).await;
     let (task, _) = index.get_task(2).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 2,
@@ -499,6 +504,7 @@ async fn test_summarized_delete_document() {
       "status": "succeeded",
       "type": "documentDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "providedIds": 1,
         "deletedDocuments": 0
@@ -522,7 +528,7 @@ async fn test_summarized_settings_update() {
     let (task, _) = index.get_task(0).await;
     dbg!(&task);
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -530,6 +536,7 @@ async fn test_summarized_settings_update() {
       "status": "failed",
       "type": "settingsUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "rankingRules": [
           "custom"
@@ -552,7 +559,7 @@ async fn test_summarized_settings_update() {
     index.wait_task(1).await;
     let (task, _) = index.get_task(1).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 1,
@@ -560,6 +567,7 @@ async fn test_summarized_settings_update() {
       "status": "succeeded",
       "type": "settingsUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "displayedAttributes": [
           "doggos",
@@ -590,7 +598,7 @@ async fn test_summarized_index_creation() {
     index.wait_task(0).await;
     let (task, _) = index.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -598,6 +606,7 @@ async fn test_summarized_index_creation() {
       "status": "succeeded",
       "type": "indexCreation",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "primaryKey": null
       },
@@ -613,7 +622,7 @@ async fn test_summarized_index_creation() {
     index.wait_task(1).await;
     let (task, _) = index.get_task(1).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 1,
@@ -621,6 +630,7 @@ async fn test_summarized_index_creation() {
       "status": "failed",
       "type": "indexCreation",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "primaryKey": "doggos"
       },
@@ -646,7 +656,7 @@ async fn test_summarized_index_deletion() {
     index.wait_task(0).await;
     let (task, _) = index.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -654,6 +664,7 @@ async fn test_summarized_index_deletion() {
       "status": "failed",
       "type": "indexDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "deletedDocuments": 0
       },
@@ -676,7 +687,7 @@ async fn test_summarized_index_deletion() {
     index.wait_task(2).await;
     let (task, _) = index.get_task(2).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 2,
@@ -684,6 +695,7 @@ async fn test_summarized_index_deletion() {
       "status": "succeeded",
       "type": "indexDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "deletedDocuments": 1
       },
@@ -700,7 +712,7 @@ async fn test_summarized_index_deletion() {
     index.wait_task(2).await;
     let (task, _) = index.get_task(2).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 2,
@@ -708,6 +720,7 @@ async fn test_summarized_index_deletion() {
       "status": "succeeded",
       "type": "indexDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "deletedDocuments": 1
       },
@@ -729,7 +742,7 @@ async fn test_summarized_index_update() {
     index.wait_task(0).await;
     let (task, _) = index.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -737,6 +750,7 @@ async fn test_summarized_index_update() {
       "status": "failed",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "primaryKey": null
       },
@@ -757,7 +771,7 @@ async fn test_summarized_index_update() {
     index.wait_task(1).await;
     let (task, _) = index.get_task(1).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 1,
@@ -765,6 +779,7 @@ async fn test_summarized_index_update() {
       "status": "failed",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "primaryKey": "bones"
       },
@@ -788,7 +803,7 @@ async fn test_summarized_index_update() {
     index.wait_task(3).await;
     let (task, _) = index.get_task(3).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 3,
@@ -796,6 +811,7 @@ async fn test_summarized_index_update() {
       "status": "succeeded",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "primaryKey": null
       },
@@ -811,7 +827,7 @@ async fn test_summarized_index_update() {
     index.wait_task(4).await;
     let (task, _) = index.get_task(4).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 4,
@@ -819,6 +835,7 @@ async fn test_summarized_index_update() {
       "status": "succeeded",
       "type": "indexUpdate",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "primaryKey": "bones"
       },
@@ -842,7 +859,7 @@ async fn test_summarized_index_swap() {
     server.wait_task(0).await;
     let (task, _) = server.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -850,6 +867,7 @@ async fn test_summarized_index_swap() {
       "status": "failed",
       "type": "indexSwap",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "swaps": [
           {
@@ -883,7 +901,7 @@ async fn test_summarized_index_swap() {
     server.wait_task(3).await;
     let (task, _) = server.get_task(3).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 3,
@@ -891,6 +909,7 @@ async fn test_summarized_index_swap() {
       "status": "succeeded",
       "type": "indexSwap",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "swaps": [
           {
@@ -921,7 +940,7 @@ async fn test_summarized_task_cancelation() {
     index.wait_task(1).await;
     let (task, _) = index.get_task(1).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 1,
@@ -929,6 +948,7 @@ async fn test_summarized_task_cancelation() {
       "status": "succeeded",
       "type": "taskCancelation",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "matchedTasks": 1,
         "canceledTasks": 0,
@@ -954,7 +974,7 @@ async fn test_summarized_task_deletion() {
     index.wait_task(1).await;
     let (task, _) = index.get_task(1).await;
     assert_json_snapshot!(task, 
-        { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 1,
@@ -962,6 +982,7 @@ async fn test_summarized_task_deletion() {
       "status": "succeeded",
       "type": "taskDeletion",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "matchedTasks": 1,
         "deletedTasks": 1,
@@ -983,7 +1004,7 @@ async fn test_summarized_dump_creation() {
     server.wait_task(0).await;
     let (task, _) = server.get_task(0).await;
     assert_json_snapshot!(task, 
-        { ".details.dumpUid" => "[dumpUid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
+        { ".details.dumpUid" => "[dumpUid]", ".batchUid" => "[batch_uid]", ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" },
         @r###"
     {
       "uid": 0,
@@ -991,6 +1012,7 @@ async fn test_summarized_dump_creation() {
       "status": "succeeded",
       "type": "dumpCreation",
       "canceledBy": null,
+      "batchUid": "[batch_uid]",
       "details": {
         "dumpUid": "[dumpUid]"
       },
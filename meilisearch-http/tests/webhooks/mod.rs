@@ -0,0 +1,91 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+/// Answers the requests received on the listener and sends their body to the channel.
+fn spawn_webhook(listener: TcpListener) -> mpsc::Receiver<Value> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&mut stream);
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_lowercase();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(value) = line.strip_prefix("content-length: ") {
+                    length = value.parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .unwrap();
+            if sender.send(serde_json::from_slice(&body).unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+#[actix_rt::test]
+async fn webhooks_are_configured_at_runtime() {
+    let server = Server::new().await;
+
+    let (response, code) = server.service.get("/webhooks").await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "urls": [], "hasSecret": false }));
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    let received = spawn_webhook(listener);
+
+    let (response, code) =
+        server.service.patch("/webhooks", json!({ "urls": [url], "secret": "secret" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "urls": [url], "hasSecret": true }));
+
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+
+    let task = received.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(task["uid"], 0);
+    assert_eq!(task["type"], "indexCreation");
+    assert_eq!(task["status"], "succeeded");
+
+    // Removing the secret keeps the urls.
+    let (response, code) = server.service.patch("/webhooks", json!({ "secret": null })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response, json!({ "urls": [url], "hasSecret": false }));
+}
+
+#[actix_rt::test]
+async fn error_update_webhooks_invalid_url() {
+    let server = Server::new().await;
+
+    let (response, code) =
+        server.service.patch("/webhooks", json!({ "urls": ["ftp://localhost/"] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "The webhook url `ftp://localhost/` is invalid. It must be an absolute `http` or `https` url.",
+            "code": "invalid_webhook_url",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_webhook_url"
+        })
+    );
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::tasks::TaskId;
+
+pub type BatchId = u32;
+
+/// A set of tasks that were processed together by the index scheduler.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    pub uid: BatchId,
+    /// The tasks processed by the batch, sorted by uid.
+    pub task_uids: Vec<TaskId>,
+    /// The indexes the tasks of the batch were associated with.
+    pub index_uids: Vec<String>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
@@ -1,23 +1,26 @@
 use std::borrow::Borrow;
 use std::fmt::{self, Debug, Display};
 use std::io::{self, BufReader, Read, Seek, Write};
+use std::str::FromStr;
 
 use either::Either;
 use milli::documents::{DocumentsBatchBuilder, Error};
 use milli::Object;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::error::Category;
+use serde_json::Value;
 
-use crate::error::{Code, ErrorCode};
+use crate::error::{Code, ErrorCode, ResponseError};
 use crate::internal_error;
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PayloadType {
     Ndjson,
     Json,
     Csv,
+    MessagePack,
 }
 
 impl fmt::Display for PayloadType {
@@ -26,6 +29,76 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv => f.write_str("csv"),
+            PayloadType::MessagePack => f.write_str("msgpack"),
+        }
+    }
+}
+
+/// The type of the values of a CSV column, declared after a colon in its header (`price:number`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvType {
+    String,
+    Number,
+    Boolean,
+    StringArray,
+    NumberArray,
+}
+
+impl CsvType {
+    /// Converts the value of a cell to JSON, returns `None` if it can't be coerced to the type.
+    ///
+    /// Empty cells are converted to `null` and the items of the arrays are separated by commas.
+    fn parse_value(self, value: &str) -> Option<Value> {
+        let trimmed = value.trim();
+        match self {
+            CsvType::String if value.is_empty() => Some(Value::Null),
+            CsvType::String => Some(Value::String(value.to_string())),
+            _ if trimmed.is_empty() => Some(Value::Null),
+            CsvType::Number => parse_csv_number(trimmed),
+            CsvType::Boolean => trimmed.parse().ok().map(Value::Bool),
+            CsvType::StringArray => Some(
+                trimmed.split(',').map(|item| Value::String(item.trim().to_string())).collect(),
+            ),
+            CsvType::NumberArray => {
+                trimmed.split(',').map(|item| parse_csv_number(item.trim())).collect()
+            }
+        }
+    }
+}
+
+/// Parses the number as an integer when possible, so it can be used as a document id.
+fn parse_csv_number(value: &str) -> Option<Value> {
+    match value.parse::<i64>() {
+        Ok(integer) => Some(Value::from(integer)),
+        Err(_) => {
+            value.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(Value::Number)
+        }
+    }
+}
+
+impl FromStr for CsvType {
+    type Err = ();
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "string" => Ok(CsvType::String),
+            "number" => Ok(CsvType::Number),
+            "boolean" => Ok(CsvType::Boolean),
+            "string[]" => Ok(CsvType::StringArray),
+            "number[]" => Ok(CsvType::NumberArray),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for CsvType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvType::String => f.write_str("string"),
+            CsvType::Number => f.write_str("number"),
+            CsvType::Boolean => f.write_str("boolean"),
+            CsvType::StringArray => f.write_str("string[]"),
+            CsvType::NumberArray => f.write_str("number[]"),
         }
     }
 }
@@ -34,6 +107,8 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Internal(Box<dyn std::error::Error + Send + Sync + 'static>),
     MalformedPayload(Error, PayloadType),
+    MalformedMessagePack(rmp_serde::decode::Error),
+    InvalidCsvValue { line: u64, column: String, type_: CsvType, value: String },
 }
 
 impl Display for DocumentFormatError {
@@ -72,6 +147,21 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            Self::MalformedMessagePack(e) => write!(
+                f,
+                "The `{}` payload provided is malformed: `{}`.",
+                PayloadType::MessagePack,
+                e
+            ),
+            Self::InvalidCsvValue { line, column, type_, value } => write!(
+                f,
+                "The value `{}` of the `{}` column on line {} of the `{}` payload can't be parsed as `{}`.",
+                value,
+                column,
+                line,
+                PayloadType::Csv,
+                type_
+            ),
         }
     }
 }
@@ -92,18 +182,55 @@ impl ErrorCode for DocumentFormatError {
         match self {
             DocumentFormatError::Internal(_) => Code::Internal,
             DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::MalformedMessagePack(_) => Code::MalformedPayload,
+            DocumentFormatError::InvalidCsvValue { .. } => Code::InvalidDocumentCsvValue,
         }
     }
 }
 
 internal_error!(DocumentFormatError: io::Error);
 
+#[derive(Deserialize, Debug)]
+#[serde(transparent)]
+struct ArrayOrSingleObject {
+    #[serde(with = "either::serde_untagged")]
+    inner: Either<Vec<Object>, Object>,
+}
+
 /// Reads CSV from input and write an obkv batch to writer.
-pub fn read_csv(input: impl Read, writer: impl Write + Seek) -> Result<usize> {
+///
+/// The columns are strings by default, a header can declare the type of its column
+/// after a colon: `string`, `number`, `boolean`, `string[]` or `number[]`.
+pub fn read_csv(input: impl Read, writer: impl Write + Seek, delimiter: u8) -> Result<usize> {
     let mut builder = DocumentsBatchBuilder::new(writer);
 
-    let csv = csv::Reader::from_reader(input);
-    builder.append_csv(csv).map_err(|e| (PayloadType::Csv, e))?;
+    let mut csv = csv::ReaderBuilder::new().delimiter(delimiter).from_reader(input);
+    let headers: Vec<_> = csv
+        .headers()
+        .map_err(|e| (PayloadType::Csv, Error::Csv(e)))?
+        .iter()
+        .map(parse_csv_header)
+        .collect();
+
+    let mut record = csv::StringRecord::new();
+    let mut object = Object::new();
+    while csv.read_record(&mut record).map_err(|e| (PayloadType::Csv, Error::Csv(e)))? {
+        object.clear();
+        for ((column, type_), value) in headers.iter().zip(record.iter()) {
+            let json =
+                type_.parse_value(value).ok_or_else(|| DocumentFormatError::InvalidCsvValue {
+                    line: record.position().map_or(0, |position| position.line()),
+                    column: column.clone(),
+                    type_: *type_,
+                    value: value.to_string(),
+                })?;
+            object.insert(column.clone(), json);
+        }
+        builder
+            .append_json_object(&object)
+            .map_err(Into::into)
+            .map_err(DocumentFormatError::Internal)?;
+    }
 
     let count = builder.documents_count();
     let _ = builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Internal)?;
@@ -111,6 +238,19 @@ pub fn read_csv(input: impl Read, writer: impl Write + Seek) -> Result<usize> {
     Ok(count as usize)
 }
 
+/// Splits a CSV header into the name and the type of its column.
+///
+/// A header without a known type after its last colon is entirely used as the name of a string column.
+fn parse_csv_header(header: &str) -> (String, CsvType) {
+    match header.rsplit_once(':') {
+        Some((name, type_)) => match type_.parse() {
+            Ok(type_) => (name.to_string(), type_),
+            Err(()) => (header.to_string(), CsvType::String),
+        },
+        None => (header.to_string(), CsvType::String),
+    }
+}
+
 /// Reads JSON Lines from input and write an obkv batch to writer.
 pub fn read_ndjson(input: impl Read, writer: impl Write + Seek) -> Result<usize> {
     let mut builder = DocumentsBatchBuilder::new(writer);
@@ -130,20 +270,145 @@ pub fn read_ndjson(input: impl Read, writer: impl Write + Seek) -> Result<usize>
     Ok(count as usize)
 }
 
+/// A document of a JSON Lines payload that has been rejected during its ingestion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectedDocument {
+    /// The line of the payload on which the document was, starting from one.
+    pub line: usize,
+    #[serde(flatten)]
+    pub error: ResponseError,
+}
+
+/// The maximum number of rejected documents reported for a JSON Lines payload, as they are
+/// stored in the details of its task.
+pub const MAX_REJECTED_DOCUMENTS: usize = 100;
+
+/// Incrementally reads JSON Lines and write an obkv batch to writer.
+///
+/// The chunks of the payload can be pushed as soon as they are received. Every
+/// complete line is parsed right away and the lines that are not valid JSON objects
+/// are rejected one by one instead of failing the whole payload. Only the first
+/// [`MAX_REJECTED_DOCUMENTS`] rejected documents are reported, but all of them are counted.
+pub struct NdjsonStreamReader<W> {
+    builder: DocumentsBatchBuilder<W>,
+    /// The bytes of the line that hasn't been completely received yet.
+    buffer: Vec<u8>,
+    /// The number of lines that have been read so far.
+    line: usize,
+    rejected: Vec<(usize, DocumentFormatError)>,
+    /// The number of rejected documents, including the ones that are not reported.
+    rejected_count: usize,
+}
+
+impl<W: Write + Seek> NdjsonStreamReader<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            builder: DocumentsBatchBuilder::new(writer),
+            buffer: Vec::new(),
+            line: 0,
+            rejected: Vec::new(),
+            rejected_count: 0,
+        }
+    }
+
+    /// Reads all the complete lines of the chunk and keeps the remaining bytes
+    /// until the end of their line is received.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut start = 0;
+        while let Some(position) = self.buffer[start..].iter().position(|b| *b == b'\n') {
+            let end = start + position;
+            let line = self.buffer[start..end].to_vec();
+            self.read_line(&line)?;
+            start = end + 1;
+        }
+        self.buffer.drain(..start);
+
+        Ok(())
+    }
+
+    /// Reads the last line of the payload and flushes the obkv batch.
+    ///
+    /// Returns the number of documents written, the list of the first rejected documents
+    /// and the total number of rejected documents.
+    /// If not a single document could be read, the first rejection is returned as an error.
+    pub fn finish(mut self) -> Result<(usize, Vec<RejectedDocument>, usize)> {
+        let line = std::mem::take(&mut self.buffer);
+        self.read_line(&line)?;
+
+        let count = self.builder.documents_count();
+        if count == 0 && !self.rejected.is_empty() {
+            let (_line, error) = self.rejected.swap_remove(0);
+            return Err(error);
+        }
+        let _ =
+            self.builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Internal)?;
+
+        let rejected_documents = self
+            .rejected
+            .into_iter()
+            .map(|(line, error)| RejectedDocument { line, error: error.into() })
+            .collect();
+
+        Ok((count as usize, rejected_documents, self.rejected_count))
+    }
+
+    fn read_line(&mut self, line: &[u8]) -> Result<()> {
+        self.line += 1;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(());
+        }
+
+        match serde_json::from_slice::<Object>(line) {
+            Ok(object) => self
+                .builder
+                .append_json_object(&object)
+                .map_err(Into::into)
+                .map_err(DocumentFormatError::Internal),
+            Err(e) => {
+                self.rejected_count += 1;
+                if self.rejected.len() < MAX_REJECTED_DOCUMENTS {
+                    self.rejected.push((self.line, (PayloadType::Ndjson, Error::Json(e)).into()));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Reads JSON from input and write an obkv batch to writer.
 pub fn read_json(input: impl Read, writer: impl Write + Seek) -> Result<usize> {
     let mut builder = DocumentsBatchBuilder::new(writer);
     let reader = BufReader::new(input);
 
-    #[derive(Deserialize, Debug)]
-    #[serde(transparent)]
-    struct ArrayOrSingleObject {
-        #[serde(with = "either::serde_untagged")]
-        inner: Either<Vec<Object>, Object>,
+    let content: ArrayOrSingleObject =
+        serde_json::from_reader(reader).map_err(Error::Json).map_err(|e| (PayloadType::Json, e))?;
+
+    for object in content.inner.map_right(|o| vec![o]).into_inner() {
+        builder
+            .append_json_object(&object)
+            .map_err(Into::into)
+            .map_err(DocumentFormatError::Internal)?;
     }
 
+    let count = builder.documents_count();
+    let _ = builder.into_inner().map_err(Into::into).map_err(DocumentFormatError::Internal)?;
+
+    Ok(count as usize)
+}
+
+/// Reads MessagePack from input and write an obkv batch to writer.
+///
+/// Just like JSON, the payload must either be an array of maps or a single map, and
+/// the maps must only contain string keys and values that can be represented in JSON.
+pub fn read_msgpack(input: impl Read, writer: impl Write + Seek) -> Result<usize> {
+    let mut builder = DocumentsBatchBuilder::new(writer);
+    let reader = BufReader::new(input);
+
     let content: ArrayOrSingleObject =
-        serde_json::from_reader(reader).map_err(Error::Json).map_err(|e| (PayloadType::Json, e))?;
+        rmp_serde::from_read(reader).map_err(DocumentFormatError::MalformedMessagePack)?;
 
     for object in content.inner.map_right(|o| vec![o]).into_inner() {
         builder
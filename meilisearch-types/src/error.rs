@@ -137,6 +137,9 @@ pub enum Code {
     BadParameter,
     BadRequest,
     DatabaseSizeLimitReached,
+    IndexSizeLimitReached,
+    InvalidIndexMaxSize,
+    IndexBeingResized,
     DocumentNotFound,
     Internal,
     InvalidGeoField,
@@ -153,6 +156,8 @@ pub enum Code {
     InvalidTaskCanceledByFilter,
     InvalidTaskUidsFilter,
     TaskNotFound,
+    InvalidBatchUid,
+    BatchNotFound,
     TaskDeletionWithEmptyQuery,
     TaskCancelationWithEmptyQuery,
     PayloadTooLarge,
@@ -162,12 +167,16 @@ pub enum Code {
 
     DumpAlreadyInProgress,
     DumpProcessFailed,
+    InvalidDump,
     // Only used when importing a dump
     UnretrievableErrorCode,
 
     InvalidContentType,
     MissingContentType,
     MalformedPayload,
+    InvalidDocumentCsvDelimiter,
+    InvalidDocumentCsvValue,
+    InvalidDocumentEdition,
     MissingPayload,
 
     ApiKeyNotFound,
@@ -178,8 +187,20 @@ pub enum Code {
     InvalidApiKeyDescription,
     InvalidApiKeyName,
     InvalidApiKeyUid,
+    InvalidApiKeyFilter,
     ImmutableField,
     ApiKeyAlreadyExists,
+
+    TooManyRequests,
+    ReadOnlyReplica,
+
+    InvalidLogStreamLevel,
+
+    InvalidWebhookUrl,
+
+    InvalidFacetSearchFacetName,
+
+    SearchTimeout,
 }
 
 impl Code {
@@ -233,6 +254,15 @@ impl Code {
             DatabaseSizeLimitReached => {
                 ErrCode::internal("database_size_limit_reached", StatusCode::INTERNAL_SERVER_ERROR)
             }
+            IndexSizeLimitReached => {
+                ErrCode::invalid("index_size_limit_reached", StatusCode::BAD_REQUEST)
+            }
+            InvalidIndexMaxSize => {
+                ErrCode::invalid("invalid_index_max_size", StatusCode::BAD_REQUEST)
+            }
+            IndexBeingResized => {
+                ErrCode::internal("index_being_resized", StatusCode::SERVICE_UNAVAILABLE)
+            }
             DocumentNotFound => ErrCode::invalid("document_not_found", StatusCode::NOT_FOUND),
             Internal => ErrCode::internal("internal", StatusCode::INTERNAL_SERVER_ERROR),
             InvalidGeoField => ErrCode::invalid("invalid_geo_field", StatusCode::BAD_REQUEST),
@@ -259,6 +289,8 @@ impl Code {
                 ErrCode::invalid("invalid_task_canceled_by_filter", StatusCode::BAD_REQUEST)
             }
             TaskNotFound => ErrCode::invalid("task_not_found", StatusCode::NOT_FOUND),
+            InvalidBatchUid => ErrCode::invalid("invalid_batch_uid", StatusCode::BAD_REQUEST),
+            BatchNotFound => ErrCode::invalid("batch_not_found", StatusCode::NOT_FOUND),
             TaskDeletionWithEmptyQuery => {
                 ErrCode::invalid("missing_task_filters", StatusCode::BAD_REQUEST)
             }
@@ -285,10 +317,20 @@ impl Code {
             DumpProcessFailed => {
                 ErrCode::internal("dump_process_failed", StatusCode::INTERNAL_SERVER_ERROR)
             }
+            InvalidDump => ErrCode::invalid("invalid_dump", StatusCode::BAD_REQUEST),
             MissingContentType => {
                 ErrCode::invalid("missing_content_type", StatusCode::UNSUPPORTED_MEDIA_TYPE)
             }
             MalformedPayload => ErrCode::invalid("malformed_payload", StatusCode::BAD_REQUEST),
+            InvalidDocumentCsvDelimiter => {
+                ErrCode::invalid("invalid_document_csv_delimiter", StatusCode::BAD_REQUEST)
+            }
+            InvalidDocumentCsvValue => {
+                ErrCode::invalid("invalid_document_csv_value", StatusCode::BAD_REQUEST)
+            }
+            InvalidDocumentEdition => {
+                ErrCode::invalid("invalid_document_edition", StatusCode::BAD_REQUEST)
+            }
             InvalidContentType => {
                 ErrCode::invalid("invalid_content_type", StatusCode::UNSUPPORTED_MEDIA_TYPE)
             }
@@ -315,6 +357,9 @@ impl Code {
             }
             InvalidApiKeyName => ErrCode::invalid("invalid_api_key_name", StatusCode::BAD_REQUEST),
             InvalidApiKeyUid => ErrCode::invalid("invalid_api_key_uid", StatusCode::BAD_REQUEST),
+            InvalidApiKeyFilter => {
+                ErrCode::invalid("invalid_api_key_filter", StatusCode::BAD_REQUEST)
+            }
             ApiKeyAlreadyExists => ErrCode::invalid("api_key_already_exists", StatusCode::CONFLICT),
             ImmutableField => ErrCode::invalid("immutable_field", StatusCode::BAD_REQUEST),
             InvalidMinWordLengthForTypo => {
@@ -323,6 +368,26 @@ impl Code {
             DuplicateIndexFound => {
                 ErrCode::invalid("duplicate_index_found", StatusCode::BAD_REQUEST)
             }
+
+            // error related to the rate limiting
+            TooManyRequests => ErrCode::invalid("too_many_requests", StatusCode::TOO_MANY_REQUESTS),
+            ReadOnlyReplica => ErrCode::invalid("read_only_replica", StatusCode::FORBIDDEN),
+
+            // error related to the logs
+            InvalidLogStreamLevel => {
+                ErrCode::invalid("invalid_log_stream_level", StatusCode::BAD_REQUEST)
+            }
+
+            // error related to the webhooks
+            InvalidWebhookUrl => ErrCode::invalid("invalid_webhook_url", StatusCode::BAD_REQUEST),
+
+            // error related to the facet search
+            InvalidFacetSearchFacetName => {
+                ErrCode::invalid("invalid_facet_search_facet_name", StatusCode::BAD_REQUEST)
+            }
+
+            // error related to the search pool
+            SearchTimeout => ErrCode::internal("search_timeout", StatusCode::SERVICE_UNAVAILABLE),
         }
     }
 
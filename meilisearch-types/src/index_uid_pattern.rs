@@ -0,0 +1,200 @@
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Code, ErrorCode};
+use crate::index_uid::IndexUid;
+
+/// An index uid pattern is composed of only ascii alphanumeric characters, - and _, between 1 and 400
+/// bytes long and optionally ending with a *. The single * pattern matches every index uid.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct IndexUidPattern(String);
+
+impl IndexUidPattern {
+    pub fn new_unchecked(s: impl AsRef<str>) -> Self {
+        Self(s.as_ref().to_string())
+    }
+
+    /// The pattern matching every index uid.
+    pub fn all() -> Self {
+        Self::new_unchecked("*")
+    }
+
+    /// Return `true` if the pattern matches every index uid.
+    pub fn matches_all(&self) -> bool {
+        self.0 == "*"
+    }
+
+    /// Return `true` if the pattern matches a single index uid.
+    pub fn is_exact(&self) -> bool {
+        !self.0.ends_with('*')
+    }
+
+    /// Return `true` if the given index uid matches the pattern.
+    pub fn matches_str(&self, uid: &str) -> bool {
+        match self.0.strip_suffix('*') {
+            Some(prefix) => uid.starts_with(prefix),
+            None => self.0 == uid,
+        }
+    }
+
+    /// Return `true` if every index uid matched by `other` is also matched by `self`.
+    pub fn contains(&self, other: &IndexUidPattern) -> bool {
+        match self.0.strip_suffix('*') {
+            Some(prefix) => other.0.starts_with(prefix),
+            None => self.0 == other.0,
+        }
+    }
+
+    /// Return the pattern matching the index uids matched by both `self` and `other`,
+    /// or `None` if no index uid can be matched by both patterns.
+    pub fn intersection(&self, other: &IndexUidPattern) -> Option<IndexUidPattern> {
+        // The index uids matched by two patterns are either disjoint or included in one another.
+        if self.contains(other) {
+            Some(other.clone())
+        } else if other.contains(self) {
+            Some(self.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Return a reference over the inner str.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for IndexUidPattern {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Borrow<str> for IndexUidPattern {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for IndexUidPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for IndexUidPattern {
+    type Error = IndexUidPatternFormatError;
+
+    fn try_from(uid: String) -> Result<Self, Self::Error> {
+        let valid = match uid.strip_suffix('*') {
+            // The star alone matches every index.
+            Some("") => true,
+            Some(prefix) => IndexUid::from_str(prefix).is_ok(),
+            None => IndexUid::from_str(&uid).is_ok(),
+        };
+
+        if valid {
+            Ok(IndexUidPattern(uid))
+        } else {
+            Err(IndexUidPatternFormatError { invalid_uid: uid })
+        }
+    }
+}
+
+impl FromStr for IndexUidPattern {
+    type Err = IndexUidPatternFormatError;
+
+    fn from_str(uid: &str) -> Result<IndexUidPattern, IndexUidPatternFormatError> {
+        uid.to_string().try_into()
+    }
+}
+
+impl From<IndexUidPattern> for String {
+    fn from(pattern: IndexUidPattern) -> Self {
+        pattern.0
+    }
+}
+
+impl From<IndexUid> for IndexUidPattern {
+    fn from(uid: IndexUid) -> Self {
+        IndexUidPattern(uid.into_inner())
+    }
+}
+
+#[derive(Debug)]
+pub struct IndexUidPatternFormatError {
+    pub invalid_uid: String,
+}
+
+impl fmt::Display for IndexUidPatternFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid index uid pattern. Index uid patterns \
+            can be an integer or a string containing only alphanumeric \
+            characters, hyphens (-), underscores (_), and optionally end with a star (*).",
+            self.invalid_uid,
+        )
+    }
+}
+
+impl Error for IndexUidPatternFormatError {}
+
+impl ErrorCode for IndexUidPatternFormatError {
+    fn error_code(&self) -> Code {
+        Code::InvalidIndexUid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(s: &str) -> IndexUidPattern {
+        IndexUidPattern::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parse_patterns() {
+        assert!(IndexUidPattern::from_str("*").is_ok());
+        assert!(IndexUidPattern::from_str("products").is_ok());
+        assert!(IndexUidPattern::from_str("products-*").is_ok());
+        assert!(IndexUidPattern::from_str("").is_err());
+        assert!(IndexUidPattern::from_str("**").is_err());
+        assert!(IndexUidPattern::from_str("prod*ucts").is_err());
+        assert!(IndexUidPattern::from_str("products *").is_err());
+    }
+
+    #[test]
+    fn match_patterns() {
+        assert!(pattern("*").matches_str("products"));
+        assert!(pattern("products").matches_str("products"));
+        assert!(!pattern("products").matches_str("products-fr"));
+        assert!(pattern("products-*").matches_str("products-fr"));
+        assert!(pattern("products-*").matches_str("products-"));
+        assert!(!pattern("products-*").matches_str("products"));
+    }
+
+    #[test]
+    fn intersect_patterns() {
+        assert_eq!(pattern("*").intersection(&pattern("products-*")), Some(pattern("products-*")));
+        assert_eq!(
+            pattern("products-*").intersection(&pattern("products-fr")),
+            Some(pattern("products-fr"))
+        );
+        assert_eq!(
+            pattern("products-fr*").intersection(&pattern("products-*")),
+            Some(pattern("products-fr*"))
+        );
+        assert_eq!(pattern("products-*").intersection(&pattern("movies")), None);
+        assert_eq!(pattern("products").intersection(&pattern("products-fr")), None);
+    }
+}
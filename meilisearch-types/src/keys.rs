@@ -10,8 +10,7 @@ use time::{Date, OffsetDateTime, PrimitiveDateTime};
 use uuid::Uuid;
 
 use crate::error::{Code, ErrorCode};
-use crate::index_uid::{IndexUid, IndexUidFormatError};
-use crate::star_or::StarOr;
+use crate::index_uid_pattern::{IndexUidPattern, IndexUidPatternFormatError};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -25,7 +24,10 @@ pub struct Key {
     pub name: Option<String>,
     pub uid: KeyId,
     pub actions: Vec<Action>,
-    pub indexes: Vec<StarOr<IndexUid>>,
+    pub indexes: Vec<IndexUidPattern>,
+    /// A filter applied on top of every search made with this key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub expires_at: Option<OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339")]
@@ -70,13 +72,18 @@ impl Key {
                     .map_err(|_| Error::InvalidApiKeyIndexes(ind.clone()))
                     .and_then(|ind| {
                         ind.into_iter()
-                            // If it's not a valid Index uid, return an Index Uid parsing error.
-                            .map(|i| StarOr::<IndexUid>::from_str(&i).map_err(Error::from))
+                            // If it's not a valid Index uid pattern, return an Index Uid parsing error.
+                            .map(|i| IndexUidPattern::from_str(&i).map_err(Error::from))
                             .collect()
                     })
             })
             .ok_or(Error::MissingParameter("indexes"))??;
 
+        let filter = match value.get("filter") {
+            None | Some(Value::Null) => None,
+            Some(filter) => Some(parse_filter(filter)?),
+        };
+
         let expires_at = value
             .get("expiresAt")
             .map(parse_expiration_date)
@@ -85,7 +92,17 @@ impl Key {
         let created_at = OffsetDateTime::now_utc();
         let updated_at = created_at;
 
-        Ok(Self { name, description, uid, actions, indexes, expires_at, created_at, updated_at })
+        Ok(Self {
+            name,
+            description,
+            uid,
+            actions,
+            indexes,
+            filter,
+            expires_at,
+            created_at,
+            updated_at,
+        })
     }
 
     pub fn update_from_value(&mut self, value: Value) -> Result<()> {
@@ -112,6 +129,10 @@ impl Key {
             return Err(Error::ImmutableField("indexes".to_string()));
         }
 
+        if value.get("filter").is_some() {
+            return Err(Error::ImmutableField("filter".to_string()));
+        }
+
         if value.get("expiresAt").is_some() {
             return Err(Error::ImmutableField("expiresAt".to_string()));
         }
@@ -137,7 +158,8 @@ impl Key {
             description: Some("Use it for anything that is not a search operation. Caution! Do not expose it on a public frontend".to_string()),
             uid,
             actions: vec![Action::All],
-            indexes: vec![StarOr::Star],
+            indexes: vec![IndexUidPattern::all()],
+            filter: None,
             expires_at: None,
             created_at: now,
             updated_at: now,
@@ -152,7 +174,8 @@ impl Key {
             description: Some("Use it to search from the frontend".to_string()),
             uid,
             actions: vec![Action::Search],
-            indexes: vec![StarOr::Star],
+            indexes: vec![IndexUidPattern::all()],
+            filter: None,
             expires_at: None,
             created_at: now,
             updated_at: now,
@@ -160,6 +183,24 @@ impl Key {
     }
 }
 
+/// A filter must have the same shape as a search filter: a string or an array of strings
+/// and arrays of strings.
+fn parse_filter(value: &Value) -> Result<Value> {
+    fn is_valid(value: &Value, depth: usize) -> bool {
+        match value {
+            Value::String(_) => true,
+            Value::Array(values) if depth < 2 => values.iter().all(|v| is_valid(v, depth + 1)),
+            _ => false,
+        }
+    }
+
+    if is_valid(value, 0) {
+        Ok(value.clone())
+    } else {
+        Err(Error::InvalidApiKeyFilter(value.clone()))
+    }
+}
+
 fn parse_expiration_date(value: &Value) -> Result<Option<OffsetDateTime>> {
     match value {
         Value::String(string) => OffsetDateTime::parse(string, &Rfc3339)
@@ -262,6 +303,16 @@ pub enum Action {
     KeysUpdate,
     #[serde(rename = "keys.delete")]
     KeysDelete,
+    #[serde(rename = "logs.get")]
+    LogsGet,
+    #[serde(rename = "dumps.import")]
+    DumpsImport,
+    #[serde(rename = "webhooks.get")]
+    WebhooksGet,
+    #[serde(rename = "webhooks.update")]
+    WebhooksUpdate,
+    #[serde(rename = "dumps.get")]
+    DumpsGet,
 }
 
 impl Action {
@@ -298,6 +349,11 @@ impl Action {
             KEYS_GET => Some(Self::KeysGet),
             KEYS_UPDATE => Some(Self::KeysUpdate),
             KEYS_DELETE => Some(Self::KeysDelete),
+            LOGS_GET => Some(Self::LogsGet),
+            DUMPS_IMPORT => Some(Self::DumpsImport),
+            WEBHOOKS_GET => Some(Self::WebhooksGet),
+            WEBHOOKS_UPDATE => Some(Self::WebhooksUpdate),
+            DUMPS_GET => Some(Self::DumpsGet),
             _otherwise => None,
         }
     }
@@ -340,6 +396,11 @@ pub mod actions {
     pub const KEYS_GET: u8 = KeysGet.repr();
     pub const KEYS_UPDATE: u8 = KeysUpdate.repr();
     pub const KEYS_DELETE: u8 = KeysDelete.repr();
+    pub const LOGS_GET: u8 = LogsGet.repr();
+    pub const DUMPS_IMPORT: u8 = DumpsImport.repr();
+    pub const WEBHOOKS_GET: u8 = WebhooksGet.repr();
+    pub const WEBHOOKS_UPDATE: u8 = WebhooksUpdate.repr();
+    pub const DUMPS_GET: u8 = DumpsGet.repr();
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -351,7 +412,9 @@ pub enum Error {
     #[error("`indexes` field value `{0}` is invalid. It should be an array of string representing index names.")]
     InvalidApiKeyIndexes(Value),
     #[error("{0}")]
-    InvalidApiKeyIndexUid(IndexUidFormatError),
+    InvalidApiKeyIndexUid(IndexUidPatternFormatError),
+    #[error("`filter` field value `{0}` is invalid. It should be a string, an array of strings, or an array of arrays of strings representing a search filter, or specified as a null value.")]
+    InvalidApiKeyFilter(Value),
     #[error("`expiresAt` field value `{0}` is invalid. It should follow the RFC 3339 format to represents a date or datetime in the future or specified as a null value. e.g. 'YYYY-MM-DD' or 'YYYY-MM-DD HH:MM:SS'.")]
     InvalidApiKeyExpiresAt(Value),
     #[error("`description` field value `{0}` is invalid. It should be a string or specified as a null value.")]
@@ -366,8 +429,8 @@ pub enum Error {
     ImmutableField(String),
 }
 
-impl From<IndexUidFormatError> for Error {
-    fn from(e: IndexUidFormatError) -> Self {
+impl From<IndexUidPatternFormatError> for Error {
+    fn from(e: IndexUidPatternFormatError) -> Self {
         Self::InvalidApiKeyIndexUid(e)
     }
 }
@@ -384,6 +447,7 @@ impl ErrorCode for Error {
             Self::InvalidApiKeyDescription(_) => Code::InvalidApiKeyDescription,
             Self::InvalidApiKeyName(_) => Code::InvalidApiKeyName,
             Self::InvalidApiKeyUid(_) => Code::InvalidApiKeyUid,
+            Self::InvalidApiKeyFilter(_) => Code::InvalidApiKeyFilter,
             Self::ImmutableField(_) => Code::ImmutableField,
         }
     }
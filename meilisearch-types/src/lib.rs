@@ -1,7 +1,9 @@
+pub mod batches;
 pub mod compression;
 pub mod document_formats;
 pub mod error;
 pub mod index_uid;
+pub mod index_uid_pattern;
 pub mod keys;
 pub mod settings;
 pub mod star_or;
@@ -1,14 +1,17 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
 use enum_iterator::Sequence;
-use milli::update::IndexDocumentsMethod;
+use milli::update::{IndexDocumentsMethod, Setting};
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Number, Value};
 use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
+use crate::batches::BatchId;
+use crate::document_formats::RejectedDocument;
 use crate::error::{Code, ResponseError};
 use crate::keys::Key;
 use crate::settings::{Settings, Unchecked};
@@ -30,6 +33,9 @@ pub struct Task {
 
     pub error: Option<ResponseError>,
     pub canceled_by: Option<TaskId>,
+    /// The batch that processed the task, if any.
+    #[serde(default)]
+    pub batch_uid: Option<BatchId>,
     pub details: Option<Details>,
 
     pub status: Status,
@@ -42,6 +48,7 @@ impl Task {
 
         match &self.kind {
             DumpCreation { .. }
+            | DumpImport { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. }
@@ -49,7 +56,9 @@ impl Task {
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentClear { index_uid }
+            | DocumentEdition { index_uid, .. }
             | SettingsUpdate { index_uid, .. }
+            | SettingsImport { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
             | IndexDeletion { index_uid } => Some(index_uid),
@@ -64,9 +73,12 @@ impl Task {
     /// Return the content-uuid if there is one
     pub fn content_uuid(&self) -> Option<Uuid> {
         match self.kind {
-            KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
+            KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+            | KindWithContent::DumpImport { content_file }
+            | KindWithContent::SettingsImport { content_file, .. } => Some(content_file),
             KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentClear { .. }
+            | KindWithContent::DocumentEdition { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
             | KindWithContent::IndexCreation { .. }
@@ -98,22 +110,38 @@ pub enum KindWithContent {
     DocumentClear {
         index_uid: String,
     },
+    DocumentEdition {
+        index_uid: String,
+        filter_expr: String,
+        edition: DocumentEdition,
+    },
     SettingsUpdate {
         index_uid: String,
         new_settings: Box<Settings<Unchecked>>,
         is_deletion: bool,
         allow_index_creation: bool,
     },
+    /// The [`SettingsDelta`] of the import is stored in the content file.
+    SettingsImport {
+        index_uid: String,
+        content_file: Uuid,
+        added_entries: u64,
+        removed_entries: u64,
+    },
     IndexDeletion {
         index_uid: String,
     },
     IndexCreation {
         index_uid: String,
         primary_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+        max_size: Setting<u64>,
     },
     IndexUpdate {
         index_uid: String,
         primary_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+        max_size: Setting<u64>,
     },
     IndexSwap {
         swaps: Vec<IndexSwap>,
@@ -131,6 +159,9 @@ pub enum KindWithContent {
         instance_uid: Option<InstanceUid>,
     },
     SnapshotCreation,
+    DumpImport {
+        content_file: Uuid,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -139,13 +170,159 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+/// The modifications applied to each document matching the filter of a document edition.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentEdition {
+    /// The fields to set, with their new value.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub set: Map<String, Value>,
+    /// The fields to remove.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unset: Vec<String>,
+    /// The numeric fields to increment, with the value to add to them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub increment: BTreeMap<String, Number>,
+}
+
+impl DocumentEdition {
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty() && self.unset.is_empty() && self.increment.is_empty()
+    }
+
+    /// Returns every field modified by the edition, once per operation modifying it.
+    pub fn fields(&self) -> impl Iterator<Item = &str> {
+        self.set.keys().chain(&self.unset).chain(self.increment.keys()).map(String::as_str)
+    }
+}
+
+/// The changes of a bulk import of synonyms or stop words. They are merged into the settings
+/// of the index when the task is processed: the removals are applied first, then the additions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsDelta {
+    /// The synonyms removed from each word, all of them when `None`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub removed_synonyms: BTreeMap<String, Option<BTreeSet<String>>>,
+    /// The synonyms added to each word.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub added_synonyms: BTreeMap<String, BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub removed_stop_words: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub added_stop_words: BTreeSet<String>,
+}
+
+impl SettingsDelta {
+    /// Adds the synonym to the word, canceling a previous removal of the same synonym.
+    pub fn add_synonym(&mut self, word: String, synonym: String) {
+        if let Some(Some(removed)) = self.removed_synonyms.get_mut(&word) {
+            removed.remove(&synonym);
+        }
+        self.added_synonyms.entry(word).or_default().insert(synonym);
+    }
+
+    /// Removes the synonym from the word, or all the synonyms of the word when `None`,
+    /// canceling the previous additions.
+    pub fn remove_synonym(&mut self, word: String, synonym: Option<String>) {
+        match synonym {
+            Some(synonym) => {
+                if let Some(added) = self.added_synonyms.get_mut(&word) {
+                    added.remove(&synonym);
+                    if added.is_empty() {
+                        self.added_synonyms.remove(&word);
+                    }
+                }
+                // When `None`, all the synonyms of the word are already removed.
+                if let Some(removed) =
+                    self.removed_synonyms.entry(word).or_insert_with(|| Some(BTreeSet::new()))
+                {
+                    removed.insert(synonym);
+                }
+            }
+            None => {
+                self.added_synonyms.remove(&word);
+                self.removed_synonyms.insert(word, None);
+            }
+        }
+    }
+
+    pub fn add_stop_word(&mut self, word: String) {
+        self.removed_stop_words.remove(&word);
+        self.added_stop_words.insert(word);
+    }
+
+    pub fn remove_stop_word(&mut self, word: String) {
+        self.added_stop_words.remove(&word);
+        self.removed_stop_words.insert(word);
+    }
+
+    pub fn modifies_synonyms(&self) -> bool {
+        !self.removed_synonyms.is_empty() || !self.added_synonyms.is_empty()
+    }
+
+    pub fn modifies_stop_words(&self) -> bool {
+        !self.removed_stop_words.is_empty() || !self.added_stop_words.is_empty()
+    }
+
+    /// Returns the number of synonyms and stop words added.
+    pub fn added_entries(&self) -> u64 {
+        let synonyms: usize = self.added_synonyms.values().map(BTreeSet::len).sum();
+        (synonyms + self.added_stop_words.len()) as u64
+    }
+
+    /// Returns the number of synonyms and stop words removed, a word of which all the synonyms
+    /// are removed counts once.
+    pub fn removed_entries(&self) -> u64 {
+        let synonyms: usize = self
+            .removed_synonyms
+            .values()
+            .map(|removed| removed.as_ref().map_or(1, BTreeSet::len))
+            .sum();
+        (synonyms + self.removed_stop_words.len()) as u64
+    }
+
+    pub fn apply_to_synonyms(&self, synonyms: &mut BTreeMap<String, Vec<String>>) {
+        for (word, removed) in &self.removed_synonyms {
+            match removed {
+                Some(removed) => {
+                    if let Some(current) = synonyms.get_mut(word) {
+                        current.retain(|synonym| !removed.contains(synonym));
+                        if current.is_empty() {
+                            synonyms.remove(word);
+                        }
+                    }
+                }
+                None => {
+                    synonyms.remove(word);
+                }
+            }
+        }
+        for (word, added) in &self.added_synonyms {
+            let current = synonyms.entry(word.clone()).or_default();
+            for synonym in added {
+                if !current.contains(synonym) {
+                    current.push(synonym.clone());
+                }
+            }
+        }
+    }
+
+    pub fn apply_to_stop_words(&self, stop_words: &mut BTreeSet<String>) {
+        stop_words.retain(|word| !self.removed_stop_words.contains(word));
+        stop_words.extend(self.added_stop_words.iter().cloned());
+    }
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentEdition { .. } => Kind::DocumentEdition,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
+            KindWithContent::SettingsImport { .. } => Kind::SettingsImport,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
@@ -154,6 +331,7 @@ impl KindWithContent {
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
             KindWithContent::SnapshotCreation => Kind::SnapshotCreation,
+            KindWithContent::DumpImport { .. } => Kind::DumpImport,
         }
     }
 
@@ -162,13 +340,16 @@ impl KindWithContent {
 
         match self {
             DumpCreation { .. }
+            | DumpImport { .. }
             | SnapshotCreation
             | TaskCancelation { .. }
             | TaskDeletion { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentClear { index_uid }
+            | DocumentEdition { index_uid, .. }
             | SettingsUpdate { index_uid, .. }
+            | SettingsImport { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
             | IndexDeletion { index_uid } => vec![index_uid],
@@ -191,6 +372,8 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    rejected_documents: Vec::new(),
+                    rejected_documents_count: 0,
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -202,12 +385,18 @@ impl KindWithContent {
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
+            KindWithContent::DocumentEdition { filter_expr, .. } => {
+                Some(Details::DocumentEdition {
+                    edited_documents: None,
+                    original_filter: filter_expr.clone(),
+                })
+            }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
             }
-            KindWithContent::IndexCreation { primary_key, .. }
-            | KindWithContent::IndexUpdate { primary_key, .. } => {
-                Some(Details::IndexInfo { primary_key: primary_key.clone() })
+            KindWithContent::IndexCreation { primary_key, max_size, .. }
+            | KindWithContent::IndexUpdate { primary_key, max_size, .. } => {
+                Some(Details::IndexInfo { primary_key: primary_key.clone(), max_size: *max_size })
             }
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
@@ -224,6 +413,15 @@ impl KindWithContent {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::DumpImport { .. } => {
+                Some(Details::DumpImport { imported_indexes: None })
+            }
+            KindWithContent::SettingsImport { added_entries, removed_entries, .. } => {
+                Some(Details::SettingsImport {
+                    added_entries: *added_entries,
+                    removed_entries: *removed_entries,
+                })
+            }
         }
     }
 
@@ -233,6 +431,8 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    rejected_documents: Vec::new(),
+                    rejected_documents_count: 0,
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -244,13 +444,19 @@ impl KindWithContent {
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
+            KindWithContent::DocumentEdition { filter_expr, .. } => {
+                Some(Details::DocumentEdition {
+                    edited_documents: Some(0),
+                    original_filter: filter_expr.clone(),
+                })
+            }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
             }
             KindWithContent::IndexDeletion { .. } => None,
-            KindWithContent::IndexCreation { primary_key, .. }
-            | KindWithContent::IndexUpdate { primary_key, .. } => {
-                Some(Details::IndexInfo { primary_key: primary_key.clone() })
+            KindWithContent::IndexCreation { primary_key, max_size, .. }
+            | KindWithContent::IndexUpdate { primary_key, max_size, .. } => {
+                Some(Details::IndexInfo { primary_key: primary_key.clone(), max_size: *max_size })
             }
             KindWithContent::IndexSwap { .. } => {
                 todo!()
@@ -267,6 +473,15 @@ impl KindWithContent {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::DumpImport { .. } => {
+                Some(Details::DumpImport { imported_indexes: Some(0) })
+            }
+            KindWithContent::SettingsImport { added_entries, removed_entries, .. } => {
+                Some(Details::SettingsImport {
+                    added_entries: *added_entries,
+                    removed_entries: *removed_entries,
+                })
+            }
         }
     }
 }
@@ -278,19 +493,25 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    rejected_documents: Vec::new(),
+                    rejected_documents_count: 0,
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
+            KindWithContent::DocumentEdition { filter_expr, .. } => {
+                Some(Details::DocumentEdition {
+                    edited_documents: None,
+                    original_filter: filter_expr.clone(),
+                })
+            }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
             }
             KindWithContent::IndexDeletion { .. } => None,
-            KindWithContent::IndexCreation { primary_key, .. } => {
-                Some(Details::IndexInfo { primary_key: primary_key.clone() })
-            }
-            KindWithContent::IndexUpdate { primary_key, .. } => {
-                Some(Details::IndexInfo { primary_key: primary_key.clone() })
+            KindWithContent::IndexCreation { primary_key, max_size, .. }
+            | KindWithContent::IndexUpdate { primary_key, max_size, .. } => {
+                Some(Details::IndexInfo { primary_key: primary_key.clone(), max_size: *max_size })
             }
             KindWithContent::IndexSwap { .. } => None,
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
@@ -305,6 +526,15 @@ impl From<&KindWithContent> for Option<Details> {
             }),
             KindWithContent::DumpCreation { .. } => Some(Details::Dump { dump_uid: None }),
             KindWithContent::SnapshotCreation => None,
+            KindWithContent::DumpImport { .. } => {
+                Some(Details::DumpImport { imported_indexes: None })
+            }
+            KindWithContent::SettingsImport { added_entries, removed_entries, .. } => {
+                Some(Details::SettingsImport {
+                    added_entries: *added_entries,
+                    removed_entries: *removed_entries,
+                })
+            }
         }
     }
 }
@@ -375,6 +605,10 @@ pub enum Kind {
     TaskDeletion,
     DumpCreation,
     SnapshotCreation,
+    // Stored with bincode in the task queue, the new kinds must be added at the end.
+    DocumentEdition,
+    DumpImport,
+    SettingsImport,
 }
 
 impl Kind {
@@ -382,7 +616,9 @@ impl Kind {
         match self {
             Kind::DocumentAdditionOrUpdate
             | Kind::DocumentDeletion
+            | Kind::DocumentEdition
             | Kind::SettingsUpdate
+            | Kind::SettingsImport
             | Kind::IndexCreation
             | Kind::IndexDeletion
             | Kind::IndexUpdate => true,
@@ -390,6 +626,7 @@ impl Kind {
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
+            | Kind::DumpImport
             | Kind::SnapshotCreation => false,
         }
     }
@@ -399,7 +636,9 @@ impl Display for Kind {
         match self {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
+            Kind::DocumentEdition => write!(f, "documentEdition"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
+            Kind::SettingsImport => write!(f, "settingsImport"),
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
             Kind::IndexUpdate => write!(f, "indexUpdate"),
@@ -407,6 +646,7 @@ impl Display for Kind {
             Kind::TaskCancelation => write!(f, "taskCancelation"),
             Kind::TaskDeletion => write!(f, "taskDeletion"),
             Kind::DumpCreation => write!(f, "dumpCreation"),
+            Kind::DumpImport => write!(f, "dumpImport"),
             Kind::SnapshotCreation => write!(f, "snapshotCreation"),
         }
     }
@@ -427,14 +667,20 @@ impl FromStr for Kind {
             Ok(Kind::DocumentAdditionOrUpdate)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
+        } else if kind.eq_ignore_ascii_case("documentEdition") {
+            Ok(Kind::DocumentEdition)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
             Ok(Kind::SettingsUpdate)
+        } else if kind.eq_ignore_ascii_case("settingsImport") {
+            Ok(Kind::SettingsImport)
         } else if kind.eq_ignore_ascii_case("taskCancelation") {
             Ok(Kind::TaskCancelation)
         } else if kind.eq_ignore_ascii_case("taskDeletion") {
             Ok(Kind::TaskDeletion)
         } else if kind.eq_ignore_ascii_case("dumpCreation") {
             Ok(Kind::DumpCreation)
+        } else if kind.eq_ignore_ascii_case("dumpImport") {
+            Ok(Kind::DumpImport)
         } else if kind.eq_ignore_ascii_case("snapshotCreation") {
             Ok(Kind::SnapshotCreation)
         } else {
@@ -458,16 +704,33 @@ impl FromStr for Kind {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[rustfmt::skip]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rejected_documents: Vec<RejectedDocument>,
+        /// The number of rejected documents, which can be more than the rejected documents
+        /// reported above.
+        #[serde(default)]
+        rejected_documents_count: u64,
+    },
     SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
+    SettingsImport { added_entries: u64, removed_entries: u64 },
+    IndexInfo {
+        primary_key: Option<String>,
+        #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+        max_size: Setting<u64>,
+    },
     DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
     ClearAll { deleted_documents: Option<u64> },
+    DocumentEdition { edited_documents: Option<u64>, original_filter: String },
     TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
     TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
     Dump { dump_uid: Option<String> },
     IndexSwap { swaps: Vec<IndexSwap> },
+    DumpImport { imported_indexes: Option<u64> },
 }
 
 impl Details {
@@ -479,9 +742,12 @@ impl Details {
             }
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
+            Self::DocumentEdition { edited_documents, .. } => *edited_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
+            Self::DumpImport { imported_indexes } => *imported_indexes = Some(0),
             Self::SettingsUpdate { .. }
+            | Self::SettingsImport { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
             | Self::IndexSwap { .. } => (),
@@ -544,7 +810,9 @@ pub fn serialize_duration<S: Serializer>(
 
 #[cfg(test)]
 mod tests {
-    use super::Details;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use super::{Details, SettingsDelta};
     use crate::heed::types::SerdeJson;
     use crate::heed::{BytesDecode, BytesEncode};
 
@@ -560,4 +828,31 @@ mod tests {
         meili_snap::snapshot!(format!("{:?}", details), @r###"TaskDeletion { matched_tasks: 1, deleted_tasks: None, original_filter: "hello" }"###);
         meili_snap::snapshot!(format!("{:?}", deserialised), @r###"TaskDeletion { matched_tasks: 1, deleted_tasks: None, original_filter: "hello" }"###);
     }
+
+    #[test]
+    fn settings_delta_merging() {
+        let mut delta = SettingsDelta::default();
+        delta.add_synonym("phone".to_owned(), "mobile".to_owned());
+        delta.remove_synonym("phone".to_owned(), Some("cellphone".to_owned()));
+        delta.remove_synonym("tv".to_owned(), None);
+        delta.add_synonym("tv".to_owned(), "television".to_owned());
+        delta.add_synonym("car".to_owned(), "auto".to_owned());
+        delta.remove_synonym("car".to_owned(), Some("auto".to_owned()));
+        delta.add_stop_word("the".to_owned());
+        delta.remove_stop_word("a".to_owned());
+        assert_eq!(delta.added_entries(), 3);
+        assert_eq!(delta.removed_entries(), 4);
+
+        let mut synonyms = BTreeMap::from([
+            ("phone".to_owned(), vec!["cellphone".to_owned(), "smartphone".to_owned()]),
+            ("tv".to_owned(), vec!["tube".to_owned()]),
+            ("car".to_owned(), vec!["auto".to_owned()]),
+        ]);
+        delta.apply_to_synonyms(&mut synonyms);
+        meili_snap::snapshot!(format!("{:?}", synonyms), @r###"{"phone": ["smartphone", "mobile"], "tv": ["television"]}"###);
+
+        let mut stop_words = BTreeSet::from(["a".to_owned(), "of".to_owned()]);
+        delta.apply_to_stop_words(&mut stop_words);
+        meili_snap::snapshot!(format!("{:?}", stop_words), @r###"{"of", "the"}"###);
+    }
 }